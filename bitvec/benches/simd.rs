@@ -0,0 +1,71 @@
+//! Bulk operations over the aligned interior of large bit-slices.
+//!
+//! Run these with and without `--features simd` to compare the vectorized and
+//! portable implementations.
+
+#![feature(test)]
+
+extern crate test;
+
+use bitvec::prelude::*;
+use test::{
+	Bencher,
+	black_box,
+};
+
+/// The number of bits in each benchmarked bit-slice.
+const LEN: usize = 1 << 16;
+
+#[bench]
+fn count_ones(bench: &mut Bencher) {
+	let bits = bitvec![usize, Lsb0; 1; LEN];
+	bench.iter(|| black_box(&bits[3 ..]).count_ones());
+}
+
+#[bench]
+fn count_zeros(bench: &mut Bencher) {
+	let bits = bitvec![usize, Lsb0; 1; LEN];
+	bench.iter(|| black_box(&bits[3 ..]).count_zeros());
+}
+
+#[bench]
+fn first_one(bench: &mut Bencher) {
+	let mut bits = bitvec![usize, Lsb0; 0; LEN];
+	bits.set(LEN - 2, true);
+	bench.iter(|| black_box(&bits[1 ..]).first_one());
+}
+
+#[bench]
+fn last_one(bench: &mut Bencher) {
+	let mut bits = bitvec![usize, Msb0; 0; LEN];
+	bits.set(1, true);
+	bench.iter(|| black_box(&bits[.. LEN - 1]).last_one());
+}
+
+#[bench]
+fn first_zero(bench: &mut Bencher) {
+	let mut bits = bitvec![u8, Msb0; 1; LEN];
+	bits.set(LEN - 2, false);
+	bench.iter(|| black_box(&bits[1 ..]).first_zero());
+}
+
+#[bench]
+fn eq(bench: &mut Bencher) {
+	let a = bitvec![usize, Lsb0; 1; LEN];
+	let b = a.clone();
+	bench.iter(|| black_box(&a[5 ..]) == black_box(&b[5 ..]));
+}
+
+#[bench]
+fn bitand_assign(bench: &mut Bencher) {
+	let mut a = bitvec![usize, Lsb0; 1; LEN];
+	let b = bitvec![usize, Lsb0; 1; LEN];
+	bench.iter(|| *black_box(&mut a[7 ..]) &= black_box(&b[7 ..]));
+}
+
+#[bench]
+fn bitxor_assign(bench: &mut Bencher) {
+	let mut a = bitvec![u8, Msb0; 1; LEN];
+	let b = bitvec![u8, Msb0; 0; LEN];
+	bench.iter(|| *black_box(&mut a[7 ..]) ^= black_box(&b[7 ..]));
+}
@@ -246,6 +246,9 @@ safe!(u64 => BitSafeU64 => radium::types::RadiumU64);
 
 safe!(usize => BitSafeUsize => radium::types::RadiumUsize);
 
+#[cfg(feature = "u128")]
+safe!(u128 => BitSafeU128 => radium::types::RadiumU128);
+
 #[cfg(test)]
 mod tests {
 	use core::cell::Cell;
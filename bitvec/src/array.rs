@@ -8,11 +8,17 @@ use crate::{
 		BitOrder,
 		Lsb0,
 	},
-	slice::BitSlice,
+	slice::{
+		parse::Syntax,
+		BitSlice,
+		ParseBitsError,
+	},
+	store::BitStore,
 	view::BitViewSized,
 };
 
 mod api;
+mod constant;
 mod iter;
 mod ops;
 mod tests;
@@ -56,8 +62,44 @@ where
 	/// assert_eq!(bits.len(), 64);
 	/// ```
 	#[inline]
-	pub fn new(data: A) -> Self {
-		Self { data, ..Self::ZERO }
+	pub const fn new(data: A) -> Self {
+		Self {
+			_ord: PhantomData,
+			data,
+		}
+	}
+
+	/// Parses the text that a numeric formatter writes, in a given radix.
+	///
+	/// This accepts the same text as [`TryFrom<&str>`], except that numbers
+	/// without a prefix are read in `radix`, as `{:o}` and `{:x}` write them,
+	/// rather than in binary.
+	///
+	/// ## Panics
+	///
+	/// This panics if `radix` is not 2, 8, or 16.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bits = bitarr![u16, Lsb0; 1, 0, 1, 1, 0, 0, 1, 0];
+	/// let text = format!("{:x}", bits);
+	/// assert_eq!(BitArray::from_str_radix(&text, 16), Ok(bits));
+	/// ```
+	///
+	/// [`TryFrom<&str>`]: core::convert::TryFrom
+	#[inline]
+	pub fn from_str_radix(
+		src: &str,
+		radix: u32,
+	) -> Result<Self, ParseBitsError> {
+		Self::parse_text(
+			src,
+			Syntax::new(mem::bits_of::<<A::Store as BitStore>::Mem>())
+				.radix(radix),
+		)
 	}
 
 	/// Removes the bit-array wrapper, returning the contained buffer.
@@ -88,9 +130,19 @@ where
 	}
 
 	/// Views the bit-array as a slice of its underlying memory elements.
+	///
+	/// This method can be called in `const` contexts.
 	#[inline]
-	pub fn as_raw_slice(&self) -> &[A::Store] {
-		self.data.as_raw_slice()
+	pub const fn as_raw_slice(&self) -> &[A::Store] {
+		let elts = mem::elts::<A::Store>(mem::bits_of::<A>());
+		//  `A` is either one `A::Store` element or an array of them, so it is
+		//  a contiguous run of `elts` elements.
+		unsafe {
+			core::slice::from_raw_parts(
+				(&self.data as *const A).cast::<A::Store>(),
+				elts,
+			)
+		}
 	}
 
 	/// Views the bit-array as a mutable slice of its underlying memory
@@ -104,7 +156,7 @@ where
 	///
 	/// This method is a compile-time constant.
 	#[inline]
-	pub fn len(&self) -> usize {
+	pub const fn len(&self) -> usize {
 		mem::bits_of::<A>()
 	}
 
@@ -112,7 +164,7 @@ where
 	///
 	/// This method is a compile-time constant.
 	#[inline]
-	pub fn is_empty(&self) -> bool {
+	pub const fn is_empty(&self) -> bool {
 		mem::bits_of::<A>() == 0
 	}
 }
@@ -0,0 +1,192 @@
+#![doc = include_str!("../../doc/array/constant.md")]
+
+use super::BitArray;
+use crate::order::{
+	Lsb0,
+	Msb0,
+};
+
+/// Implements the `const` bit-array API for arrays of the given integers,
+/// under both of the provided orderings.
+macro_rules! constant {
+	($($t:ident),+ $(,)?) => { $(
+		constant!(@ $t, Lsb0, |bit| bit);
+		constant!(@ $t, Msb0, |bit| <$t>::BITS as usize - 1 - bit);
+	)+ };
+	(@ $t:ident, $ord:ident, |$bit:ident| $pos:expr) => {
+		impl<const N: usize> BitArray<[$t; N], $ord> {
+			/// Reads the bit at `index`.
+			///
+			/// This is usable in `const` contexts. Outside of them, indexing
+			/// or [`BitSlice::get`] is equivalent.
+			///
+			/// ## Panics
+			///
+			/// This panics if `index` is not less than `self.len()`. In a
+			/// `const` context, this is a compilation error.
+			///
+			/// [`BitSlice::get`]: crate::slice::BitSlice::get
+			#[inline]
+			pub const fn bit(&self, index: usize) -> bool {
+				self.data[index / <$t>::BITS as usize] & Self::mask(index) != 0
+			}
+
+			/// Produces a copy of the bit-array with the bit at `index` set to
+			/// `value`.
+			///
+			/// This is the `const` counterpart of [`BitSlice::set`]. It takes
+			/// and returns the bit-array by value, so that calls can be chained
+			/// to build a constant.
+			///
+			/// ## Panics
+			///
+			/// This panics if `index` is not less than `self.len()`. In a
+			/// `const` context, this is a compilation error.
+			///
+			/// [`BitSlice::set`]: crate::slice::BitSlice::set
+			#[inline]
+			#[must_use = "this returns a new bit-array, leaving `self` intact"]
+			pub const fn with_bit(self, index: usize, value: bool) -> Self {
+				let mut data = self.data;
+				let elem = index / <$t>::BITS as usize;
+				if value {
+					data[elem] |= Self::mask(index);
+				}
+				else {
+					data[elem] &= !Self::mask(index);
+				}
+				Self::new(data)
+			}
+
+			/// Counts the number of bits set to `1` in the bit-array.
+			#[inline]
+			pub const fn count_ones(&self) -> usize {
+				let mut out = 0;
+				let mut idx = 0;
+				while idx < N {
+					out += self.data[idx].count_ones() as usize;
+					idx += 1;
+				}
+				out
+			}
+
+			/// Counts the number of bits cleared to `0` in the bit-array.
+			#[inline]
+			pub const fn count_zeros(&self) -> usize {
+				self.len() - self.count_ones()
+			}
+
+			/// Tests if at least one bit in the bit-array is set to `1`.
+			#[inline]
+			pub const fn any(&self) -> bool {
+				let mut idx = 0;
+				while idx < N {
+					if self.data[idx] != 0 {
+						return true;
+					}
+					idx += 1;
+				}
+				false
+			}
+
+			/// Tests if every bit in the bit-array is set to `1`.
+			///
+			/// An empty bit-array returns `true`.
+			#[inline]
+			pub const fn all(&self) -> bool {
+				let mut idx = 0;
+				while idx < N {
+					if self.data[idx] != !0 {
+						return false;
+					}
+					idx += 1;
+				}
+				true
+			}
+
+			/// Tests if every bit in the bit-array is cleared to `0`.
+			#[inline]
+			pub const fn not_any(&self) -> bool {
+				!self.any()
+			}
+
+			/// Tests if at least one bit in the bit-array is cleared to `0`.
+			#[inline]
+			pub const fn not_all(&self) -> bool {
+				!self.all()
+			}
+
+			/// Computes the Boolean AND of two bit-arrays.
+			///
+			/// This is the `const` counterpart of the `&` operator.
+			#[inline]
+			#[must_use]
+			pub const fn and(self, rhs: Self) -> Self {
+				let mut data = self.data;
+				let mut idx = 0;
+				while idx < N {
+					data[idx] &= rhs.data[idx];
+					idx += 1;
+				}
+				Self::new(data)
+			}
+
+			/// Computes the Boolean OR of two bit-arrays.
+			///
+			/// This is the `const` counterpart of the `|` operator.
+			#[inline]
+			#[must_use]
+			pub const fn or(self, rhs: Self) -> Self {
+				let mut data = self.data;
+				let mut idx = 0;
+				while idx < N {
+					data[idx] |= rhs.data[idx];
+					idx += 1;
+				}
+				Self::new(data)
+			}
+
+			/// Computes the Boolean XOR of two bit-arrays.
+			///
+			/// This is the `const` counterpart of the `^` operator.
+			#[inline]
+			#[must_use]
+			pub const fn xor(self, rhs: Self) -> Self {
+				let mut data = self.data;
+				let mut idx = 0;
+				while idx < N {
+					data[idx] ^= rhs.data[idx];
+					idx += 1;
+				}
+				Self::new(data)
+			}
+
+			/// Inverts every bit in the bit-array.
+			///
+			/// This is the `const` counterpart of the `!` operator.
+			#[inline]
+			#[must_use]
+			pub const fn invert(self) -> Self {
+				let mut data = self.data;
+				let mut idx = 0;
+				while idx < N {
+					data[idx] = !data[idx];
+					idx += 1;
+				}
+				Self::new(data)
+			}
+
+			/// Computes the element mask that selects the bit at `index`.
+			#[inline]
+			const fn mask(index: usize) -> $t {
+				let $bit = index % <$t>::BITS as usize;
+				1 << $pos
+			}
+		}
+	};
+}
+
+constant!(u8, u16, u32, u64, usize);
+
+#[cfg(feature = "u128")]
+constant!(u128);
@@ -12,6 +12,10 @@ use core::ops::{
 	Index,
 	IndexMut,
 	Not,
+	Shl,
+	ShlAssign,
+	Shr,
+	ShrAssign,
 };
 
 use super::BitArray;
@@ -240,3 +244,65 @@ where
 		self
 	}
 }
+
+#[cfg(not(tarpaulin_include))]
+impl<A, O> Shl<usize> for BitArray<A, O>
+where
+	A: BitViewSized,
+	O: BitOrder,
+{
+	type Output = Self;
+
+	#[inline]
+	fn shl(mut self, rhs: usize) -> Self::Output {
+		self <<= rhs;
+		self
+	}
+}
+
+/// Shifts the contents of the bit-array towards the zero index.
+///
+/// This is [`BitSlice::shift_start`], except that shifting by the length of
+/// the bit-array or more clears it rather than panicking.
+impl<A, O> ShlAssign<usize> for BitArray<A, O>
+where
+	A: BitViewSized,
+	O: BitOrder,
+{
+	#[inline]
+	fn shl_assign(&mut self, rhs: usize) {
+		let bits = self.as_mut_bitslice();
+		bits.shift_start(rhs.min(bits.len()));
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<A, O> Shr<usize> for BitArray<A, O>
+where
+	A: BitViewSized,
+	O: BitOrder,
+{
+	type Output = Self;
+
+	#[inline]
+	fn shr(mut self, rhs: usize) -> Self::Output {
+		self >>= rhs;
+		self
+	}
+}
+
+/// Shifts the contents of the bit-array away from the zero index.
+///
+/// This is [`BitSlice::shift_end`], except that shifting by the length of
+/// the bit-array or more clears it rather than panicking.
+impl<A, O> ShrAssign<usize> for BitArray<A, O>
+where
+	A: BitViewSized,
+	O: BitOrder,
+{
+	#[inline]
+	fn shr_assign(&mut self, rhs: usize) {
+		let bits = self.as_mut_bitslice();
+		bits.shift_end(rhs.min(bits.len()));
+	}
+}
@@ -91,10 +91,67 @@ fn ops() {
 	let mut f = !e;
 	assert_eq!(f[.. 4], bitarr![1, 0, 0, 1][.. 4]);
 
+	let g = bitarr![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0];
+	assert_eq!(g << 3, bitarr![u8, Msb0; 1, 0, 0, 1, 0, 0, 0, 0]);
+	assert_eq!(g >> 3, bitarr![u8, Msb0; 0, 0, 0, 1, 0, 1, 1, 0]);
+	assert_eq!(g << 8, bitarr![u8, Msb0; 0; 8]);
+	assert_eq!(g >> 20, bitarr![u8, Msb0; 0; 8]);
+
 	let _: &BitSlice = &a;
 	let _: &mut BitSlice = &mut f;
 }
 
+#[test]
+fn const_api() {
+	type Mask = BitArray<[u8; 2], Msb0>;
+	const MASK: Mask =
+		Mask::ZERO.with_bit(0, true).with_bit(9, true).with_bit(15, true);
+	const ONES: usize = MASK.count_ones();
+	const FLIP: Mask = MASK.invert().with_bit(1, true);
+	assert_eq!(MASK.data, [0x80, 0x41]);
+	assert_eq!(ONES, 3);
+	assert_eq!(FLIP.data, [0x7F, 0xBE]);
+	assert!(MASK.bit(9) && !MASK.bit(10));
+	assert_eq!(MASK.or(FLIP), Mask::new([!0; 2]));
+	assert!(MASK.and(FLIP).not_any());
+
+	const RAW: (usize, u8) =
+		(MASK.as_raw_slice().len(), FLIP.as_raw_slice()[1]);
+	const ONE: u16 = BitArray::<u16, Lsb0>::new(0x1234).as_raw_slice()[0];
+	assert_eq!(RAW, (2, 0xBE));
+	assert_eq!(ONE, 0x1234);
+
+	macro_rules! check {
+		($($t:ty),+) => { $(
+			let a = BitArray::<[$t; 3], Lsb0>::new(rand::random());
+			let b = BitArray::<[$t; 3], Lsb0>::new(rand::random());
+			check!(@ a, b);
+			let a = BitArray::<[$t; 3], Msb0>::new(rand::random());
+			let b = BitArray::<[$t; 3], Msb0>::new(rand::random());
+			check!(@ a, b);
+		)+ };
+		(@ $a:ident, $b:ident) => {
+			for idx in 0 .. $a.len() {
+				assert_eq!($a.bit(idx), $a[idx]);
+				let mut c = $a;
+				c.set(idx, !$a[idx]);
+				assert_eq!($a.with_bit(idx, !$a[idx]), c);
+			}
+			assert_eq!($a.count_ones(), $a.as_bitslice().count_ones());
+			assert_eq!($a.count_zeros(), $a.as_bitslice().count_zeros());
+			assert_eq!($a.any(), $a.as_bitslice().any());
+			assert_eq!($a.not_all(), $a.as_bitslice().not_all());
+			assert_eq!($a.and($b), $a & $b);
+			assert_eq!($a.or($b), $a | $b);
+			assert_eq!($a.xor($b), $a ^ $b);
+			assert_eq!($a.invert(), !$a);
+			assert!($a.or($a.invert()).all());
+			assert!($a.and($a.invert()).not_any());
+		};
+	}
+	check!(u8, u16, u32, u64, usize);
+}
+
 #[test]
 fn traits() {
 	let a = BitArray::<[Cell<u16>; 3], Msb0>::default();
@@ -27,7 +27,15 @@ use crate::{
 	index::BitIdx,
 	mem,
 	order::BitOrder,
-	slice::BitSlice,
+	slice::{
+		parse::{
+			self,
+			Syntax,
+		},
+		BitSlice,
+		ParseBitsError,
+		ParseBitsErrorKind,
+	},
 	store::BitStore,
 	view::BitViewSized,
 };
@@ -234,6 +242,61 @@ where
 	}
 }
 
+#[doc = include_str!("../../doc/slice/from_str.md")]
+///
+/// ## Bit-Array Length
+///
+/// The text must contain exactly as many bits as the bit-array does, which is
+/// every bit of its underlying storage.
+impl<A, O> TryFrom<&str> for BitArray<A, O>
+where
+	A: BitViewSized,
+	O: BitOrder,
+{
+	type Error = ParseBitsError;
+
+	#[inline]
+	fn try_from(src: &str) -> Result<Self, Self::Error> {
+		Self::parse_text(
+			src,
+			Syntax::new(mem::bits_of::<<A::Store as BitStore>::Mem>()),
+		)
+	}
+}
+
+impl<A, O> BitArray<A, O>
+where
+	A: BitViewSized,
+	O: BitOrder,
+{
+	/// Parses text that must hold exactly every bit of the bit-array.
+	pub(crate) fn parse_text(
+		src: &str,
+		syntax: Syntax,
+	) -> Result<Self, ParseBitsError> {
+		let mut out = Self::ZERO;
+		let expected = out.len();
+		let mut actual = 0;
+		let mut overflow = None;
+		parse::parse(src, syntax.whole(), |bit, pos| {
+			match out.get_mut(actual) {
+				Some(mut slot) => *slot = bit,
+				None => {
+					overflow.get_or_insert(pos);
+				},
+			}
+			actual += 1;
+		})?;
+		if actual != expected {
+			return Err(ParseBitsError::new(
+				ParseBitsErrorKind::Length { actual, expected },
+				overflow.unwrap_or(src.len()),
+			));
+		}
+		Ok(out)
+	}
+}
+
 impl<A, O> Default for BitArray<A, O>
 where
 	A: BitViewSized,
@@ -0,0 +1,454 @@
+#![cfg(target_has_atomic = "ptr")]
+#![doc = include_str!("../doc/atomic.md")]
+
+use core::{
+	cmp,
+	fmt::{
+		self,
+		Debug,
+		Formatter,
+	},
+	ops::Deref,
+	slice,
+	sync::atomic::{
+		AtomicUsize,
+		Ordering,
+	},
+};
+
+use funty::Integral;
+use radium::Radium;
+
+use crate::{
+	array::BitArray,
+	index::BitIdx,
+	mem::{
+		BitRegister,
+		bits_of,
+	},
+	order::{
+		BitOrder,
+		Lsb0,
+	},
+	slice::BitSlice,
+	store::BitStore,
+	view::BitViewSized,
+};
+#[cfg(feature = "alloc")]
+use crate::{
+	boxed::BitBox,
+	vec::BitVec,
+};
+
+mod tests;
+
+/// Generates the shared-access bit operations for a wrapper type.
+///
+/// The wrapper must provide an `as_bitslice` method that views its contents.
+macro_rules! atomic_ops {
+	() => {
+		/// Reads the bit at `index`.
+		///
+		/// `order` must be valid for an atomic load.
+		///
+		/// ## Panics
+		///
+		/// This panics if `index` is out of bounds, or if `order` is `Release`
+		/// or `AcqRel`.
+		#[inline]
+		pub fn test(&self, index: usize, order: Ordering) -> bool {
+			fetch(self.as_bitslice(), index, |elem, _| elem.load(order))
+		}
+
+		/// Sets the bit at `index` to `1`.
+		///
+		/// ## Panics
+		///
+		/// This panics if `index` is out of bounds.
+		#[inline]
+		pub fn set(&self, index: usize, order: Ordering) {
+			self.test_and_set(index, order);
+		}
+
+		/// Clears the bit at `index` to `0`.
+		///
+		/// ## Panics
+		///
+		/// This panics if `index` is out of bounds.
+		#[inline]
+		pub fn clear(&self, index: usize, order: Ordering) {
+			self.test_and_clear(index, order);
+		}
+
+		/// Sets the bit at `index` to `1`, returning its previous value.
+		///
+		/// Exactly one of any number of concurrent calls on a cleared bit
+		/// observes `false`, so this can be used to claim a bit.
+		///
+		/// ## Panics
+		///
+		/// This panics if `index` is out of bounds.
+		#[inline]
+		pub fn test_and_set(&self, index: usize, order: Ordering) -> bool {
+			fetch(self.as_bitslice(), index, |elem, sel| {
+				elem.fetch_or(sel, order)
+			})
+		}
+
+		/// Clears the bit at `index` to `0`, returning its previous value.
+		///
+		/// Exactly one of any number of concurrent calls on a set bit observes
+		/// `true`, so this can be used to release a bit.
+		///
+		/// ## Panics
+		///
+		/// This panics if `index` is out of bounds.
+		#[inline]
+		pub fn test_and_clear(&self, index: usize, order: Ordering) -> bool {
+			fetch(self.as_bitslice(), index, |elem, sel| {
+				elem.fetch_and(!sel, order)
+			})
+		}
+
+		/// Inverts the bit at `index`, returning its previous value.
+		///
+		/// ## Panics
+		///
+		/// This panics if `index` is out of bounds.
+		#[inline]
+		pub fn fetch_toggle(&self, index: usize, order: Ordering) -> bool {
+			fetch(self.as_bitslice(), index, |elem, sel| {
+				elem.fetch_xor(sel, order)
+			})
+		}
+
+		/// Finds the first bit cleared to `0`, sets it to `1`, and returns its
+		/// index.
+		///
+		/// This is wait-free: each memory element is read once, and then
+		/// receives at most one read-modify-write for each of its bits. Each
+		/// failed attempt to claim a bit learns the element’s new value from
+		/// the attempt itself, and moves on to the next bit that is still `0`.
+		///
+		/// `order` applies to the read-modify-write that claims the bit. When
+		/// this returns `Some`, the bit was claimed by this call and no other.
+		///
+		/// ## Returns
+		///
+		/// The index of the claimed bit, or `None` if every bit was observed
+		/// to be `1`. Bits that other threads clear while the search is in
+		/// progress may not be found.
+		#[inline]
+		pub fn find_first_zero_and_set(&self, order: Ordering) -> Option<usize> {
+			find_first_zero_and_set(self.as_bitslice(), order)
+		}
+	};
+}
+
+#[doc = include_str!("../doc/atomic/AtomicBitArray.md")]
+#[repr(transparent)]
+pub struct AtomicBitArray<A = [AtomicUsize; 1], O = Lsb0>
+where
+	A: BitViewSized,
+	O: BitOrder,
+{
+	/// The bit-array that holds the atomic elements.
+	inner: BitArray<A, O>,
+}
+
+impl<A, O> AtomicBitArray<A, O>
+where
+	A: BitViewSized,
+	A::Store: Radium<Item = <A::Store as BitStore>::Mem> + Sync,
+	O: BitOrder,
+{
+	/// A bit-array with all bits cleared to `0`.
+	pub const ZERO: Self = Self {
+		inner: BitArray::ZERO,
+	};
+
+	atomic_ops!();
+
+	/// Wraps an existing buffer of atomic elements.
+	#[inline]
+	pub fn new(data: A) -> Self {
+		Self {
+			inner: BitArray::new(data),
+		}
+	}
+
+	/// Removes the wrapper, returning the bit-array.
+	#[inline]
+	pub fn into_inner(self) -> BitArray<A, O> {
+		self.inner
+	}
+
+	/// Views the bits as a bit-slice.
+	///
+	/// The bit-slice can be read with all of the usual `BitSlice` methods,
+	/// which use `Relaxed` loads.
+	#[inline]
+	pub fn as_bitslice(&self) -> &BitSlice<A::Store, O> {
+		self.inner.as_bitslice()
+	}
+
+	/// Views the underlying atomic elements.
+	#[inline]
+	pub fn as_raw_slice(&self) -> &[A::Store] {
+		self.inner.as_raw_slice()
+	}
+}
+
+#[doc = include_str!("../doc/atomic/AtomicBitVec.md")]
+#[cfg(feature = "alloc")]
+#[repr(transparent)]
+pub struct AtomicBitVec<T = AtomicUsize, O = Lsb0>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// The bit-box that holds the atomic elements.
+	inner: BitBox<T, O>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T, O> AtomicBitVec<T, O>
+where
+	T: BitStore + Radium<Item = <T as BitStore>::Mem> + Sync,
+	O: BitOrder,
+{
+	atomic_ops!();
+
+	/// Allocates a bit-vector of `len` bits, all cleared to `0`.
+	#[inline]
+	pub fn new(len: usize) -> Self {
+		Self::repeat(false, len)
+	}
+
+	/// Allocates a bit-vector of `len` bits, all set to `bit`.
+	#[inline]
+	pub fn repeat(bit: bool, len: usize) -> Self {
+		Self {
+			inner: BitVec::repeat(bit, len).into_boxed_bitslice(),
+		}
+	}
+
+	/// Removes the wrapper, returning the bit-box.
+	#[inline]
+	pub fn into_inner(self) -> BitBox<T, O> {
+		self.inner
+	}
+
+	/// Views the bits as a bit-slice.
+	///
+	/// The bit-slice can be read with all of the usual `BitSlice` methods,
+	/// which use `Relaxed` loads.
+	#[inline]
+	pub fn as_bitslice(&self) -> &BitSlice<T, O> {
+		self.inner.as_bitslice()
+	}
+
+	/// Views the underlying atomic elements.
+	#[inline]
+	pub fn as_raw_slice(&self) -> &[T] {
+		self.inner.as_raw_slice()
+	}
+}
+
+impl<A, O> Default for AtomicBitArray<A, O>
+where
+	A: BitViewSized,
+	A::Store: Radium<Item = <A::Store as BitStore>::Mem> + Sync,
+	O: BitOrder,
+{
+	#[inline]
+	fn default() -> Self {
+		Self::ZERO
+	}
+}
+
+impl<A, O> Deref for AtomicBitArray<A, O>
+where
+	A: BitViewSized,
+	A::Store: Radium<Item = <A::Store as BitStore>::Mem> + Sync,
+	O: BitOrder,
+{
+	type Target = BitSlice<A::Store, O>;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		self.as_bitslice()
+	}
+}
+
+impl<A, O> From<BitArray<A, O>> for AtomicBitArray<A, O>
+where
+	A: BitViewSized,
+	A::Store: Radium<Item = <A::Store as BitStore>::Mem> + Sync,
+	O: BitOrder,
+{
+	#[inline]
+	fn from(inner: BitArray<A, O>) -> Self {
+		Self { inner }
+	}
+}
+
+impl<A, O> Debug for AtomicBitArray<A, O>
+where
+	A: BitViewSized,
+	A::Store: Radium<Item = <A::Store as BitStore>::Mem> + Sync,
+	O: BitOrder,
+{
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		Debug::fmt(self.as_bitslice(), fmt)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T, O> Deref for AtomicBitVec<T, O>
+where
+	T: BitStore + Radium<Item = <T as BitStore>::Mem> + Sync,
+	O: BitOrder,
+{
+	type Target = BitSlice<T, O>;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		self.as_bitslice()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T, O> From<BitBox<T, O>> for AtomicBitVec<T, O>
+where
+	T: BitStore + Radium<Item = <T as BitStore>::Mem> + Sync,
+	O: BitOrder,
+{
+	#[inline]
+	fn from(inner: BitBox<T, O>) -> Self {
+		Self { inner }
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T, O> From<BitVec<T, O>> for AtomicBitVec<T, O>
+where
+	T: BitStore + Radium<Item = <T as BitStore>::Mem> + Sync,
+	O: BitOrder,
+{
+	#[inline]
+	fn from(bv: BitVec<T, O>) -> Self {
+		bv.into_boxed_bitslice().into()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T, O> Debug for AtomicBitVec<T, O>
+where
+	T: BitStore + Radium<Item = <T as BitStore>::Mem> + Sync,
+	O: BitOrder,
+{
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		Debug::fmt(self.as_bitslice(), fmt)
+	}
+}
+
+/// Views the memory elements underlying a bit-slice.
+fn elements<T, O>(bits: &BitSlice<T, O>) -> &[T]
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	let span = bits.as_bitspan();
+	unsafe { slice::from_raw_parts(span.address().to_const(), span.elements()) }
+}
+
+/// Locates the memory element that holds a bit, and the mask that selects it.
+///
+/// ## Panics
+///
+/// This panics if `index` is out of bounds.
+fn locate<T, O>(bits: &BitSlice<T, O>, index: usize) -> (&T, T::Mem)
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	bits.assert_in_bounds(index, 0 .. bits.len());
+	let pos = bits.as_bitspan().head().into_inner() as usize + index;
+	let width = bits_of::<T::Mem>();
+	(
+		&elements(bits)[pos / width],
+		select::<T::Mem, O>(pos % width),
+	)
+}
+
+/// Applies an atomic operation to the element that holds a bit, and tests
+/// that bit in the value it returns.
+///
+/// `op` receives the element and the mask that selects the bit.
+///
+/// ## Panics
+///
+/// This panics if `index` is out of bounds.
+fn fetch<T, O>(
+	bits: &BitSlice<T, O>,
+	index: usize,
+	op: impl FnOnce(&T, T::Mem) -> T::Mem,
+) -> bool
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	let (elem, sel) = locate(bits, index);
+	op(elem, sel) & sel != <T::Mem as Integral>::ZERO
+}
+
+/// Produces the mask that selects a bit within a memory element.
+fn select<M, O>(bit: usize) -> M
+where
+	M: BitRegister,
+	O: BitOrder,
+{
+	unsafe { BitIdx::<M>::new_unchecked(bit as u8) }
+		.select::<O>()
+		.into_inner()
+}
+
+/// Claims the first bit in a bit-slice that is cleared to `0`.
+///
+/// See [`AtomicBitArray::find_first_zero_and_set`].
+fn find_first_zero_and_set<T, O>(
+	bits: &BitSlice<T, O>,
+	order: Ordering,
+) -> Option<usize>
+where
+	T: BitStore + Radium<Item = <T as BitStore>::Mem>,
+	O: BitOrder,
+{
+	let zero = <T::Mem as Integral>::ZERO;
+	let width = bits_of::<T::Mem>();
+	let head = bits.as_bitspan().head().into_inner() as usize;
+	let end = head + bits.len();
+	for (idx, elem) in elements(bits).iter().enumerate() {
+		let base = idx * width;
+		let live = head.saturating_sub(base) .. cmp::min(width, end - base);
+		let mut seen = elem.load(Ordering::Relaxed);
+		for _ in live.clone() {
+			let bit = match live
+				.clone()
+				.find(|&bit| seen & select::<T::Mem, O>(bit) == zero)
+			{
+				Some(bit) => bit,
+				None => break,
+			};
+			let sel = select::<T::Mem, O>(bit);
+			seen = elem.fetch_or(sel, order);
+			if seen & sel == zero {
+				return Some(base + bit - head);
+			}
+		}
+	}
+	None
+}
@@ -0,0 +1,108 @@
+//! Unit tests for shared atomic bit-sets.
+
+#![cfg(test)]
+
+use core::sync::atomic::{
+	AtomicU8,
+	AtomicU16,
+	Ordering::*,
+};
+
+use super::*;
+use crate::order::Msb0;
+
+#[test]
+fn single_bits() {
+	let bits = AtomicBitArray::<[AtomicU8; 3], Msb0>::ZERO;
+	assert!(!bits.test_and_set(9, AcqRel));
+	assert!(bits.test_and_set(9, AcqRel));
+	assert!(bits.test(9, Acquire));
+	assert_eq!(bits.as_raw_slice()[1].load(Relaxed), 0x40);
+
+	assert!(bits.fetch_toggle(9, AcqRel));
+	assert!(!bits.fetch_toggle(23, AcqRel));
+	assert_eq!(bits.as_raw_slice()[2].load(Relaxed), 0x01);
+
+	bits.set(0, Release);
+	assert!(bits.test_and_clear(0, AcqRel));
+	assert!(!bits.test_and_clear(0, AcqRel));
+	bits.clear(23, Release);
+	assert!(bits.not_any());
+
+	let data = bits.into_inner().into_inner();
+	assert_eq!(data.map(AtomicU8::into_inner), [0; 3]);
+}
+
+#[test]
+#[should_panic]
+fn out_of_bounds() {
+	AtomicBitArray::<[AtomicU8; 1]>::ZERO.set(8, Relaxed);
+}
+
+#[test]
+fn claiming() {
+	let bits = AtomicBitArray::<[AtomicU16; 2]>::ZERO;
+	for idx in 0 .. 32 {
+		assert_eq!(bits.find_first_zero_and_set(AcqRel), Some(idx));
+	}
+	assert_eq!(bits.find_first_zero_and_set(AcqRel), None);
+	bits.clear(20, Release);
+	bits.clear(5, Release);
+	assert_eq!(bits.find_first_zero_and_set(AcqRel), Some(5));
+	assert_eq!(bits.find_first_zero_and_set(AcqRel), Some(20));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn claiming_partial_slices() {
+	let bv = BitVec::<AtomicU8, Msb0>::repeat(true, 40);
+	let bits = AtomicBitVec::from(bv);
+	bits.clear(2, Release);
+	bits.clear(39, Release);
+	assert_eq!(bits.find_first_zero_and_set(AcqRel), Some(2));
+	assert_eq!(bits.find_first_zero_and_set(AcqRel), Some(39));
+	assert_eq!(bits.find_first_zero_and_set(AcqRel), None);
+
+	//  The search is confined to the live bits of a bit-box that starts and
+	//  ends partway through its elements.
+	let mut bb =
+		BitVec::<AtomicU8, Msb0>::repeat(false, 20).into_boxed_bitslice();
+	bb[.. 3].fill(true);
+	bb[17 ..].fill(true);
+	let bits = AtomicBitVec::from(bb);
+	assert_eq!(bits.len(), 20);
+	for idx in 3 .. 17 {
+		assert_eq!(bits.find_first_zero_and_set(AcqRel), Some(idx));
+	}
+	assert_eq!(bits.find_first_zero_and_set(AcqRel), None);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn contention() {
+	use std::{
+		sync::Arc,
+		thread,
+	};
+
+	let slots: Arc<AtomicBitVec> = Arc::new(AtomicBitVec::new(1000));
+	let handles = (0 .. 8)
+		.map(|_| {
+			let slots = Arc::clone(&slots);
+			thread::spawn(move || {
+				let mut mine = Vec::new();
+				while let Some(idx) = slots.find_first_zero_and_set(AcqRel) {
+					mine.push(idx);
+				}
+				mine
+			})
+		})
+		.collect::<Vec<_>>();
+	let mut claimed = handles
+		.into_iter()
+		.flat_map(|h| h.join().unwrap())
+		.collect::<Vec<_>>();
+	claimed.sort_unstable();
+	assert!(claimed.into_iter().eq(0 .. 1000));
+	assert!(slots.all());
+}
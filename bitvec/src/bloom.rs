@@ -0,0 +1,687 @@
+#![cfg(feature = "alloc")]
+#![doc = include_str!("../doc/bloom.md")]
+
+#[cfg(all(target_pointer_width = "64", target_has_atomic = "64"))]
+use core::sync::atomic::AtomicU64;
+use core::{
+	fmt::{
+		self,
+		Debug,
+		Formatter,
+	},
+	hash::{
+		BuildHasher,
+		Hash,
+		Hasher,
+	},
+};
+
+#[cfg(all(target_pointer_width = "64", target_has_atomic = "64"))]
+use crate::{
+	access::BitAccess,
+	boxed::BitBox,
+	index::BitIdx,
+};
+use crate::{
+	field::BitField,
+	order::Lsb0,
+	slice::BitSlice,
+	vec::BitVec,
+};
+
+mod tests;
+
+#[doc = include_str!("../doc/bloom/BloomFilter.md")]
+#[derive(Clone)]
+pub struct BloomFilter<H> {
+	/// The filter bits.
+	bits:   BitVec<usize, Lsb0>,
+	/// The number of bits that each item sets.
+	hashes: u32,
+	/// Hashes items into probe sequences.
+	hasher: H,
+}
+
+impl<H> BloomFilter<H>
+where H: BuildHasher
+{
+	/// Creates an empty filter sized to hold `items` items with a false
+	/// positive rate of at most `rate`.
+	///
+	/// This chooses the number of bits, `m = -n·ln(p) / ln(2)²`, and the number
+	/// of hashes, `k = (m / n)·ln(2)`, that minimize the false-positive rate
+	/// for `n` items. Inserting more than `items` items still works, but the
+	/// false-positive rate rises above `rate`.
+	///
+	/// ## Parameters
+	///
+	/// - `items`: The number of items that the filter is expected to hold.
+	/// - `rate`: The acceptable false-positive rate, between `0` and `1`.
+	/// - `hasher`: Hashes items into probe sequences. Filters can only be
+	///   combined, or stored and reloaded, if they hash identically.
+	///
+	/// ## Panics
+	///
+	/// This panics if `rate` is not strictly between `0` and `1`, or if the
+	/// filter would be too large to allocate.
+	#[inline]
+	#[cfg(feature = "std")]
+	pub fn with_rate(items: usize, rate: f64, hasher: H) -> Self {
+		let (bits, hashes) = dimensions(items, rate);
+		Self::with_size(bits, hashes, hasher)
+	}
+
+	/// Creates an empty filter of exactly `bits` bits, which sets `hashes`
+	/// bits for each item.
+	///
+	/// ## Panics
+	///
+	/// This panics if `bits` or `hashes` is zero, or if `bits` is too large to
+	/// allocate.
+	#[inline]
+	pub fn with_size(bits: usize, hashes: u32, hasher: H) -> Self {
+		check_size(bits, hashes);
+		Self {
+			bits: BitVec::repeat(false, bits),
+			hashes,
+			hasher,
+		}
+	}
+
+	/// Adds an item to the filter.
+	///
+	/// ## Returns
+	///
+	/// `true` if the item was not already in the filter. A `false` return
+	/// may be a false positive, so it does not prove that the item was
+	/// inserted before.
+	#[inline]
+	pub fn insert<T>(&mut self, item: &T) -> bool
+	where T: Hash + ?Sized {
+		let mut added = false;
+		for idx in self.probes(item) {
+			added |= !self.bits.replace(idx, true);
+		}
+		added
+	}
+
+	/// Tests if an item may be in the filter.
+	///
+	/// ## Returns
+	///
+	/// `false` if the item was definitely never inserted, and `true` if it
+	/// probably was.
+	#[inline]
+	pub fn contains<T>(&self, item: &T) -> bool
+	where T: Hash + ?Sized {
+		self.probes(item).all(|idx| self.bits[idx])
+	}
+
+	/// Hashes an item into the indices of the bits that it sets.
+	fn probes<T>(&self, item: &T) -> Probes
+	where T: Hash + ?Sized {
+		Probes::new(&self.hasher, item, self.hashes, self.bits.len())
+	}
+}
+
+impl<H> BloomFilter<H> {
+	/// Gets the number of bits that each item sets.
+	#[inline]
+	pub fn hashes(&self) -> u32 {
+		self.hashes
+	}
+
+	/// Gets the hasher that produces probe sequences.
+	#[inline]
+	pub fn hasher(&self) -> &H {
+		&self.hasher
+	}
+
+	/// Views the filter bits.
+	#[inline]
+	pub fn as_bitslice(&self) -> &BitSlice<usize, Lsb0> {
+		self.bits.as_bitslice()
+	}
+
+	/// Tests if no item has been inserted into the filter.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.bits.not_any()
+	}
+
+	/// Removes all items from the filter.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.bits.fill(false);
+	}
+
+	/// Estimates the number of distinct items in the filter from the number of
+	/// bits that are set.
+	///
+	/// This uses the estimate `n ≈ -(m / k)·ln(1 - X / m)` by Swamidass and
+	/// Baldi, where `X` is the number of set bits. It is accurate while the
+	/// filter holds no more items than it was sized for, and becomes infinite
+	/// once every bit is set.
+	#[inline]
+	#[cfg(feature = "std")]
+	pub fn estimate_len(&self) -> f64 {
+		estimate(self.bits.len(), self.bits.count_ones(), self.hashes)
+	}
+
+	/// Adds every item of `other` into `self`.
+	///
+	/// Afterwards, `self` is exactly the filter that inserting both sets of
+	/// items would have produced.
+	///
+	/// ## Panics
+	///
+	/// This panics if the filters differ in size or number of hashes. The
+	/// hashers cannot be compared, so the caller must ensure that they hash
+	/// identically.
+	#[inline]
+	pub fn union(&mut self, other: &Self) {
+		self.check_shape(other);
+		*self.bits.as_mut_bitslice() |= other.bits.as_bitslice();
+	}
+
+	/// Keeps only the bits that are set in both `self` and `other`.
+	///
+	/// Afterwards, `self` contains every item that was inserted into both
+	/// filters. Its false-positive rate may be higher than that of a filter
+	/// built from only the common items, as bits set by different items in
+	/// each filter can coincide.
+	///
+	/// ## Panics
+	///
+	/// This panics if the filters differ in size or number of hashes. The
+	/// hashers cannot be compared, so the caller must ensure that they hash
+	/// identically.
+	#[inline]
+	pub fn intersection(&mut self, other: &Self) {
+		self.check_shape(other);
+		*self.bits.as_mut_bitslice() &= other.bits.as_bitslice();
+	}
+
+	/// Asserts that two filters can be combined.
+	fn check_shape(&self, other: &Self) {
+		assert!(
+			self.bits.len() == other.bits.len() && self.hashes == other.hashes,
+			"cannot combine a bloom filter of {} bits and {} hashes with one \
+			 of {} bits and {} hashes",
+			self.bits.len(),
+			self.hashes,
+			other.bits.len(),
+			other.hashes,
+		);
+	}
+
+	/// Assembles a filter from its parts, without checking them.
+	#[cfg(feature = "serde")]
+	pub(crate) fn from_parts(
+		bits: BitVec<usize, Lsb0>,
+		hashes: u32,
+		hasher: H,
+	) -> Self {
+		Self {
+			bits,
+			hashes,
+			hasher,
+		}
+	}
+}
+
+impl<H> Debug for BloomFilter<H> {
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_struct("BloomFilter")
+			.field("bits", &self.bits.len())
+			.field("hashes", &self.hashes)
+			.field("ones", &self.bits.count_ones())
+			.finish()
+	}
+}
+
+#[doc = include_str!("../doc/bloom/CountingBloomFilter.md")]
+#[derive(Clone)]
+pub struct CountingBloomFilter<H> {
+	/// The filter counters, each `COUNTER` bits wide.
+	counters: BitVec<usize, Lsb0>,
+	/// The number of counters that each item increments.
+	hashes:   u32,
+	/// Hashes items into probe sequences.
+	hasher:   H,
+}
+
+/// The width of each counter in a counting filter.
+const COUNTER: usize = 4;
+
+/// The value at which a counter saturates.
+const SATURATED: u8 = (1 << COUNTER) - 1;
+
+impl<H> CountingBloomFilter<H>
+where H: BuildHasher
+{
+	/// Creates an empty filter sized to hold `items` items with a false
+	/// positive rate of at most `rate`.
+	///
+	/// This uses one counter wherever [`BloomFilter::with_rate`] would use one
+	/// bit, so the filter is four times as large.
+	///
+	/// ## Panics
+	///
+	/// This panics if `rate` is not strictly between `0` and `1`, or if the
+	/// filter would be too large to allocate.
+	#[inline]
+	#[cfg(feature = "std")]
+	pub fn with_rate(items: usize, rate: f64, hasher: H) -> Self {
+		let (counters, hashes) = dimensions(items, rate);
+		Self::with_size(counters, hashes, hasher)
+	}
+
+	/// Creates an empty filter of exactly `counters` counters, which
+	/// increments `hashes` counters for each item.
+	///
+	/// ## Panics
+	///
+	/// This panics if `counters` or `hashes` is zero, or if `counters` is too
+	/// large to allocate.
+	#[inline]
+	pub fn with_size(counters: usize, hashes: u32, hasher: H) -> Self {
+		check_size(counters, hashes);
+		let bits = counters
+			.checked_mul(COUNTER)
+			.expect("counting bloom filter is too large to allocate");
+		Self {
+			counters: BitVec::repeat(false, bits),
+			hashes,
+			hasher,
+		}
+	}
+
+	/// Adds an item to the filter.
+	///
+	/// Each of the item’s counters is incremented, unless it has saturated.
+	///
+	/// ## Returns
+	///
+	/// `true` if the item was not already in the filter. A `false` return
+	/// may be a false positive, so it does not prove that the item was
+	/// inserted before.
+	#[inline]
+	pub fn insert<T>(&mut self, item: &T) -> bool
+	where T: Hash + ?Sized {
+		let mut added = false;
+		for idx in self.probes(item) {
+			let count = self.count(idx);
+			added |= count == 0;
+			if count < SATURATED {
+				self.set_count(idx, count + 1);
+			}
+		}
+		added
+	}
+
+	/// Removes an item from the filter.
+	///
+	/// Each of the item’s counters is decremented, unless it has saturated. A
+	/// saturated counter no longer knows how many items it counts, so it stays
+	/// saturated rather than risk dropping to zero while other items still
+	/// use it.
+	///
+	/// Removing an item that was never inserted, but is a false positive,
+	/// corrupts the filter: it may then forget items that were inserted.
+	///
+	/// ## Returns
+	///
+	/// `true` if the item may have been in the filter, and was removed;
+	/// `false` if it was definitely absent, in which case the filter is
+	/// unchanged.
+	#[inline]
+	pub fn remove<T>(&mut self, item: &T) -> bool
+	where T: Hash + ?Sized {
+		if !self.contains(item) {
+			return false;
+		}
+		for idx in self.probes(item) {
+			let count = self.count(idx);
+			//  Repeated probes may have already taken a counter to zero.
+			if count > 0 && count < SATURATED {
+				self.set_count(idx, count - 1);
+			}
+		}
+		true
+	}
+
+	/// Tests if an item may be in the filter.
+	///
+	/// ## Returns
+	///
+	/// `false` if the item was definitely never inserted, or has been removed,
+	/// and `true` if it probably is present.
+	#[inline]
+	pub fn contains<T>(&self, item: &T) -> bool
+	where T: Hash + ?Sized {
+		self.probes(item).all(|idx| self.count(idx) > 0)
+	}
+
+	/// Hashes an item into the indices of the counters that it increments.
+	fn probes<T>(&self, item: &T) -> Probes
+	where T: Hash + ?Sized {
+		Probes::new(&self.hasher, item, self.hashes, self.counters())
+	}
+}
+
+impl<H> CountingBloomFilter<H> {
+	/// Gets the number of counters in the filter.
+	#[inline]
+	pub fn counters(&self) -> usize {
+		self.counters.len() / COUNTER
+	}
+
+	/// Gets the number of counters that each item increments.
+	#[inline]
+	pub fn hashes(&self) -> u32 {
+		self.hashes
+	}
+
+	/// Gets the hasher that produces probe sequences.
+	#[inline]
+	pub fn hasher(&self) -> &H {
+		&self.hasher
+	}
+
+	/// Reads a counter.
+	///
+	/// ## Panics
+	///
+	/// This panics if `idx` is not less than [`counters`].
+	///
+	/// [`counters`]: Self::counters
+	#[inline]
+	pub fn count(&self, idx: usize) -> u8 {
+		self.counters[idx * COUNTER ..][.. COUNTER].load_le::<u8>()
+	}
+
+	/// Tests if no item is in the filter.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.counters.not_any()
+	}
+
+	/// Removes all items from the filter.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.counters.fill(false);
+	}
+
+	/// Writes a counter.
+	fn set_count(&mut self, idx: usize, count: u8) {
+		self.counters[idx * COUNTER ..][.. COUNTER].store_le(count);
+	}
+}
+
+impl<H> Debug for CountingBloomFilter<H> {
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_struct("CountingBloomFilter")
+			.field("counters", &self.counters())
+			.field("hashes", &self.hashes)
+			.finish()
+	}
+}
+
+#[doc = include_str!("../doc/bloom/AtomicBloomFilter.md")]
+#[cfg(all(target_pointer_width = "64", target_has_atomic = "64"))]
+pub struct AtomicBloomFilter<H> {
+	/// The filter bits.
+	bits:   BitBox<AtomicU64, Lsb0>,
+	/// The number of bits that each item sets.
+	hashes: u32,
+	/// Hashes items into probe sequences.
+	hasher: H,
+}
+
+#[cfg(all(target_pointer_width = "64", target_has_atomic = "64"))]
+impl<H> AtomicBloomFilter<H>
+where H: BuildHasher
+{
+	/// Creates an empty filter sized to hold `items` items with a false
+	/// positive rate of at most `rate`.
+	///
+	/// See [`BloomFilter::with_rate`].
+	///
+	/// ## Panics
+	///
+	/// This panics if `rate` is not strictly between `0` and `1`, or if the
+	/// filter would be too large to allocate.
+	#[inline]
+	#[cfg(feature = "std")]
+	pub fn with_rate(items: usize, rate: f64, hasher: H) -> Self {
+		let (bits, hashes) = dimensions(items, rate);
+		Self::with_size(bits, hashes, hasher)
+	}
+
+	/// Creates an empty filter of exactly `bits` bits, which sets `hashes`
+	/// bits for each item.
+	///
+	/// ## Panics
+	///
+	/// This panics if `bits` or `hashes` is zero, or if `bits` is too large to
+	/// allocate.
+	#[inline]
+	pub fn with_size(bits: usize, hashes: u32, hasher: H) -> Self {
+		check_size(bits, hashes);
+		Self {
+			bits: BitVec::repeat(false, bits).into_boxed_bitslice(),
+			hashes,
+			hasher,
+		}
+	}
+
+	/// Adds an item to the filter.
+	///
+	/// Each probed bit is set with a single `Relaxed` read-modify-write, so
+	/// concurrent insertions never block each other. An item is visible to
+	/// [`contains`] on another thread once that thread synchronizes with the
+	/// inserting thread by some other means.
+	///
+	/// ## Returns
+	///
+	/// `true` if this call set any bit that was previously cleared. When
+	/// several threads insert the same item at once, more than one of them may
+	/// observe `true`.
+	///
+	/// [`contains`]: Self::contains
+	#[inline]
+	pub fn insert<T>(&self, item: &T) -> bool
+	where T: Hash + ?Sized {
+		let elts = self.bits.as_raw_slice();
+		let mut added = false;
+		for idx in self.probes(item) {
+			let mask = BitIdx::<u64>::new((idx % 64) as u8)
+				.expect("remainder is less than the element width")
+				.mask::<Lsb0>();
+			added |= elts[idx / 64].set_bits(mask) & mask.into_inner() == 0;
+		}
+		added
+	}
+
+	/// Tests if an item may be in the filter.
+	///
+	/// ## Returns
+	///
+	/// `false` if the item was definitely never inserted, and `true` if it
+	/// probably was.
+	#[inline]
+	pub fn contains<T>(&self, item: &T) -> bool
+	where T: Hash + ?Sized {
+		self.probes(item).all(|idx| self.bits[idx])
+	}
+
+	/// Hashes an item into the indices of the bits that it sets.
+	fn probes<T>(&self, item: &T) -> Probes
+	where T: Hash + ?Sized {
+		Probes::new(&self.hasher, item, self.hashes, self.bits.len())
+	}
+}
+
+#[cfg(all(target_pointer_width = "64", target_has_atomic = "64"))]
+impl<H> AtomicBloomFilter<H> {
+	/// Gets the number of bits that each item sets.
+	#[inline]
+	pub fn hashes(&self) -> u32 {
+		self.hashes
+	}
+
+	/// Gets the hasher that produces probe sequences.
+	#[inline]
+	pub fn hasher(&self) -> &H {
+		&self.hasher
+	}
+
+	/// Views the filter bits.
+	#[inline]
+	pub fn as_bitslice(&self) -> &BitSlice<AtomicU64, Lsb0> {
+		self.bits.as_bitslice()
+	}
+
+	/// Estimates the number of distinct items in the filter from the number of
+	/// bits that are set.
+	///
+	/// See [`BloomFilter::estimate_len`]. Insertions that run concurrently
+	/// with this call may or may not be counted.
+	#[inline]
+	#[cfg(feature = "std")]
+	pub fn estimate_len(&self) -> f64 {
+		estimate(self.bits.len(), self.bits.count_ones(), self.hashes)
+	}
+}
+
+#[cfg(all(target_pointer_width = "64", target_has_atomic = "64"))]
+impl<H> From<BloomFilter<H>> for AtomicBloomFilter<H> {
+	#[inline]
+	fn from(filter: BloomFilter<H>) -> Self {
+		let mut bits = BitVec::with_capacity(filter.bits.len());
+		bits.extend_from_bitslice(filter.bits.as_bitslice());
+		Self {
+			bits:   bits.into_boxed_bitslice(),
+			hashes: filter.hashes,
+			hasher: filter.hasher,
+		}
+	}
+}
+
+#[cfg(all(target_pointer_width = "64", target_has_atomic = "64"))]
+impl<H> From<AtomicBloomFilter<H>> for BloomFilter<H> {
+	#[inline]
+	fn from(filter: AtomicBloomFilter<H>) -> Self {
+		let mut bits = BitVec::with_capacity(filter.bits.len());
+		bits.extend_from_bitslice(filter.bits.as_bitslice());
+		Self {
+			bits,
+			hashes: filter.hashes,
+			hasher: filter.hasher,
+		}
+	}
+}
+
+#[cfg(all(target_pointer_width = "64", target_has_atomic = "64"))]
+impl<H> Debug for AtomicBloomFilter<H> {
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_struct("AtomicBloomFilter")
+			.field("bits", &self.bits.len())
+			.field("hashes", &self.hashes)
+			.field("ones", &self.bits.count_ones())
+			.finish()
+	}
+}
+
+/// The sequence of bit indices that an item sets.
+///
+/// This uses enhanced double hashing, after Dillinger and Manolios: the probes
+/// are `h₁ + i·h₂ + (i³ - i) / 6`, modulo the filter length, so only two hash
+/// values are computed for each item.
+struct Probes {
+	/// The next probe, before reduction modulo the filter length.
+	next:   u64,
+	/// The step to the probe after `next`.
+	step:   u64,
+	/// The number of probes produced so far.
+	index:  u32,
+	/// The number of probes to produce.
+	hashes: u32,
+	/// The filter length.
+	len:    u64,
+}
+
+impl Probes {
+	/// Hashes an item into its probe sequence.
+	fn new<H, T>(hasher: &H, item: &T, hashes: u32, len: usize) -> Self
+	where
+		H: BuildHasher,
+		T: Hash + ?Sized,
+	{
+		let mut state = hasher.build_hasher();
+		item.hash(&mut state);
+		let first = state.finish();
+		//  Feeding one more byte into the same state yields a second hash that
+		//  still depends on the whole item, without hashing it again.
+		state.write_u8(0xFF);
+		let second = state.finish();
+		Self {
+			next: first,
+			step: second,
+			index: 0,
+			hashes,
+			len: len as u64,
+		}
+	}
+}
+
+impl Iterator for Probes {
+	type Item = usize;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.index == self.hashes {
+			return None;
+		}
+		let out = self.next % self.len;
+		self.index += 1;
+		self.next = self.next.wrapping_add(self.step);
+		self.step = self.step.wrapping_add(u64::from(self.index));
+		Some(out as usize)
+	}
+}
+
+/// Asserts that a filter has a usable shape.
+fn check_size(bits: usize, hashes: u32) {
+	assert!(bits > 0, "a bloom filter must have at least one bit");
+	assert!(hashes > 0, "a bloom filter must use at least one hash");
+}
+
+/// Computes the number of bits and hashes that minimize the false-positive
+/// rate for a number of items.
+#[cfg(feature = "std")]
+fn dimensions(items: usize, rate: f64) -> (usize, u32) {
+	assert!(
+		rate > 0.0 && rate < 1.0,
+		"false-positive rate must be between 0 and 1, not {}",
+		rate,
+	);
+	let ln2 = core::f64::consts::LN_2;
+	let items = items.max(1) as f64;
+	//  Float-to-integer casts saturate, so an enormous filter is caught by the
+	//  allocator rather than wrapping to a small one.
+	let bits = (-items * rate.ln() / (ln2 * ln2)).ceil().max(1.0);
+	let hashes = (bits / items * ln2).round().max(1.0);
+	(bits as usize, hashes as u32)
+}
+
+/// Estimates the number of items that set `ones` bits out of `bits`.
+#[cfg(feature = "std")]
+fn estimate(bits: usize, ones: usize, hashes: u32) -> f64 {
+	let bits = bits as f64;
+	-(bits / f64::from(hashes)) * (-(ones as f64) / bits).ln_1p()
+}
@@ -0,0 +1,182 @@
+//! Unit tests for bloom filters.
+
+#![cfg(test)]
+
+use core::hash::BuildHasherDefault;
+use std::collections::hash_map::DefaultHasher;
+
+use super::*;
+
+type Fixed = BuildHasherDefault<DefaultHasher>;
+
+#[test]
+fn sizing() {
+	let filter = BloomFilter::with_rate(1000, 0.01, Fixed::default());
+	assert_eq!(filter.as_bitslice().len(), 9586);
+	assert_eq!(filter.hashes(), 7);
+	assert!(filter.is_empty());
+
+	let tiny = BloomFilter::with_rate(0, 0.5, Fixed::default());
+	assert_eq!((tiny.as_bitslice().len(), tiny.hashes()), (2, 1));
+
+	let probes =
+		Probes::new(&Fixed::default(), "probe", 8, 64).collect::<Vec<_>>();
+	assert_eq!(probes.len(), 8);
+	assert!(probes.iter().all(|&idx| idx < 64));
+}
+
+#[test]
+#[should_panic = "false-positive rate must be between 0 and 1"]
+fn bad_rate() {
+	BloomFilter::with_rate(10, 1.0, Fixed::default());
+}
+
+#[test]
+fn membership() {
+	let mut filter = BloomFilter::with_rate(500, 0.01, Fixed::default());
+	for item in 0 .. 500u32 {
+		filter.insert(&item);
+	}
+	assert!((0 .. 500u32).all(|item| filter.contains(&item)));
+	assert!(!filter.insert(&250u32));
+
+	//  Roughly one percent of absent items are false positives.
+	let false_positives = (500 .. 10_500u32)
+		.filter(|item| filter.contains(item))
+		.count();
+	assert!(false_positives < 200, "{} false positives", false_positives);
+
+	let estimate = filter.estimate_len();
+	assert!(
+		(475.0 .. 525.0).contains(&estimate),
+		"estimated {}",
+		estimate
+	);
+
+	filter.clear();
+	assert!(filter.is_empty());
+	assert_eq!(filter.estimate_len(), 0.0);
+}
+
+#[test]
+fn combination() {
+	let mut evens = BloomFilter::with_size(4096, 4, Fixed::default());
+	let mut small = evens.clone();
+	for item in (0 .. 100u32).step_by(2) {
+		evens.insert(&item);
+	}
+	for item in 0 .. 10u32 {
+		small.insert(&item);
+	}
+
+	let mut both = evens.clone();
+	both.union(&small);
+	assert!((0 .. 10u32).all(|item| both.contains(&item)));
+	assert!((0 .. 100u32).step_by(2).all(|item| both.contains(&item)));
+
+	let mut common = evens;
+	common.intersection(&small);
+	assert!((0 .. 10u32).step_by(2).all(|item| common.contains(&item)));
+	assert!(
+		common.as_bitslice().count_ones() <= small.as_bitslice().count_ones()
+	);
+}
+
+#[test]
+#[should_panic = "cannot combine a bloom filter of 64 bits and 2 hashes"]
+fn mismatched() {
+	let mut one = BloomFilter::with_size(64, 2, Fixed::default());
+	one.union(&BloomFilter::with_size(64, 3, Fixed::default()));
+}
+
+#[test]
+fn removal() {
+	let mut filter =
+		CountingBloomFilter::with_rate(500, 0.01, Fixed::default());
+	assert_eq!(filter.counters(), 4793);
+	assert_eq!(filter.hashes(), 7);
+	for item in 0 .. 500u32 {
+		assert!(filter.insert(&item) || filter.contains(&item));
+	}
+	assert!((0 .. 500u32).all(|item| filter.contains(&item)));
+
+	for item in (0 .. 500u32).step_by(2) {
+		assert!(filter.remove(&item));
+	}
+	assert!((1 .. 500u32).step_by(2).all(|item| filter.contains(&item)));
+	let remaining = (0 .. 500u32)
+		.step_by(2)
+		.filter(|item| filter.contains(item))
+		.count();
+	assert!(remaining < 20, "{} removed items remain", remaining);
+
+	//  Removing an absent item changes nothing.
+	let before = filter.clone();
+	let absent = (1000 ..).find(|item: &u32| !filter.contains(item)).unwrap();
+	assert!(!filter.remove(&absent));
+	assert_eq!(before.counters, filter.counters);
+
+	for item in (1 .. 500u32).step_by(2) {
+		filter.remove(&item);
+	}
+	assert!(filter.is_empty());
+}
+
+#[test]
+fn saturation() {
+	let mut filter = CountingBloomFilter::with_size(1, 1, Fixed::default());
+	assert!(filter.insert(&0u32));
+	for item in 1 .. 20u32 {
+		assert!(!filter.insert(&item));
+	}
+	assert_eq!(filter.count(0), 15);
+
+	//  A saturated counter never returns to zero.
+	for item in 0 .. 20u32 {
+		assert!(filter.remove(&item));
+	}
+	assert_eq!(filter.count(0), 15);
+	assert!(filter.contains(&0u32));
+
+	let mut filter = CountingBloomFilter::with_size(1, 1, Fixed::default());
+	for item in 0 .. 14u32 {
+		filter.insert(&item);
+	}
+	for item in 0 .. 14u32 {
+		filter.remove(&item);
+	}
+	assert!(filter.is_empty());
+	filter.insert(&1u32);
+	filter.clear();
+	assert!(!filter.contains(&1u32));
+}
+
+#[test]
+#[cfg(all(target_pointer_width = "64", target_has_atomic = "64"))]
+fn concurrent() {
+	let filter = AtomicBloomFilter::with_rate(4000, 0.01, Fixed::default());
+	std::thread::scope(|s| {
+		for id in 0 .. 4u32 {
+			let filter = &filter;
+			s.spawn(move || {
+				for item in id * 1000 .. (id + 1) * 1000 {
+					filter.insert(&item);
+				}
+			});
+		}
+	});
+	assert!((0 .. 4000u32).all(|item| filter.contains(&item)));
+	assert!(!filter.insert(&17u32));
+
+	let mut plain = BloomFilter::with_rate(4000, 0.01, Fixed::default());
+	for item in 0 .. 4000u32 {
+		plain.insert(&item);
+	}
+	assert_eq!(filter.as_bitslice(), plain.as_bitslice());
+	assert_eq!(filter.estimate_len(), plain.estimate_len());
+
+	let back = BloomFilter::from(filter);
+	assert_eq!(back.as_bitslice(), plain.as_bitslice());
+	let again = AtomicBloomFilter::from(back);
+	assert!(again.contains(&3999u32));
+}
@@ -24,7 +24,10 @@ use crate::{
 		BitPtr,
 		BitSpan,
 	},
-	slice::BitSlice,
+	slice::{
+		BitSlice,
+		ParseBitsError,
+	},
 	store::BitStore,
 	vec::BitVec,
 	view::BitView,
@@ -89,6 +92,39 @@ where
 		BitVec::from_bitslice(slice).into_boxed_bitslice()
 	}
 
+	/// Parses the text that a numeric formatter writes, in a given radix.
+	///
+	/// See [`BitVec::from_str_radix`].
+	///
+	/// ## Panics
+	///
+	/// This panics if `radix` is not 2, 8, or 16.
+	#[inline]
+	pub fn from_str_radix(
+		src: &str,
+		radix: u32,
+	) -> Result<Self, ParseBitsError> {
+		BitVec::from_str_radix(src, radix).map(BitVec::into_boxed_bitslice)
+	}
+
+	/// Parses the text that a numeric formatter writes for a bit-box of known
+	/// length.
+	///
+	/// See [`BitVec::from_str_radix_len`].
+	///
+	/// ## Panics
+	///
+	/// This panics if `radix` is not 2, 8, or 16.
+	#[inline]
+	pub fn from_str_radix_len(
+		src: &str,
+		radix: u32,
+		len: usize,
+	) -> Result<Self, ParseBitsError> {
+		BitVec::from_str_radix_len(src, radix, len)
+			.map(BitVec::into_boxed_bitslice)
+	}
+
 	/// Converts a `Box<[T]>` into a `BitBox<T, O>`, in place.
 	///
 	/// This does not affect the referent buffer, and only transforms the
@@ -167,6 +167,13 @@ fn ops() {
 	let mut f = !e;
 	assert_eq!(f, bitbox![1, 0, 0, 1]);
 
+	let mut g = f.clone() << 1;
+	assert_eq!(g, bitbox![0, 0, 1, 0]);
+	g >>= 2;
+	assert_eq!(g, bitbox![0, 0, 0, 0]);
+	assert_eq!(f.clone() >> 3, bitbox![0, 0, 0, 1]);
+	assert_eq!(f.clone() << 5, bitbox![0; 4]);
+
 	let _: &BitSlice = &a;
 	let _: &mut BitSlice = &mut f;
 }
@@ -22,6 +22,7 @@ use core::{
 		Hasher,
 	},
 	iter::FromIterator,
+	str::FromStr,
 };
 
 use tap::Pipe;
@@ -30,7 +31,10 @@ use super::BitBox;
 use crate::{
 	array::BitArray,
 	order::BitOrder,
-	slice::BitSlice,
+	slice::{
+		BitSlice,
+		ParseBitsError,
+	},
 	store::BitStore,
 	vec::BitVec,
 	view::BitViewSized,
@@ -308,6 +312,20 @@ where
 	}
 }
 
+#[doc = include_str!("../../doc/slice/from_str.md")]
+impl<T, O> FromStr for BitBox<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	type Err = ParseBitsError;
+
+	#[inline]
+	fn from_str(src: &str) -> Result<Self, Self::Err> {
+		src.parse::<BitVec<T, O>>().map(BitVec::into_boxed_bitslice)
+	}
+}
+
 impl<T, O> Default for BitBox<T, O>
 where
 	T: BitStore,
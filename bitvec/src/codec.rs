@@ -0,0 +1,382 @@
+#![doc = include_str!("../doc/codec.md")]
+
+use core::{
+	convert::TryFrom,
+	num::NonZeroU64,
+};
+
+use crate::{
+	field::cursor::{
+		BitRead,
+		BitWrite,
+		CursorError,
+	},
+	mem::bits_of,
+};
+
+mod tests;
+
+#[doc = include_str!("../doc/codec/Decode.md")]
+pub trait Decode: BitRead {
+	/// Reads a unary code: a run of zeros, terminated by a one.
+	///
+	/// The value is the number of zeros.
+	#[inline]
+	fn read_unary(&mut self) -> Result<u64, Self::Error> {
+		read_zeros(self, u64::MAX)
+	}
+
+	/// Reads an Exp-Golomb code of order `k`.
+	///
+	/// `k` must be no more than 64.
+	#[inline]
+	fn read_exp_golomb(&mut self, k: usize) -> Result<u64, Self::Error> {
+		let value = read_exp_golomb(self, k)?;
+		u64::try_from(value).map_err(|_| CursorError::Overflow.into())
+	}
+
+	/// Reads an unsigned Exp-Golomb code, written `ue(v)` in the H.264 and
+	/// H.265 specifications.
+	#[inline]
+	fn read_ue(&mut self) -> Result<u64, Self::Error> {
+		self.read_exp_golomb(0)
+	}
+
+	/// Reads a signed Exp-Golomb code, written `se(v)` in the H.264 and H.265
+	/// specifications.
+	///
+	/// Code numbers alternate between positive and negative values:
+	/// `0, 1, -1, 2, -2, …`.
+	#[inline]
+	fn read_se(&mut self) -> Result<i64, Self::Error> {
+		let code = read_exp_golomb(self, 0)? as i128;
+		let value = if code & 1 == 1 {
+			(code + 1) / 2
+		}
+		else {
+			-code / 2
+		};
+		i64::try_from(value).map_err(|_| CursorError::Overflow.into())
+	}
+
+	/// Reads an Elias gamma code, which cannot encode zero.
+	#[inline]
+	fn read_elias_gamma(&mut self) -> Result<u64, Self::Error> {
+		let zeros = read_zeros(self, bits_of::<u64>() as u64 - 1)? as usize;
+		let low = self.read_bits::<u64>(zeros)?;
+		Ok((1 << zeros) | low)
+	}
+
+	/// Reads an Elias delta code, which cannot encode zero.
+	#[inline]
+	fn read_elias_delta(&mut self) -> Result<u64, Self::Error> {
+		let zeros = read_zeros(self, 6)? as usize;
+		let len = (1 << zeros) | self.read_bits::<usize>(zeros)?;
+		if len > bits_of::<u64>() {
+			return Err(CursorError::Overflow.into());
+		}
+		let low = self.read_bits::<u64>(len - 1)?;
+		Ok((1 << (len - 1)) | low)
+	}
+
+	/// Reads a Golomb-Rice code with a divisor of `2^k`.
+	///
+	/// `k` must be no more than 64.
+	#[inline]
+	fn read_rice(&mut self, k: usize) -> Result<u64, Self::Error> {
+		check_order(k)?;
+		let quotient =
+			read_zeros(self, u64::MAX.checked_shr(k as u32).unwrap_or(0))?;
+		let remainder = self.read_bits::<u64>(k)?;
+		Ok(quotient.checked_shl(k as u32).unwrap_or(0) | remainder)
+	}
+
+	/// Reads a Golomb code with a divisor of `m`.
+	///
+	/// When `m` is a power of two, this is the same as [`.read_rice()`].
+	///
+	/// [`.read_rice()`]: Self::read_rice
+	#[inline]
+	fn read_golomb(&mut self, m: NonZeroU64) -> Result<u64, Self::Error> {
+		let m = m.get();
+		let quotient = read_zeros(self, u64::MAX / m)?;
+		let (width, cutoff) = truncated_binary(m);
+		let remainder = if width == 0 {
+			0
+		}
+		else if cutoff == 0 {
+			self.read_bits::<u64>(width)? as u128
+		}
+		else {
+			let high = self.read_bits::<u64>(width - 1)? as u128;
+			if high < cutoff {
+				high
+			}
+			else {
+				((high << 1) | self.read_bool()? as u128) - cutoff
+			}
+		};
+		u64::try_from(quotient as u128 * m as u128 + remainder)
+			.map_err(|_| CursorError::Overflow.into())
+	}
+
+	/// Reads an unsigned LEB128 code.
+	#[inline]
+	fn read_leb128(&mut self) -> Result<u64, Self::Error> {
+		let mut value = 0u64;
+		let mut shift = 0;
+		loop {
+			let byte = self.read_bits::<u8>(8)?;
+			let low = (byte & 0x7F) as u64;
+			if shift >= bits_of::<u64>() || low << shift >> shift != low {
+				return Err(CursorError::Overflow.into());
+			}
+			value |= low << shift;
+			if byte & 0x80 == 0 {
+				return Ok(value);
+			}
+			shift += 7;
+		}
+	}
+
+	/// Reads a signed LEB128 code.
+	#[inline]
+	fn read_sleb128(&mut self) -> Result<i64, Self::Error> {
+		let mut value = 0i64;
+		let mut shift = 0;
+		loop {
+			let byte = self.read_bits::<u8>(8)?;
+			let low = (byte & 0x7F) as i64;
+			if shift >= bits_of::<i64>()
+				|| (shift == bits_of::<i64>() - 1 && low != 0 && low != 0x7F)
+			{
+				return Err(CursorError::Overflow.into());
+			}
+			value |= low << shift;
+			shift += 7;
+			if byte & 0x80 == 0 {
+				if shift < bits_of::<i64>() && byte & 0x40 != 0 {
+					value |= -1 << shift;
+				}
+				return Ok(value);
+			}
+		}
+	}
+}
+
+impl<R> Decode for R where R: BitRead + ?Sized {}
+
+#[doc = include_str!("../doc/codec/Encode.md")]
+pub trait Encode: BitWrite {
+	/// Writes `value` as a unary code: that many zeros, then a one.
+	#[inline]
+	fn write_unary(&mut self, value: u64) -> Result<(), Self::Error> {
+		write_zeros(self, value)?;
+		self.write_bool(true)
+	}
+
+	/// Writes an Exp-Golomb code of order `k`.
+	///
+	/// `k` must be no more than 64.
+	#[inline]
+	fn write_exp_golomb(
+		&mut self,
+		value: u64,
+		k: usize,
+	) -> Result<(), Self::Error> {
+		write_exp_golomb(self, value as u128, k)
+	}
+
+	/// Writes an unsigned Exp-Golomb code, written `ue(v)` in the H.264 and
+	/// H.265 specifications.
+	#[inline]
+	fn write_ue(&mut self, value: u64) -> Result<(), Self::Error> {
+		self.write_exp_golomb(value, 0)
+	}
+
+	/// Writes a signed Exp-Golomb code, written `se(v)` in the H.264 and H.265
+	/// specifications.
+	#[inline]
+	fn write_se(&mut self, value: i64) -> Result<(), Self::Error> {
+		let value = value as i128;
+		let code = if value > 0 { value * 2 - 1 } else { -value * 2 };
+		write_exp_golomb(self, code as u128, 0)
+	}
+
+	/// Writes an Elias gamma code.
+	///
+	/// Zero cannot be encoded, and produces [`CursorError::Unrepresentable`].
+	///
+	/// [`CursorError::Unrepresentable`]: crate::field::cursor::CursorError::Unrepresentable
+	#[inline]
+	fn write_elias_gamma(&mut self, value: u64) -> Result<(), Self::Error> {
+		let zeros = ilog2(value)?;
+		write_zeros(self, zeros as u64)?;
+		self.write_bool(true)?;
+		self.write_bits(value, zeros)
+	}
+
+	/// Writes an Elias delta code.
+	///
+	/// Zero cannot be encoded, and produces [`CursorError::Unrepresentable`].
+	///
+	/// [`CursorError::Unrepresentable`]: crate::field::cursor::CursorError::Unrepresentable
+	#[inline]
+	fn write_elias_delta(&mut self, value: u64) -> Result<(), Self::Error> {
+		let len = ilog2(value)? + 1;
+		let zeros = ilog2(len as u64)?;
+		write_zeros(self, zeros as u64)?;
+		self.write_bool(true)?;
+		self.write_bits(len, zeros)?;
+		self.write_bits(value, len - 1)
+	}
+
+	/// Writes a Golomb-Rice code with a divisor of `2^k`.
+	///
+	/// `k` must be no more than 64.
+	#[inline]
+	fn write_rice(&mut self, value: u64, k: usize) -> Result<(), Self::Error> {
+		check_order(k)?;
+		self.write_unary(value.checked_shr(k as u32).unwrap_or(0))?;
+		self.write_bits(value, k)
+	}
+
+	/// Writes a Golomb code with a divisor of `m`.
+	///
+	/// When `m` is a power of two, this is the same as [`.write_rice()`].
+	///
+	/// [`.write_rice()`]: Self::write_rice
+	#[inline]
+	fn write_golomb(
+		&mut self,
+		value: u64,
+		m: NonZeroU64,
+	) -> Result<(), Self::Error> {
+		let m = m.get();
+		self.write_unary(value / m)?;
+		let (width, cutoff) = truncated_binary(m);
+		let remainder = (value % m) as u128;
+		if width == 0 {
+			Ok(())
+		}
+		else if cutoff == 0 {
+			self.write_bits(remainder, width)
+		}
+		else if remainder < cutoff {
+			self.write_bits(remainder, width - 1)
+		}
+		else {
+			let code = remainder + cutoff;
+			self.write_bits(code >> 1, width - 1)?;
+			self.write_bool(code & 1 == 1)
+		}
+	}
+
+	/// Writes an unsigned LEB128 code.
+	#[inline]
+	fn write_leb128(&mut self, mut value: u64) -> Result<(), Self::Error> {
+		loop {
+			let byte = (value & 0x7F) as u8;
+			value >>= 7;
+			if value == 0 {
+				return self.write_bits(byte, 8);
+			}
+			self.write_bits(byte | 0x80, 8)?;
+		}
+	}
+
+	/// Writes a signed LEB128 code.
+	#[inline]
+	fn write_sleb128(&mut self, mut value: i64) -> Result<(), Self::Error> {
+		loop {
+			let byte = (value & 0x7F) as u8;
+			value >>= 7;
+			let sign = byte & 0x40 != 0;
+			if (value == 0 && !sign) || (value == -1 && sign) {
+				return self.write_bits(byte, 8);
+			}
+			self.write_bits(byte | 0x80, 8)?;
+		}
+	}
+}
+
+impl<W> Encode for W where W: BitWrite + ?Sized {}
+
+/// Reads zeros up to and including a terminating one, and returns how many
+/// zeros were read.
+///
+/// Fails with `Overflow`, without reading further, once more than `limit`
+/// zeros have been seen.
+fn read_zeros<R>(reader: &mut R, limit: u64) -> Result<u64, R::Error>
+where R: BitRead + ?Sized {
+	let mut count = 0;
+	while !reader.read_bool()? {
+		if count == limit {
+			return Err(CursorError::Overflow.into());
+		}
+		count += 1;
+	}
+	Ok(count)
+}
+
+/// Writes `count` zeros.
+fn write_zeros<W>(writer: &mut W, mut count: u64) -> Result<(), W::Error>
+where W: BitWrite + ?Sized {
+	while count > 0 {
+		let step = count.min(bits_of::<u64>() as u64);
+		writer.write_bits(0u64, step as usize)?;
+		count -= step;
+	}
+	Ok(())
+}
+
+/// Reads an Exp-Golomb code of order `k` into a type wide enough to hold the
+/// signed mapping of every `i64`.
+fn read_exp_golomb<R>(reader: &mut R, k: usize) -> Result<u128, R::Error>
+where R: BitRead + ?Sized {
+	check_order(k)?;
+	let zeros = read_zeros(reader, (bits_of::<u64>() - k) as u64)? as usize;
+	let low = reader.read_bits::<u128>(zeros + k)?;
+	Ok(((1u128 << (zeros + k)) | low) - (1u128 << k))
+}
+
+/// Writes an Exp-Golomb code of order `k`.
+fn write_exp_golomb<W>(
+	writer: &mut W,
+	value: u128,
+	k: usize,
+) -> Result<(), W::Error>
+where
+	W: BitWrite + ?Sized,
+{
+	check_order(k)?;
+	let code = value + (1u128 << k);
+	let len = bits_of::<u128>() - code.leading_zeros() as usize;
+	write_zeros(writer, (len - 1 - k) as u64)?;
+	writer.write_bool(true)?;
+	writer.write_bits(code, len - 1)
+}
+
+/// Checks that a code parameter selects no more than 64 bits.
+fn check_order(k: usize) -> Result<(), CursorError> {
+	let max = bits_of::<u64>();
+	if k > max {
+		return Err(CursorError::TooWide { count: k, max });
+	}
+	Ok(())
+}
+
+/// Computes the floor of the base-2 logarithm of a nonzero value.
+fn ilog2(value: u64) -> Result<usize, CursorError> {
+	match value {
+		0 => Err(CursorError::Unrepresentable),
+		n => Ok(bits_of::<u64>() - 1 - n.leading_zeros() as usize),
+	}
+}
+
+/// Computes the width of the longer codewords, and the number of shorter
+/// codewords, of the truncated binary code for the range `0 .. m`.
+fn truncated_binary(m: u64) -> (usize, u128) {
+	let width = bits_of::<u64>() - (m - 1).leading_zeros() as usize;
+	(width, (1u128 << width) - m as u128)
+}
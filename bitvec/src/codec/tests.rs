@@ -0,0 +1,242 @@
+#![cfg(test)]
+
+use core::num::NonZeroU64;
+
+use rand::prelude::*;
+
+use super::*;
+use crate::{
+	field::{
+		BitField,
+		cursor::BitReader,
+	},
+	prelude::*,
+};
+
+/// Produces random values, biased towards the edges of the `u64` range and
+/// towards small magnitudes, which exercise the short codes.
+fn samples() -> Vec<u64> {
+	let mut rng = thread_rng();
+	let mut out = vec![0, 1, 2, 3, 127, 128, 1 << 63, u64::MAX - 1, u64::MAX];
+	out.extend((0 .. 500).map(|_| rng.gen::<u64>() >> rng.gen_range(0 .. 64)));
+	out
+}
+
+/// Maps every sample into a code’s domain, encodes them all into one stream,
+/// then decodes them all back out.
+fn round_trip<O>(
+	domain: impl Fn(u64) -> u64,
+	encode: impl Fn(&mut BitVec<u8, O>, u64) -> Result<(), CursorError>,
+	decode: impl Fn(&mut BitReader<u8, O>) -> Result<u64, CursorError>,
+) where
+	O: BitOrder,
+	BitSlice<u8, O>: BitField,
+{
+	let values = samples().into_iter().map(domain).collect::<Vec<_>>();
+	let mut bv = BitVec::<u8, O>::new();
+	for &value in &values {
+		encode(&mut bv, value).unwrap();
+	}
+	let mut reader = BitReader::new(bv.as_bitslice());
+	for &value in &values {
+		assert_eq!(decode(&mut reader), Ok(value));
+	}
+	assert_eq!(reader.remaining(), 0);
+}
+
+fn round_trips<O>()
+where
+	O: BitOrder,
+	BitSlice<u8, O>: BitField,
+{
+	let mut rng = thread_rng();
+	let any = |v| v;
+
+	//  Unary codes are as long as their value, so keep them short.
+	round_trip::<O>(|v| v % 300, |bv, v| bv.write_unary(v), |r| r.read_unary());
+	round_trip::<O>(any, |bv, v| bv.write_ue(v), |r| r.read_ue());
+	for k in [1, 5, 31, 63, 64] {
+		round_trip::<O>(
+			any,
+			|bv, v| bv.write_exp_golomb(v, k),
+			|r| r.read_exp_golomb(k),
+		);
+	}
+	round_trip::<O>(
+		any,
+		|bv, v| bv.write_se(v as i64),
+		|r| r.read_se().map(|v| v as u64),
+	);
+	round_trip::<O>(
+		|v| v.max(1),
+		|bv, v| bv.write_elias_gamma(v),
+		|r| r.read_elias_gamma(),
+	);
+	round_trip::<O>(
+		|v| v.max(1),
+		|bv, v| bv.write_elias_delta(v),
+		|r| r.read_elias_delta(),
+	);
+	for k in [0, 1, 7, 63, 64] {
+		//  Keep the unary quotient below 256.
+		let max = u64::MAX >> 64usize.saturating_sub(k + 8);
+		round_trip::<O>(
+			|v| v & max,
+			|bv, v| bv.write_rice(v, k),
+			|r| r.read_rice(k),
+		);
+	}
+	for _ in 0 .. 20 {
+		let m = rng.gen::<u64>() >> rng.gen_range(0 .. 64);
+		let m =
+			NonZeroU64::new(m).unwrap_or_else(|| NonZeroU64::new(1).unwrap());
+		round_trip::<O>(
+			|v| v % m.get().saturating_mul(200),
+			|bv, v| bv.write_golomb(v, m),
+			|r| r.read_golomb(m),
+		);
+	}
+	round_trip::<O>(any, |bv, v| bv.write_leb128(v), |r| r.read_leb128());
+	round_trip::<O>(
+		any,
+		|bv, v| bv.write_sleb128(v as i64),
+		|r| r.read_sleb128().map(|v| v as u64),
+	);
+}
+
+#[test]
+fn round_trips_lsb0() {
+	round_trips::<Lsb0>();
+}
+
+#[test]
+fn round_trips_msb0() {
+	round_trips::<Msb0>();
+}
+
+/// Encodes a value, and renders the stream as a string of `0` and `1`.
+fn code(encode: impl FnOnce(&mut BitVec<u8, Msb0>)) -> String {
+	let mut bv = BitVec::<u8, Msb0>::new();
+	encode(&mut bv);
+	bv.iter()
+		.by_vals()
+		.map(|bit| if bit { '1' } else { '0' })
+		.collect()
+}
+
+#[test]
+fn known_codes() {
+	assert_eq!(code(|bv| bv.write_unary(3).unwrap()), "0001");
+
+	let ue = [
+		"1", "010", "011", "00100", "00101", "00110", "00111", "0001000",
+	];
+	for (value, expected) in ue.iter().enumerate() {
+		assert_eq!(code(|bv| bv.write_ue(value as u64).unwrap()), *expected);
+	}
+	let se = [
+		(0, "1"),
+		(1, "010"),
+		(-1, "011"),
+		(2, "00100"),
+		(-2, "00101"),
+	];
+	for &(value, expected) in &se {
+		assert_eq!(code(|bv| bv.write_se(value).unwrap()), expected);
+	}
+	assert_eq!(code(|bv| bv.write_exp_golomb(3, 2).unwrap()), "111");
+	assert_eq!(code(|bv| bv.write_exp_golomb(4, 2).unwrap()), "01000");
+
+	assert_eq!(code(|bv| bv.write_elias_gamma(1).unwrap()), "1");
+	assert_eq!(code(|bv| bv.write_elias_gamma(9).unwrap()), "0001001");
+	assert_eq!(code(|bv| bv.write_elias_delta(1).unwrap()), "1");
+	assert_eq!(code(|bv| bv.write_elias_delta(10).unwrap()), "00100010");
+
+	assert_eq!(code(|bv| bv.write_rice(9, 2).unwrap()), "00101");
+	let ten = NonZeroU64::new(10).unwrap();
+	assert_eq!(code(|bv| bv.write_golomb(42, ten).unwrap()), "00001010");
+	assert_eq!(code(|bv| bv.write_golomb(47, ten).unwrap()), "000011101");
+	let four = NonZeroU64::new(4).unwrap();
+	let mut golomb = BitVec::<u8, Lsb0>::new();
+	let mut rice = BitVec::<u8, Lsb0>::new();
+	golomb.write_golomb(13, four).unwrap();
+	rice.write_rice(13, 2).unwrap();
+	assert_eq!(golomb, rice);
+
+	let mut leb = BitVec::<u8, Lsb0>::new();
+	leb.write_leb128(624_485).unwrap();
+	leb.write_sleb128(-123_456).unwrap();
+	assert_eq!(leb.as_raw_slice(), [0xE5, 0x8E, 0x26, 0xC0, 0xBB, 0x78]);
+}
+
+#[test]
+fn malformed_codes() {
+	let zeros = bits![u8, Msb0; 0; 80];
+	assert_eq!(BitReader::new(zeros).read_ue(), Err(CursorError::Overflow),);
+	assert_eq!(
+		BitReader::new(zeros).read_elias_gamma(),
+		Err(CursorError::Overflow),
+	);
+	assert_eq!(
+		BitReader::new(zeros).read_unary(),
+		Err(CursorError::UnexpectedEnd {
+			requested: 1,
+			remaining: 0,
+		}),
+	);
+
+	//  Sixty-four zeros and a one is a valid prefix for `se`, but not for
+	//  `ue`, when it is followed by anything other than zero.
+	let mut bv = BitVec::<u8, Msb0>::new();
+	bv.write_se(i64::MIN).unwrap();
+	assert_eq!(bv.len(), 129);
+	assert_eq!(BitReader::new(&bv).read_ue(), Err(CursorError::Overflow));
+	assert_eq!(BitReader::new(&bv).read_se(), Ok(i64::MIN));
+
+	//  An Elias delta length of 65 bits.
+	let delta = bits![u8, Msb0; 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1];
+	assert_eq!(
+		BitReader::new(delta).read_elias_delta(),
+		Err(CursorError::Overflow),
+	);
+
+	let leb = [0xFFu8; 9]
+		.iter()
+		.copied()
+		.chain([0x02])
+		.collect::<Vec<_>>();
+	assert_eq!(
+		BitReader::new(leb.view_bits::<Lsb0>()).read_leb128(),
+		Err(CursorError::Overflow),
+	);
+	let sleb = [0x80u8; 9]
+		.iter()
+		.copied()
+		.chain([0x01])
+		.collect::<Vec<_>>();
+	assert_eq!(
+		BitReader::new(sleb.view_bits::<Lsb0>()).read_sleb128(),
+		Err(CursorError::Overflow),
+	);
+	let long = [0x80u8; 10]
+		.iter()
+		.copied()
+		.chain([0x00])
+		.collect::<Vec<_>>();
+	assert_eq!(
+		BitReader::new(long.view_bits::<Lsb0>()).read_leb128(),
+		Err(CursorError::Overflow),
+	);
+
+	let mut bv = BitVec::<u8, Msb0>::new();
+	assert_eq!(bv.write_elias_gamma(0), Err(CursorError::Unrepresentable));
+	assert_eq!(bv.write_elias_delta(0), Err(CursorError::Unrepresentable));
+	assert_eq!(
+		bv.write_rice(0, 65),
+		Err(CursorError::TooWide {
+			count: 65,
+			max:   64,
+		}),
+	);
+	assert!(bv.is_empty());
+}
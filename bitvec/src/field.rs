@@ -1,6 +1,11 @@
 #![doc = include_str!("../doc/field.md")]
 
 use core::{
+	fmt::{
+		self,
+		Display,
+		Formatter,
+	},
 	mem,
 	ptr,
 };
@@ -35,9 +40,14 @@ use crate::{
 	vec::BitVec,
 };
 
+pub mod cursor;
 mod io;
 mod tests;
 
+#[cfg(feature = "derive")]
+#[doc = include_str!("../doc/field/BitFields.md")]
+pub use bitvec_derive::BitFields;
+
 #[doc = include_str!("../doc/field/BitField.md")]
 pub trait BitField {
 	#[inline]
@@ -92,6 +102,64 @@ pub trait BitField {
 		}
 	}
 
+	#[inline]
+	#[cfg(not(tarpaulin_include))]
+	#[doc = include_str!("../doc/field/BitField_try_load.md")]
+	fn try_load<I>(&self) -> Result<I, BitFieldError>
+	where
+		I: Integral,
+		Self: FieldLen,
+	{
+		if cfg!(target_endian = "little") {
+			self.try_load_le::<I>()
+		}
+		else if cfg!(target_endian = "big") {
+			self.try_load_be::<I>()
+		}
+		else {
+			match option_env!("CARGO_PKG_REPOSITORY") {
+				Some(env) => unreachable!(
+					"This architecture is not supported! Please consider \
+					 filing an issue at {}",
+					env
+				),
+				None => unreachable!(
+					"This architecture is not supported! Please consider \
+					 filing an issue"
+				),
+			}
+		}
+	}
+
+	#[inline]
+	#[cfg(not(tarpaulin_include))]
+	#[doc = include_str!("../doc/field/BitField_try_store.md")]
+	fn try_store<I>(&mut self, value: I) -> Result<(), BitFieldError>
+	where
+		I: Integral,
+		Self: FieldLen,
+	{
+		if cfg!(target_endian = "little") {
+			self.try_store_le::<I>(value)
+		}
+		else if cfg!(target_endian = "big") {
+			self.try_store_be::<I>(value)
+		}
+		else {
+			match option_env!("CARGO_PKG_REPOSITORY") {
+				Some(env) => unreachable!(
+					"This architecture is not supported! Please consider \
+					 filing an issue at {}",
+					env
+				),
+				None => unreachable!(
+					"This architecture is not supported! Please consider \
+					 filing an issue"
+				),
+			}
+		}
+	}
+
 	#[doc = include_str!("../doc/field/BitField_load_le.md")]
 	fn load_le<I>(&self) -> I
 	where I: Integral;
@@ -107,6 +175,100 @@ pub trait BitField {
 	#[doc = include_str!("../doc/field/BitField_store_be.md")]
 	fn store_be<I>(&mut self, value: I)
 	where I: Integral;
+
+	#[inline]
+	#[doc = include_str!("../doc/field/BitField_try_load_le.md")]
+	fn try_load_le<I>(&self) -> Result<I, BitFieldError>
+	where
+		I: Integral,
+		Self: FieldLen,
+	{
+		BitFieldError::check::<I>(self.field_len()).map(|()| self.load_le())
+	}
+
+	#[inline]
+	#[doc = include_str!("../doc/field/BitField_try_load_be.md")]
+	fn try_load_be<I>(&self) -> Result<I, BitFieldError>
+	where
+		I: Integral,
+		Self: FieldLen,
+	{
+		BitFieldError::check::<I>(self.field_len()).map(|()| self.load_be())
+	}
+
+	#[inline]
+	#[doc = include_str!("../doc/field/BitField_try_store_le.md")]
+	fn try_store_le<I>(&mut self, value: I) -> Result<(), BitFieldError>
+	where
+		I: Integral,
+		Self: FieldLen,
+	{
+		BitFieldError::check::<I>(self.field_len())
+			.map(|()| self.store_le(value))
+	}
+
+	#[inline]
+	#[doc = include_str!("../doc/field/BitField_try_store_be.md")]
+	fn try_store_be<I>(&mut self, value: I) -> Result<(), BitFieldError>
+	where
+		I: Integral,
+		Self: FieldLen,
+	{
+		BitFieldError::check::<I>(self.field_len())
+			.map(|()| self.store_be(value))
+	}
+}
+
+#[doc = include_str!("../doc/field/FieldLen.md")]
+pub trait FieldLen {
+	/// Counts the bits that a load or store transfers.
+	fn field_len(&self) -> usize;
+}
+
+impl<T, O> FieldLen for BitSlice<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline(always)]
+	fn field_len(&self) -> usize {
+		self.len()
+	}
+}
+
+impl<A, O> FieldLen for BitArray<A, O>
+where
+	A: BitViewSized,
+	O: BitOrder,
+{
+	#[inline(always)]
+	fn field_len(&self) -> usize {
+		self.len()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T, O> FieldLen for BitBox<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline(always)]
+	fn field_len(&self) -> usize {
+		self.len()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T, O> FieldLen for BitVec<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline(always)]
+	fn field_len(&self) -> usize {
+		self.len()
+	}
 }
 
 #[doc = include_str!("../doc/field/BitField_Lsb0.md")]
@@ -506,6 +668,120 @@ where
 	}
 }
 
+#[doc = include_str!("../doc/field/FieldValue.md")]
+pub trait FieldValue: Sized {
+	/// The integer type that holds this value in a bit-field.
+	type Repr: Integral;
+
+	/// Converts the value into its bit-field representation.
+	fn into_repr(self) -> Self::Repr;
+
+	/// Converts a bit-field representation back into a value, if it is valid.
+	fn from_repr(repr: Self::Repr) -> Option<Self>;
+}
+
+/// Integers are their own representation, and every bit-pattern is valid.
+macro_rules! field_value {
+	($($t:ty),+ $(,)?) => { $(
+		#[cfg(not(tarpaulin_include))]
+		impl FieldValue for $t {
+			type Repr = Self;
+
+			#[inline(always)]
+			fn into_repr(self) -> Self::Repr {
+				self
+			}
+
+			#[inline(always)]
+			fn from_repr(repr: Self::Repr) -> Option<Self> {
+				Some(repr)
+			}
+		}
+	)+ };
+}
+
+field_value!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl FieldValue for bool {
+	type Repr = u8;
+
+	#[inline]
+	fn into_repr(self) -> Self::Repr {
+		self as u8
+	}
+
+	#[inline]
+	fn from_repr(repr: Self::Repr) -> Option<Self> {
+		match repr {
+			0 => Some(false),
+			1 => Some(true),
+			_ => None,
+		}
+	}
+}
+
+#[doc = include_str!("../doc/field/BitFieldError.md")]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum BitFieldError {
+	/// The bit-slice has no bits with which to transfer an integer.
+	Empty,
+	/// The bit-slice is wider than the integer being transferred.
+	TooWide {
+		/// The length of the bit-slice.
+		len: usize,
+		/// The width of the integer type.
+		max: usize,
+	},
+}
+
+impl BitFieldError {
+	/// Checks that a bit-slice can transfer an integer.
+	///
+	/// ## Type Parameters
+	///
+	/// - `I`: The integer type being stored into or loaded out of a bit-slice.
+	///
+	/// ## Parameters
+	///
+	/// - `len`: the length of the bit-slice under test.
+	///
+	/// ## Returns
+	///
+	/// `Ok` if `len` is in `1 ..= I::BITS`; otherwise, the reason it is not.
+	#[inline]
+	fn check<I>(len: usize) -> Result<(), Self>
+	where I: Integral {
+		let max = bits_of::<I>();
+		if len == 0 {
+			return Err(Self::Empty);
+		}
+		if len > max {
+			return Err(Self::TooWide { len, max });
+		}
+		Ok(())
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl Display for BitFieldError {
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Empty => {
+				fmt.write_str("cannot transfer an integer through zero bits")
+			},
+			Self::TooWide { len, max } => write!(
+				fmt,
+				"cannot transfer {} bits through an integer of {} bits",
+				len, max,
+			),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BitFieldError {}
+
 /** Asserts that a bit-slice is not longer than a memory element.
 
 ## Type Parameters
@@ -532,18 +808,26 @@ where I: Integral {
 	);
 }
 
-/// Shifts a value to the left, if it can support the shift amount.
+/// Shifts a value to the left, clearing it if it cannot support the shift
+/// amount.
 fn maybe_shift_left<T: Integral>(elem: &mut T, shamt: usize) {
 	if bits_of::<T>() > shamt {
 		*elem <<= shamt;
 	}
+	else {
+		*elem = T::ZERO;
+	}
 }
 
-/// Shifts a value to the right, if it can support the shift amount.
+/// Shifts a value to the right, clearing it if it cannot support the shift
+/// amount.
 fn maybe_shift_right<T: Integral>(elem: &mut T, shamt: usize) {
 	if bits_of::<T>() > shamt {
 		*elem >>= shamt;
 	}
+	else {
+		*elem = T::ZERO;
+	}
 }
 
 #[doc = include_str!("../doc/field/get.md")]
@@ -572,8 +856,9 @@ where I: Integral {
 	if dvl::is_unsigned::<I>() {
 		return elem;
 	}
-	//  Find the number of high bits that are not loaded.
-	let shamt = bits_of::<I>() - width;
+	//  Find the number of high bits that are not loaded. Bit-arrays can be
+	//  wider than the destination, in which case every bit was loaded.
+	let shamt = bits_of::<I>().saturating_sub(width);
 	//  Shift left, so that the highest loaded bit is now in the sign position.
 	let shl: I = elem << shamt;
 	//  Shift right with sign extension back to the original place.
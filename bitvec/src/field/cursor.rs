@@ -0,0 +1,756 @@
+#![doc = include_str!("../../doc/field/cursor.md")]
+
+use core::fmt::{
+	self,
+	Display,
+	Formatter,
+};
+#[cfg(feature = "std")]
+use core::{
+	mem::ManuallyDrop,
+	ptr,
+};
+#[cfg(feature = "std")]
+use std::io;
+
+use funty::Integral;
+
+use super::BitField;
+#[cfg(feature = "std")]
+use crate::array::BitArray;
+#[cfg(feature = "alloc")]
+use crate::vec::BitVec;
+use crate::{
+	index::BitIdx,
+	mem::bits_of,
+	order::{
+		BitOrder,
+		Lsb0,
+	},
+	slice::BitSlice,
+	store::BitStore,
+};
+
+mod tests;
+
+#[doc = include_str!("../../doc/field/cursor/BitRead.md")]
+pub trait BitRead {
+	/// The error produced when a read fails.
+	type Error: From<CursorError>;
+
+	/// Reads the next `count` bits as an integer.
+	///
+	/// If the bit-ordering counts from the least significant edge of an
+	/// element, as `Lsb0` does, the first bit read is the least significant
+	/// bit of the result; otherwise, it is the most significant. Signed
+	/// integers are sign-extended from the last bit read into that position.
+	/// Reading zero bits produces zero.
+	fn read_bits<I>(&mut self, count: usize) -> Result<I, Self::Error>
+	where I: Integral;
+
+	/// Reads the next bit.
+	#[inline]
+	fn read_bool(&mut self) -> Result<bool, Self::Error> {
+		self.read_bits::<u8>(1).map(|bit| bit != 0)
+	}
+
+	/// Advances the cursor by `count` bits without reading them.
+	fn skip(&mut self, count: usize) -> Result<(), Self::Error>;
+
+	/// Advances the cursor to the next multiple of eight bits from the start
+	/// of the stream, discarding any bits in between.
+	#[inline]
+	fn align_to_byte(&mut self) -> Result<(), Self::Error> {
+		match self.position() % bits_of::<u8>() {
+			0 => Ok(()),
+			rem => self.skip(bits_of::<u8>() - rem),
+		}
+	}
+
+	/// Counts how many bits have been consumed since the stream began.
+	fn position(&self) -> usize;
+}
+
+#[doc = include_str!("../../doc/field/cursor/BitWrite.md")]
+pub trait BitWrite {
+	/// The error produced when a write fails.
+	type Error: From<CursorError>;
+
+	/// Writes the `count` least significant bits of `value`.
+	///
+	/// The bits are placed in the stream in the order that
+	/// [`BitRead::read_bits`] would read them back. Writing zero bits does
+	/// nothing.
+	fn write_bits<I>(
+		&mut self,
+		value: I,
+		count: usize,
+	) -> Result<(), Self::Error>
+	where
+		I: Integral;
+
+	/// Writes a single bit.
+	#[inline]
+	fn write_bool(&mut self, bit: bool) -> Result<(), Self::Error> {
+		self.write_bits(bit as u8, 1)
+	}
+
+	/// Advances the cursor by `count` bits without writing them.
+	///
+	/// Destinations that already hold data keep their previous contents in
+	/// the skipped region; destinations that grow fill it with zeros.
+	fn skip(&mut self, count: usize) -> Result<(), Self::Error>;
+
+	/// Writes zeros until the cursor is at a multiple of eight bits from the
+	/// start of the stream.
+	#[inline]
+	fn align_to_byte(&mut self) -> Result<(), Self::Error> {
+		match self.position() % bits_of::<u8>() {
+			0 => Ok(()),
+			rem => self.write_bits(0u8, bits_of::<u8>() - rem),
+		}
+	}
+
+	/// Counts how many bits have been produced since the stream began.
+	fn position(&self) -> usize;
+}
+
+#[doc = include_str!("../../doc/field/cursor/BitReader.md")]
+#[derive(Clone, Copy, Debug)]
+pub struct BitReader<'a, T = usize, O = Lsb0>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// The bit-slice being read.
+	bits: &'a BitSlice<T, O>,
+	/// The index of the next bit to read.
+	pos:  usize,
+}
+
+impl<'a, T, O> BitReader<'a, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// Creates a reader at the start of a bit-slice.
+	#[inline]
+	pub fn new(bits: &'a BitSlice<T, O>) -> Self {
+		Self { bits, pos: 0 }
+	}
+
+	/// Moves the cursor to an absolute bit index.
+	///
+	/// The index may be equal to the length of the bit-slice, which leaves
+	/// nothing to read.
+	#[inline]
+	pub fn seek(&mut self, pos: usize) -> Result<(), CursorError> {
+		let len = self.bits.len();
+		if pos > len {
+			return Err(CursorError::OutOfBounds { pos, len });
+		}
+		self.pos = pos;
+		Ok(())
+	}
+
+	/// Counts how many bits are left to read.
+	#[inline]
+	pub fn remaining(&self) -> usize {
+		self.bits.len() - self.pos
+	}
+
+	/// Views the bits that have not yet been read.
+	#[inline]
+	pub fn rest(&self) -> &'a BitSlice<T, O> {
+		unsafe { self.bits.get_unchecked(self.pos ..) }
+	}
+
+	/// Unwraps the reader, returning the entire underlying bit-slice.
+	#[inline]
+	pub fn into_inner(self) -> &'a BitSlice<T, O> {
+		self.bits
+	}
+
+	/// Takes the next `count` bits off the front of the unread region.
+	#[inline]
+	fn take(&mut self, count: usize) -> Result<&'a BitSlice<T, O>, CursorError> {
+		let remaining = self.remaining();
+		if count > remaining {
+			return Err(CursorError::UnexpectedEnd {
+				requested: count,
+				remaining,
+			});
+		}
+		let start = self.pos;
+		self.pos += count;
+		Ok(unsafe { self.bits.get_unchecked(start .. self.pos) })
+	}
+}
+
+impl<T, O> BitRead for BitReader<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+	BitSlice<T, O>: BitField,
+{
+	type Error = CursorError;
+
+	#[inline]
+	fn read_bits<I>(&mut self, count: usize) -> Result<I, Self::Error>
+	where I: Integral {
+		check::<I>(count)?;
+		self.take(count).map(load::<T, O, I>)
+	}
+
+	#[inline]
+	fn skip(&mut self, count: usize) -> Result<(), Self::Error> {
+		self.take(count).map(drop)
+	}
+
+	#[inline]
+	fn position(&self) -> usize {
+		self.pos
+	}
+}
+
+#[doc = include_str!("../../doc/field/cursor/BitWriter.md")]
+#[derive(Debug)]
+pub struct BitWriter<'a, T = usize, O = Lsb0>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// The bit-slice being written.
+	bits: &'a mut BitSlice<T, O>,
+	/// The index of the next bit to write.
+	pos:  usize,
+}
+
+impl<'a, T, O> BitWriter<'a, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// Creates a writer at the start of a bit-slice.
+	#[inline]
+	pub fn new(bits: &'a mut BitSlice<T, O>) -> Self {
+		Self { bits, pos: 0 }
+	}
+
+	/// Moves the cursor to an absolute bit index.
+	///
+	/// The index may be equal to the length of the bit-slice, which leaves no
+	/// room to write.
+	#[inline]
+	pub fn seek(&mut self, pos: usize) -> Result<(), CursorError> {
+		let len = self.bits.len();
+		if pos > len {
+			return Err(CursorError::OutOfBounds { pos, len });
+		}
+		self.pos = pos;
+		Ok(())
+	}
+
+	/// Counts how many bits can still be written.
+	#[inline]
+	pub fn remaining(&self) -> usize {
+		self.bits.len() - self.pos
+	}
+
+	/// Views the bits that have been written so far.
+	#[inline]
+	pub fn written(&self) -> &BitSlice<T, O> {
+		unsafe { self.bits.get_unchecked(.. self.pos) }
+	}
+
+	/// Unwraps the writer, returning the entire underlying bit-slice.
+	#[inline]
+	pub fn into_inner(self) -> &'a mut BitSlice<T, O> {
+		self.bits
+	}
+
+	/// Takes the next `count` bits off the front of the unwritten region.
+	#[inline]
+	fn take(
+		&mut self,
+		count: usize,
+	) -> Result<&mut BitSlice<T, O>, CursorError> {
+		let remaining = self.remaining();
+		if count > remaining {
+			return Err(CursorError::UnexpectedEnd {
+				requested: count,
+				remaining,
+			});
+		}
+		let start = self.pos;
+		self.pos += count;
+		Ok(unsafe { self.bits.get_unchecked_mut(start .. self.pos) })
+	}
+}
+
+impl<T, O> BitWrite for BitWriter<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+	BitSlice<T, O>: BitField,
+{
+	type Error = CursorError;
+
+	#[inline]
+	fn write_bits<I>(
+		&mut self,
+		value: I,
+		count: usize,
+	) -> Result<(), Self::Error>
+	where
+		I: Integral,
+	{
+		check::<I>(count)?;
+		self.take(count).map(|bits| store(bits, value))
+	}
+
+	#[inline]
+	fn skip(&mut self, count: usize) -> Result<(), Self::Error> {
+		self.take(count).map(drop)
+	}
+
+	#[inline]
+	fn position(&self) -> usize {
+		self.pos
+	}
+}
+
+/// Bit-vectors are writable streams that grow as bits are written to them.
+#[cfg(feature = "alloc")]
+impl<T, O> BitWrite for BitVec<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+	BitSlice<T, O>: BitField,
+{
+	type Error = CursorError;
+
+	#[inline]
+	fn write_bits<I>(
+		&mut self,
+		value: I,
+		count: usize,
+	) -> Result<(), Self::Error>
+	where
+		I: Integral,
+	{
+		check::<I>(count)?;
+		let len = self.len();
+		self.resize(len + count, false);
+		store(unsafe { self.get_unchecked_mut(len ..) }, value);
+		Ok(())
+	}
+
+	#[inline]
+	fn skip(&mut self, count: usize) -> Result<(), Self::Error> {
+		self.resize(self.len() + count, false);
+		Ok(())
+	}
+
+	#[inline]
+	fn position(&self) -> usize {
+		self.len()
+	}
+}
+
+/// The number of bytes buffered by the `std::io` adapters.
+#[cfg(feature = "std")]
+const IO_BUF: usize = 64;
+
+#[doc = include_str!("../../doc/field/cursor/IoReader.md")]
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct IoReader<R, O = Lsb0>
+where O: BitOrder
+{
+	/// The byte source.
+	inner: R,
+	/// Bytes read out of the source that have not been fully consumed.
+	buf:   BitArray<[u8; IO_BUF], O>,
+	/// The index in `buf` of the next bit to read.
+	head:  usize,
+	/// The index in `buf` after the last byte read from the source.
+	tail:  usize,
+	/// The number of bits consumed since the reader was created.
+	pos:   usize,
+}
+
+#[cfg(feature = "std")]
+impl<R, O> IoReader<R, O>
+where
+	R: io::Read,
+	O: BitOrder,
+{
+	/// Creates a bit-reader over a byte source.
+	#[inline]
+	pub fn new(inner: R) -> Self {
+		Self {
+			inner,
+			buf: BitArray::ZERO,
+			head: 0,
+			tail: 0,
+			pos: 0,
+		}
+	}
+
+	/// Unwraps the reader, returning the byte source.
+	///
+	/// Any bytes that were buffered but not yet fully read are lost.
+	#[inline]
+	pub fn into_inner(self) -> R {
+		self.inner
+	}
+
+	/// Reads from the source until at least `count` bits are buffered.
+	fn fill(&mut self, count: usize) -> io::Result<()> {
+		while self.tail - self.head < count {
+			//  Move the partially-read byte, and everything after it, to the
+			//  front of the buffer.
+			let consumed = self.head / bits_of::<u8>();
+			if consumed > 0 {
+				let end = self.tail / bits_of::<u8>();
+				self.buf.as_raw_mut_slice().copy_within(consumed .. end, 0);
+				self.head -= consumed * bits_of::<u8>();
+				self.tail -= consumed * bits_of::<u8>();
+			}
+			let free =
+				&mut self.buf.as_raw_mut_slice()[self.tail / bits_of::<u8>() ..];
+			match self.inner.read(free) {
+				Ok(0) => {
+					return Err(CursorError::UnexpectedEnd {
+						requested: count,
+						remaining: self.tail - self.head,
+					}
+					.into());
+				},
+				Ok(n) => self.tail += n * bits_of::<u8>(),
+				Err(err) if err.kind() == io::ErrorKind::Interrupted => {},
+				Err(err) => return Err(err),
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(feature = "std")]
+impl<R, O> BitRead for IoReader<R, O>
+where
+	R: io::Read,
+	O: BitOrder,
+	BitSlice<u8, O>: BitField,
+{
+	type Error = io::Error;
+
+	#[inline]
+	fn read_bits<I>(&mut self, count: usize) -> Result<I, Self::Error>
+	where I: Integral {
+		check::<I>(count)?;
+		self.fill(count)?;
+		let bits = &self.buf[self.head ..][.. count];
+		self.head += count;
+		self.pos += count;
+		Ok(load::<u8, O, I>(bits))
+	}
+
+	#[inline]
+	fn skip(&mut self, mut count: usize) -> Result<(), Self::Error> {
+		while count > 0 {
+			let step = count.min(bits_of::<u8>());
+			self.fill(step)?;
+			self.head += step;
+			self.pos += step;
+			count -= step;
+		}
+		Ok(())
+	}
+
+	#[inline]
+	fn position(&self) -> usize {
+		self.pos
+	}
+}
+
+#[doc = include_str!("../../doc/field/cursor/IoWriter.md")]
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct IoWriter<W, O = Lsb0>
+where
+	W: io::Write,
+	O: BitOrder,
+{
+	/// The byte sink.
+	inner: W,
+	/// Bits written to the stream that have not yet been sent to the sink.
+	buf:   BitArray<[u8; IO_BUF], O>,
+	/// The number of live bits in `buf`.
+	len:   usize,
+	/// The number of bits written since the writer was created.
+	pos:   usize,
+}
+
+#[cfg(feature = "std")]
+impl<W, O> IoWriter<W, O>
+where
+	W: io::Write,
+	O: BitOrder,
+{
+	/// Creates a bit-writer over a byte sink.
+	#[inline]
+	pub fn new(inner: W) -> Self {
+		Self {
+			inner,
+			buf: BitArray::ZERO,
+			len: 0,
+			pos: 0,
+		}
+	}
+
+	/// Sends every complete byte to the sink, and flushes it.
+	///
+	/// A trailing partial byte stays buffered until more bits complete it,
+	/// or until [`.finish()`] pads it.
+	///
+	/// [`.finish()`]: Self::finish
+	#[inline]
+	pub fn flush(&mut self) -> io::Result<()> {
+		self.drain()?;
+		self.inner.flush()
+	}
+
+	/// Pads the stream with zeros to a byte boundary, sends everything to the
+	/// sink, and returns it.
+	#[inline]
+	pub fn finish(mut self) -> io::Result<W>
+	where BitSlice<u8, O>: BitField {
+		self.align_to_byte()?;
+		self.flush()?;
+		//  The buffer is now empty, so there is nothing left for `Drop` to do.
+		let this = ManuallyDrop::new(self);
+		Ok(unsafe { ptr::read(&this.inner) })
+	}
+
+	/// Sends every complete byte to the sink, keeping any partial byte.
+	fn drain(&mut self) -> io::Result<()> {
+		let full = self.len / bits_of::<u8>();
+		if full == 0 {
+			return Ok(());
+		}
+		let raw = self.buf.as_raw_mut_slice();
+		self.inner.write_all(&raw[.. full])?;
+		let partial = raw.get(full).copied().unwrap_or(0);
+		raw.fill(0);
+		raw[0] = partial;
+		self.len -= full * bits_of::<u8>();
+		Ok(())
+	}
+}
+
+/// Sends any buffered bits to the sink, padding a trailing partial byte with
+/// zeros. Errors are ignored, as in [`BufWriter`]; use [`.finish()`] to observe
+/// them.
+///
+/// [`BufWriter`]: std::io::BufWriter
+/// [`.finish()`]: IoWriter::finish
+#[cfg(feature = "std")]
+impl<W, O> Drop for IoWriter<W, O>
+where
+	W: io::Write,
+	O: BitOrder,
+{
+	#[inline]
+	fn drop(&mut self) {
+		//  Bits past `len` are always zero.
+		let bytes = (self.len + bits_of::<u8>() - 1) / bits_of::<u8>();
+		let _ = self.inner.write_all(&self.buf.as_raw_slice()[.. bytes]);
+		self.len = 0;
+	}
+}
+
+#[cfg(feature = "std")]
+impl<W, O> BitWrite for IoWriter<W, O>
+where
+	W: io::Write,
+	O: BitOrder,
+	BitSlice<u8, O>: BitField,
+{
+	type Error = io::Error;
+
+	#[inline]
+	fn write_bits<I>(
+		&mut self,
+		value: I,
+		count: usize,
+	) -> Result<(), Self::Error>
+	where
+		I: Integral,
+	{
+		check::<I>(count)?;
+		if self.len + count > bits_of::<[u8; IO_BUF]>() {
+			self.drain()?;
+		}
+		store(&mut self.buf[self.len ..][.. count], value);
+		self.len += count;
+		self.pos += count;
+		Ok(())
+	}
+
+	#[inline]
+	fn skip(&mut self, mut count: usize) -> Result<(), Self::Error> {
+		while count > 0 {
+			let step = count.min(bits_of::<u8>());
+			self.write_bits(0u8, step)?;
+			count -= step;
+		}
+		Ok(())
+	}
+
+	#[inline]
+	fn position(&self) -> usize {
+		self.pos
+	}
+}
+
+#[doc = include_str!("../../doc/field/cursor/CursorError.md")]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum CursorError {
+	/// The stream ended before the requested number of bits.
+	UnexpectedEnd {
+		/// The number of bits requested.
+		requested: usize,
+		/// The number of bits left in the stream.
+		remaining: usize,
+	},
+	/// The requested number of bits does not fit in the integer type.
+	TooWide {
+		/// The number of bits requested.
+		count: usize,
+		/// The width of the integer type.
+		max:   usize,
+	},
+	/// A seek targeted an index beyond the end of the stream.
+	OutOfBounds {
+		/// The requested index.
+		pos: usize,
+		/// The length of the stream.
+		len: usize,
+	},
+	/// A value lies outside the domain of the variable-length code it was to
+	/// be written in, such as zero in an Elias code.
+	Unrepresentable,
+	/// A variable-length code in the stream describes a value too large for
+	/// the integer it is decoded into.
+	Overflow,
+}
+
+#[cfg(not(tarpaulin_include))]
+impl Display for CursorError {
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::UnexpectedEnd {
+				requested,
+				remaining,
+			} => write!(
+				fmt,
+				"cannot transfer {} bits with only {} remaining",
+				requested, remaining,
+			),
+			Self::TooWide { count, max } => write!(
+				fmt,
+				"cannot transfer {} bits through an integer of {} bits",
+				count, max,
+			),
+			Self::OutOfBounds { pos, len } => write!(
+				fmt,
+				"cannot seek to bit {} in a stream of {} bits",
+				pos, len,
+			),
+			Self::Unrepresentable => {
+				fmt.write_str("value cannot be represented in this code")
+			},
+			Self::Overflow => {
+				fmt.write_str("decoded value does not fit in its integer type")
+			},
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CursorError {}
+
+#[cfg(feature = "std")]
+impl From<CursorError> for io::Error {
+	#[inline]
+	fn from(err: CursorError) -> Self {
+		let kind = match err {
+			CursorError::UnexpectedEnd { .. } => io::ErrorKind::UnexpectedEof,
+			CursorError::TooWide { .. }
+			| CursorError::OutOfBounds { .. }
+			| CursorError::Unrepresentable => io::ErrorKind::InvalidInput,
+			CursorError::Overflow => io::ErrorKind::InvalidData,
+		};
+		io::Error::new(kind, err)
+	}
+}
+
+/// Checks that `count` bits fit in an `I` integer.
+fn check<I>(count: usize) -> Result<(), CursorError>
+where I: Integral {
+	let max = bits_of::<I>();
+	if count > max {
+		return Err(CursorError::TooWide { count, max });
+	}
+	Ok(())
+}
+
+/// Tests whether an ordering numbers bits from the least significant edge of
+/// an element. Such orderings read streams least-significant-bit first, and
+/// all others read most-significant-bit first.
+fn lsb_first<O>() -> bool
+where O: BitOrder {
+	O::at::<u8>(BitIdx::MIN).into_inner() == 0
+}
+
+/// Loads a stream segment, choosing the element order that matches the bit
+/// order so that the stream reads continuously across element boundaries.
+fn load<T, O, I>(bits: &BitSlice<T, O>) -> I
+where
+	T: BitStore,
+	O: BitOrder,
+	BitSlice<T, O>: BitField,
+	I: Integral,
+{
+	if bits.is_empty() {
+		I::ZERO
+	}
+	else if lsb_first::<O>() {
+		bits.load_le()
+	}
+	else {
+		bits.load_be()
+	}
+}
+
+/// Stores a stream segment, in the inverse of [`load`].
+fn store<T, O, I>(bits: &mut BitSlice<T, O>, value: I)
+where
+	T: BitStore,
+	O: BitOrder,
+	BitSlice<T, O>: BitField,
+	I: Integral,
+{
+	if bits.is_empty() {
+		return;
+	}
+	if lsb_first::<O>() {
+		bits.store_le(value);
+	}
+	else {
+		bits.store_be(value);
+	}
+}
@@ -0,0 +1,266 @@
+#![cfg(test)]
+
+#[cfg(feature = "std")]
+use std::io;
+
+use rand::prelude::*;
+
+use super::*;
+use crate::prelude::*;
+
+#[test]
+fn msb0_stream() {
+	let data = [0b1010_0101u8, 0b1100_0011];
+	let mut reader = BitReader::new(data.view_bits::<Msb0>());
+	assert_eq!(reader.read_bits::<u8>(3), Ok(0b101));
+	assert_eq!(reader.read_bits::<u16>(10), Ok(0b00_1011_1000));
+	assert_eq!(reader.position(), 13);
+	assert_eq!(reader.read_bits::<i8>(3), Ok(0b011));
+	assert_eq!(reader.remaining(), 0);
+	assert_eq!(
+		reader.read_bool(),
+		Err(CursorError::UnexpectedEnd {
+			requested: 1,
+			remaining: 0,
+		}),
+	);
+
+	reader.seek(12).unwrap();
+	assert_eq!(reader.read_bits::<i8>(4), Ok(3));
+	reader.seek(14).unwrap();
+	assert_eq!(reader.read_bits::<i8>(2), Ok(-1));
+	assert_eq!(
+		reader.seek(17),
+		Err(CursorError::OutOfBounds { pos: 17, len: 16 }),
+	);
+}
+
+#[test]
+fn lsb0_stream() {
+	//  DEFLATE: BFINAL = 1, BTYPE = 01, then five bits of a fixed code.
+	let data = [0b1010_1011u8, 0x01];
+	let mut reader = BitReader::new(data.view_bits::<Lsb0>());
+	assert_eq!(reader.read_bool(), Ok(true));
+	assert_eq!(reader.read_bits::<u8>(2), Ok(0b01));
+	assert_eq!(reader.read_bits::<u8>(5), Ok(0b10101));
+	reader.align_to_byte().unwrap();
+	assert_eq!(reader.position(), 8);
+	assert_eq!(reader.rest(), bits![u8, Lsb0; 1, 0, 0, 0, 0, 0, 0, 0]);
+
+	reader.seek(4).unwrap();
+	assert_eq!(reader.read_bits::<u8>(8), Ok(0x1A));
+	reader.align_to_byte().unwrap();
+	assert_eq!(reader.position(), 16);
+}
+
+#[test]
+fn errors_do_not_move() {
+	let data = [0u16; 2];
+	let mut reader = BitReader::new(data.view_bits::<Msb0>());
+	reader.skip(20).unwrap();
+	assert_eq!(
+		reader.read_bits::<u8>(9),
+		Err(CursorError::TooWide { count: 9, max: 8 }),
+	);
+	assert_eq!(
+		reader.read_bits::<u16>(13),
+		Err(CursorError::UnexpectedEnd {
+			requested: 13,
+			remaining: 12,
+		}),
+	);
+	assert_eq!(reader.position(), 20);
+	assert_eq!(reader.read_bits::<u8>(0), Ok(0));
+
+	let mut data = [0u8; 2];
+	let mut writer = BitWriter::new(data.view_bits_mut::<Lsb0>());
+	assert!(writer.write_bits(0xFFu16, 17).is_err());
+	assert!(writer.write_bits(0xFFu8, 9).is_err());
+	assert_eq!(writer.position(), 0);
+	writer.write_bits(0x7u8, 3).unwrap();
+	writer.skip(3).unwrap();
+	writer.align_to_byte().unwrap();
+	assert_eq!(writer.written().len(), 8);
+	assert_eq!(writer.remaining(), 8);
+	assert_eq!(data, [0x07, 0x00]);
+}
+
+#[test]
+fn writer_patches() {
+	let mut data = [0xFFu8; 2];
+	let mut writer = BitWriter::new(data.view_bits_mut::<Msb0>());
+	writer.skip(4).unwrap();
+	writer.write_bits(0u8, 4).unwrap();
+	writer.seek(12).unwrap();
+	writer.write_bits(0b1010u8, 4).unwrap();
+	assert!(writer.into_inner().len() == 16);
+	assert_eq!(data, [0xF0, 0xFA]);
+}
+
+fn round_trip<O>()
+where
+	O: BitOrder,
+	BitSlice<u8, O>: BitField,
+	BitSlice<u32, O>: BitField,
+{
+	let mut rng = thread_rng();
+	let fields = (0 .. 200)
+		.map(|_| {
+			let width = rng.gen_range(0 ..= 64);
+			let value = rng.gen::<u64>();
+			(width, value, rng.gen::<bool>())
+		})
+		.collect::<Vec<_>>();
+	let mask = |value: u64, width: usize| {
+		value & u64::MAX.checked_shr(64 - width as u32).unwrap_or(0)
+	};
+	let extend = |value: u64, width: usize| {
+		if width == 0 {
+			0
+		}
+		else {
+			let shamt = 64 - width as u32;
+			((value << shamt) as i64 >> shamt) as u64
+		}
+	};
+
+	let mut bv = BitVec::<u8, O>::new();
+	for &(width, value, signed) in &fields {
+		if signed {
+			bv.write_bits(value as i64, width).unwrap();
+		}
+		else {
+			bv.write_bits(value, width).unwrap();
+		}
+	}
+	let total = fields.iter().map(|&(width, ..)| width).sum::<usize>();
+	assert_eq!(bv.position(), total);
+
+	let mut buf = BitVec::<u32, O>::repeat(false, total);
+	let mut writer = BitWriter::new(buf.as_mut_bitslice());
+	for &(width, value, _) in &fields {
+		writer.write_bits(value, width).unwrap();
+	}
+	assert_eq!(writer.remaining(), 0);
+	assert_eq!(buf, bv);
+
+	let mut reader = BitReader::new(buf.as_bitslice());
+	for &(width, value, signed) in &fields {
+		if signed {
+			let expected = extend(value, width) as i64;
+			assert_eq!(reader.read_bits::<i64>(width), Ok(expected));
+		}
+		else {
+			assert_eq!(reader.read_bits::<u64>(width), Ok(mask(value, width)));
+		}
+	}
+	assert_eq!(reader.remaining(), 0);
+
+	#[cfg(feature = "std")]
+	{
+		let mut writer = IoWriter::<_, O>::new(Vec::new());
+		for &(width, value, _) in &fields {
+			writer.write_bits(value, width).unwrap();
+		}
+		assert_eq!(writer.position(), total);
+		let bytes = writer.finish().unwrap();
+		assert_eq!(bytes.len(), (total + 7) / 8);
+		assert_eq!(bytes.view_bits::<O>()[.. total], bv);
+
+		let mut reader = IoReader::<_, O>::new(&bytes[..]);
+		for &(width, value, _) in &fields {
+			assert_eq!(
+				reader.read_bits::<u64>(width).unwrap(),
+				mask(value, width)
+			);
+		}
+		reader.align_to_byte().unwrap();
+		let err = reader.read_bool().unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+	}
+}
+
+#[test]
+fn round_trips() {
+	round_trip::<Lsb0>();
+	round_trip::<Msb0>();
+}
+
+#[test]
+fn order_significance() {
+	let mut lsb = BitVec::<u8, Lsb0>::new();
+	let mut msb = BitVec::<u8, Msb0>::new();
+	lsb.write_bits(0b110u8, 3).unwrap();
+	msb.write_bits(0b110u8, 3).unwrap();
+	assert_eq!(lsb, bits![0, 1, 1]);
+	assert_eq!(msb, bits![1, 1, 0]);
+	lsb.align_to_byte().unwrap();
+	msb.align_to_byte().unwrap();
+	assert_eq!(lsb.as_raw_slice(), [0b0000_0110]);
+	assert_eq!(msb.as_raw_slice(), [0b1100_0000]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn io_adapters() {
+	/// Produces one byte per call, and fails every other call.
+	struct Trickle<'a> {
+		data:  &'a [u8],
+		stall: bool,
+	}
+
+	impl io::Read for Trickle<'_> {
+		fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+			self.stall = !self.stall;
+			if self.stall {
+				return Err(io::ErrorKind::Interrupted.into());
+			}
+			match self.data.split_first() {
+				Some((&byte, rest)) if !buf.is_empty() => {
+					buf[0] = byte;
+					self.data = rest;
+					Ok(1)
+				},
+				_ => Ok(0),
+			}
+		}
+	}
+
+	let data = (0 ..= 255u8).collect::<Vec<_>>();
+	let mut reader = IoReader::<_, Msb0>::new(Trickle {
+		data:  &data,
+		stall: false,
+	});
+	reader.skip(4).unwrap();
+	for byte in 0 .. 255u8 {
+		let expected = (byte << 4) | ((byte + 1) >> 4);
+		assert_eq!(reader.read_bits::<u8>(8).unwrap(), expected);
+	}
+	reader.skip(4).unwrap();
+	assert_eq!(reader.position(), 256 * 8);
+	assert!(reader.skip(1).is_err());
+
+	let err = reader.read_bits::<u8>(9).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+	let mut writer = IoWriter::<_, Lsb0>::new(Vec::new());
+	writer.write_bool(true).unwrap();
+	writer.skip(10).unwrap();
+	writer.write_bits(u128::MAX, 128).unwrap();
+	writer.flush().unwrap();
+	assert_eq!(writer.position(), 139);
+	let bytes = writer.finish().unwrap();
+	assert_eq!(bytes.len(), 18);
+	let bits = bytes.view_bits::<Lsb0>();
+	assert_eq!(bits[.. 11], bits![1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+	assert!(bits[11 .. 139].all());
+	assert!(bits[139 ..].not_any());
+
+	//  Dropping the writer sends its buffer, padded to a byte boundary.
+	let mut bytes = Vec::new();
+	let mut writer = IoWriter::<_, Msb0>::new(&mut bytes);
+	writer.write_bits(0xAu8, 4).unwrap();
+	writer.write_bits(0x5Fu8, 8).unwrap();
+	drop(writer);
+	assert_eq!(bytes, [0xA5, 0xF0]);
+}
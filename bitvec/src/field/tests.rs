@@ -5,7 +5,15 @@ use std::io;
 
 use rand::prelude::*;
 
-use crate::prelude::*;
+use crate::{
+	field::{
+		BitField,
+		BitFieldError,
+		FieldLen,
+	},
+	mem,
+	prelude::*,
+};
 
 #[test]
 fn lsb0_u8_any_u5() {
@@ -257,6 +265,235 @@ fn msb0_u8_be_u24() {
 	);
 }
 
+/// Stores a signed value of every width that fits in an `i64`, at every head
+/// offset within the first storage element, and checks that it loads back
+/// sign-extended into every signed type wide enough to hold it.
+macro_rules! sign_extension {
+	($($name:ident => $t:ty, $o:ty);+ $(;)?) => { $(
+		#[test]
+		fn $name() {
+			let mut bits = BitArray::<[$t; 16], $o>::ZERO;
+
+			for width in 1 ..= 64 {
+				let min = -1i64 << (width - 1);
+				let max = !min;
+				let rand = random::<i64>() >> (64 - width);
+				let mask = !0u64 >> (64 - width);
+
+				for head in 0 .. mem::bits_of::<$t>() {
+					let field = &mut bits[head ..][.. width];
+					for val in [min, max, -1, 0, rand] {
+						field.store_le(val);
+						assert_eq!(field.load_le::<i64>(), val);
+						assert_eq!(field.load_le::<u64>(), val as u64 & mask);
+						if width <= 32 {
+							assert_eq!(field.load_le::<i32>(), val as i32);
+						}
+						if width <= 16 {
+							assert_eq!(field.load_le::<i16>(), val as i16);
+						}
+						if width <= 8 {
+							assert_eq!(field.load_le::<i8>(), val as i8);
+						}
+
+						field.store_be(val);
+						assert_eq!(field.load_be::<i64>(), val);
+						assert_eq!(field.load_be::<u64>(), val as u64 & mask);
+						if width <= 32 {
+							assert_eq!(field.load_be::<i32>(), val as i32);
+						}
+						if width <= 16 {
+							assert_eq!(field.load_be::<i16>(), val as i16);
+						}
+						if width <= 8 {
+							assert_eq!(field.load_be::<i8>(), val as i8);
+						}
+					}
+				}
+			}
+		}
+	)+ };
+}
+
+sign_extension! {
+	sign_extend_lsb0_u8 => u8, Lsb0;
+	sign_extend_lsb0_u16 => u16, Lsb0;
+	sign_extend_lsb0_u32 => u32, Lsb0;
+	sign_extend_lsb0_u64 => u64, Lsb0;
+	sign_extend_lsb0_usize => usize, Lsb0;
+	sign_extend_msb0_u8 => u8, Msb0;
+	sign_extend_msb0_u16 => u16, Msb0;
+	sign_extend_msb0_u32 => u32, Msb0;
+	sign_extend_msb0_u64 => u64, Msb0;
+	sign_extend_msb0_usize => usize, Msb0;
+}
+
+#[test]
+fn sign_extend_array() {
+	let mut bits = BitArray::<u16, Msb0>::ZERO;
+	bits.store_le(-300i16);
+	assert_eq!(bits.load_le::<i32>(), -300);
+	assert_eq!(bits.load_le::<i64>(), -300);
+	assert_eq!(bits.load_le::<u32>(), -300i16 as u16 as u32);
+	bits.store_be(-300i16);
+	assert_eq!(bits.load_be::<i32>(), -300);
+	assert_eq!(bits.load_be::<u32>(), -300i16 as u16 as u32);
+
+	//  Arrays wider than the destination keep only their low bits.
+	let mut bits = BitArray::<[u8; 4], Lsb0>::ZERO;
+	bits.store_le(0x1234_FF80u32);
+	assert_eq!(bits.load_le::<i16>(), 0xFF80u16 as i16);
+	assert_eq!(bits.load_le::<i8>(), -128);
+	bits.store_be(0x1234_8000u32);
+	assert_eq!(bits.load_be::<i16>(), 0x8000u16 as i16);
+	assert_eq!(bits.load_be::<u8>(), 0);
+
+	let mut bits = BitArray::<[u32; 2], Lsb0>::ZERO;
+	bits.store_le(-2i64);
+	assert_eq!(bits.load_le::<i64>(), -2);
+	assert_eq!(bits.load_le::<i16>(), -2);
+	bits.store_le(5u8);
+	assert_eq!(bits.as_raw_slice(), [5, 0]);
+	bits.store_be(-2i64);
+	assert_eq!(bits.load_be::<i64>(), -2);
+	assert_eq!(bits.load_be::<i8>(), -2);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn sign_extend_alloc() {
+	for width in 1 ..= 32 {
+		let val = -1i32 << (width - 1);
+
+		let mut bv = bitvec![u8, Lsb0; 0; width + 3];
+		bv[3 ..].store_le(val);
+		assert_eq!(bv[3 ..].load_le::<i32>(), val);
+		bv.drain(.. 3);
+		assert_eq!(bv.load_le::<i32>(), val);
+		assert_eq!(bv.load_le::<i64>(), val as i64);
+		bv.store_be(val);
+		assert_eq!(bv.load_be::<i64>(), val as i64);
+
+		let mut bb = bitbox![u16, Msb0; 0; width];
+		bb.store_le(val);
+		assert_eq!(bb.load_le::<i32>(), val);
+		assert_eq!(bb.load_le::<i64>(), val as i64);
+		bb.store_be(val);
+		assert_eq!(bb.load_be::<i64>(), val as i64);
+	}
+}
+
+#[test]
+fn checked_transfers() {
+	let mut raw = [0u16; 2];
+	let bits = raw.view_bits_mut::<Lsb0>();
+
+	assert_eq!(bits[.. 0].try_load_le::<u8>(), Err(BitFieldError::Empty));
+	assert_eq!(bits[.. 0].try_load_be::<u8>(), Err(BitFieldError::Empty));
+	assert_eq!(bits[.. 0].try_store_le(0u8), Err(BitFieldError::Empty));
+	assert_eq!(bits[.. 0].try_store_be(0u8), Err(BitFieldError::Empty));
+
+	let too_wide = BitFieldError::TooWide { len: 9, max: 8 };
+	assert_eq!(bits[3 .. 12].try_load_le::<u8>(), Err(too_wide));
+	assert_eq!(bits[3 .. 12].try_load_be::<i8>(), Err(too_wide));
+	assert_eq!(bits[3 .. 12].try_store_le(!0u8), Err(too_wide));
+	assert_eq!(bits[3 .. 12].try_store_be(!0i8), Err(too_wide));
+	assert!(bits.not_any(), "failed stores must not write");
+
+	assert_eq!(bits[12 .. 21].try_store_le(0x1A5u16), Ok(()));
+	assert_eq!(bits[12 .. 21].try_load_le::<u16>(), Ok(0x1A5));
+	assert_eq!(bits[12 .. 21].try_load_le::<i16>(), Ok(0xFFA5u16 as i16));
+	assert_eq!(bits[12 .. 21].try_store_be(0x0A5u16), Ok(()));
+	assert_eq!(bits[12 .. 21].try_load_be::<u16>(), Ok(0x0A5));
+	assert_eq!(bits[12 .. 21].try_store(0x15Au16), Ok(()));
+	assert_eq!(bits[12 .. 21].try_load::<u16>(), Ok(0x15A));
+	assert_eq!(
+		bits.try_load::<u16>(),
+		Err(BitFieldError::TooWide { len: 32, max: 16 }),
+	);
+
+	let mut bits = BitArray::<[u8; 2], Msb0>::ZERO;
+	assert_eq!(
+		bits.try_store_be(0u8),
+		Err(BitFieldError::TooWide { len: 16, max: 8 }),
+	);
+	assert_eq!(bits.try_store_be(0x1234u16), Ok(()));
+	assert_eq!(bits.try_load_be::<u16>(), Ok(0x1234));
+	assert_eq!(bits.try_store_le(-2i32), Ok(()));
+	assert_eq!(bits.try_load_le::<i32>(), Ok(-2));
+	assert!(bits.try_load_le::<i8>().is_err());
+	assert!(BitArray::<[u8; 0], Lsb0>::ZERO.try_load_le::<u8>().is_err());
+}
+
+/// An implementor written against only the required methods still gains the
+/// checked methods once it reports its width.
+#[test]
+fn checked_transfers_foreign() {
+	struct Nibble(u8);
+
+	impl BitField for Nibble {
+		fn load_le<I>(&self) -> I
+		where I: funty::Integral {
+			self.0.view_bits::<Lsb0>()[.. 4].load_le()
+		}
+
+		fn load_be<I>(&self) -> I
+		where I: funty::Integral {
+			self.0.view_bits::<Lsb0>()[.. 4].load_be()
+		}
+
+		fn store_le<I>(&mut self, value: I)
+		where I: funty::Integral {
+			self.0.view_bits_mut::<Lsb0>()[.. 4].store_le(value)
+		}
+
+		fn store_be<I>(&mut self, value: I)
+		where I: funty::Integral {
+			self.0.view_bits_mut::<Lsb0>()[.. 4].store_be(value)
+		}
+	}
+
+	impl FieldLen for Nibble {
+		fn field_len(&self) -> usize {
+			4
+		}
+	}
+
+	let mut nibble = Nibble(0);
+	assert_eq!(nibble.try_store_le(0xAu8), Ok(()));
+	assert_eq!(nibble.try_load_be::<u8>(), Ok(0xA));
+	assert_eq!(nibble.try_load_le::<i8>(), Ok(-6));
+	assert_eq!(nibble.0, 0x0A);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn checked_transfers_alloc() {
+	let mut bv = bitvec![u32, Msb0; 0; 40];
+	assert_eq!(
+		bv.try_store_le(0u32),
+		Err(BitFieldError::TooWide { len: 40, max: 32 }),
+	);
+	assert_eq!(bv.try_store_le(0xAB_CDEF_0123u64), Ok(()));
+	assert_eq!(bv.try_load_le::<u64>(), Ok(0xAB_CDEF_0123));
+	assert_eq!(bv.try_store_be(-3i64), Ok(()));
+	assert_eq!(bv.try_load_be::<i64>(), Ok(-3));
+
+	let mut bb = bv.into_boxed_bitslice();
+	assert!(bb.try_load_be::<u16>().is_err());
+	assert_eq!(bb.try_store_le(0x80_0000_0000u64), Ok(()));
+	assert_eq!(bb.try_load_le::<i64>(), Ok(-0x80_0000_0000));
+
+	bv = BitVec::new();
+	assert_eq!(bv.try_load_le::<u8>(), Err(BitFieldError::Empty));
+	assert_eq!(bv.try_store_be(1u8), Err(BitFieldError::Empty));
+
+	assert_eq!(
+		BitFieldError::TooWide { len: 9, max: 8 }.to_string(),
+		"cannot transfer 9 bits through an integer of 8 bits",
+	);
+}
+
 #[test]
 #[cfg(feature = "std")]
 fn read_bits() {
@@ -0,0 +1,716 @@
+#![doc = include_str!("../doc/image.md")]
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::{
+	any::TypeId,
+	convert::{
+		Infallible,
+		TryFrom,
+	},
+	fmt::{
+		self,
+		Display,
+		Formatter,
+	},
+};
+#[cfg(feature = "std")]
+use std::io::{
+	self,
+	Read,
+};
+
+use funty::Fundamental;
+use wyz::comu::Address;
+
+#[cfg(feature = "alloc")]
+use crate::vec::BitVec;
+use crate::{
+	mem::{
+		BitRegister,
+		bits_of,
+	},
+	order::{
+		BitOrder,
+		Lsb0,
+		Msb0,
+	},
+	ptr::check_alignment,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+mod tests;
+
+/// The bytes that begin every bit-image.
+pub const MAGIC: [u8; 4] = *b"BITV";
+
+/// The format version that this crate reads and writes.
+pub const VERSION: u8 = 1;
+
+/// The length of the bit-image header, in bytes. Element data begins at this
+/// offset.
+pub const HEADER_LEN: usize = 24;
+
+/// The number of header bytes, from the start, covered by the checksum.
+const CHECKED_LEN: usize = 20;
+
+/// The flag bit marking element data stored most-significant byte first.
+const BIG_ENDIAN: u8 = 1;
+
+/// The number of element bytes gathered before they are handed to an output.
+const CHUNK: usize = 256;
+
+/// Encoding.
+impl<T, O> BitSlice<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// Computes the number of bytes in the bit-image of this bit-slice.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::{image::HEADER_LEN, prelude::*};
+	///
+	/// assert_eq!(bits![u16, Lsb0; 1; 20].encoded_len(), HEADER_LEN + 4);
+	/// ```
+	#[inline]
+	pub fn encoded_len(&self) -> usize {
+		HEADER_LEN + Header::describe(self).data_len()
+	}
+
+	/// Writes the bit-image of this bit-slice into a byte buffer.
+	///
+	/// This does not allocate, and so is available in `#![no_std]` builds.
+	///
+	/// ## Parameters
+	///
+	/// - `buf`: A buffer of at least [`.encoded_len()`] bytes.
+	///
+	/// ## Returns
+	///
+	/// The number of bytes written at the front of `buf`.
+	///
+	/// ## Errors
+	///
+	/// This fails, without writing anything, if `buf` is too short.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bits = bits![u8, Msb0; 1, 0, 1, 1];
+	/// let mut buf = [0; 32];
+	/// let len = bits.encode_into(&mut buf).unwrap();
+	/// assert_eq!(len, 25);
+	/// assert_eq!(buf[24], 0b1011_0000);
+	/// ```
+	///
+	/// [`.encoded_len()`]: Self::encoded_len
+	#[inline]
+	pub fn encode_into(&self, buf: &mut [u8]) -> Result<usize, ImageError> {
+		let mut header = Header::describe(self);
+		let needed = HEADER_LEN + header.data_len();
+		if buf.len() < needed {
+			return Err(ImageError::Truncated {
+				needed,
+				len: buf.len(),
+			});
+		}
+		let (head, data) = buf[.. needed].split_at_mut(HEADER_LEN);
+		let mut at = 0;
+		write_data(self, &header, |chunk| {
+			data[at .. at + chunk.len()].copy_from_slice(chunk);
+			at += chunk.len();
+			Ok::<_, Infallible>(())
+		})
+		.unwrap_or_else(|never| match never {});
+		header.checksum = header.checksum(data);
+		head.copy_from_slice(&header.encode());
+		Ok(needed)
+	}
+
+	/// Writes the bit-image of this bit-slice into a byte stream.
+	///
+	/// The element data is gathered in small batches, but `writer` should
+	/// still be buffered if it performs a system call for each write.
+	///
+	/// ## Errors
+	///
+	/// This forwards any error produced by `writer`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bv = bitvec![u32, Lsb0; 1, 0, 1];
+	/// let mut file = Vec::new();
+	/// bv.write_to(&mut file).unwrap();
+	/// assert_eq!(file.len(), bv.encoded_len());
+	/// ```
+	#[inline]
+	#[cfg(feature = "std")]
+	pub fn write_to<W>(&self, mut writer: W) -> io::Result<()>
+	where W: io::Write {
+		let mut header = Header::describe(self);
+		let mut crc = Crc32::new();
+		crc.update(&header.encode()[.. CHECKED_LEN]);
+		write_data(self, &header, |chunk| {
+			crc.update(chunk);
+			Ok::<_, Infallible>(())
+		})
+		.unwrap_or_else(|never| match never {});
+		header.checksum = crc.finish();
+		writer.write_all(&header.encode())?;
+		write_data(self, &header, |chunk| writer.write_all(chunk))
+	}
+}
+
+/// Zero-copy decoding.
+impl<T, O> BitSlice<T, O>
+where
+	T: BitRegister + BitStore,
+	O: BitOrder,
+{
+	/// Views a bit-image as a bit-slice, without copying it.
+	///
+	/// The image must have been written by a bit-slice with the same element
+	/// width and bit-ordering, on a machine with the same byte order, unless
+	/// the elements are bytes. The buffer must also be aligned for `T`. Images
+	/// of `u8` bit-slices can therefore always be viewed in place. Use
+	/// [`BitVec::decode`] to read any other image.
+	///
+	/// Bytes after the end of the image are ignored.
+	///
+	/// ## Errors
+	///
+	/// This fails if the image is malformed or damaged, if its layout does not
+	/// match `BitSlice<T, O>`, or if its element data is misaligned.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut buf = [0; 32];
+	/// let len = bits![u8, Msb0; 0, 1, 1].encode_into(&mut buf).unwrap();
+	///
+	/// let bits = BitSlice::<u8, Msb0>::from_encoded(&buf[.. len]).unwrap();
+	/// assert_eq!(bits, bits![0, 1, 1]);
+	/// ```
+	///
+	/// [`BitVec::decode`]: crate::vec::BitVec::decode
+	#[inline]
+	pub fn from_encoded(bytes: &[u8]) -> Result<&Self, ImageError> {
+		let (header, data) = Header::open(bytes)?;
+		let native = header.width == 8
+			|| header.big_endian == cfg!(target_endian = "big");
+		if header.width != bits_of::<T>()
+			|| Order::of::<O>() != Some(header.order)
+			|| !native
+		{
+			return Err(ImageError::Incompatible);
+		}
+		let addr = Address::try_from(data.as_ptr().cast::<T>())
+			.ok()
+			.and_then(|addr| check_alignment(addr).ok())
+			.ok_or(ImageError::Misaligned)?;
+		let elts = unsafe {
+			core::slice::from_raw_parts(
+				addr.to_const(),
+				data.len() / core::mem::size_of::<T>(),
+			)
+		};
+		let bits =
+			Self::try_from_slice(elts).map_err(|_| ImageError::TooLong)?;
+		Ok(&bits[header.head .. header.head + header.len])
+	}
+}
+
+/// Decoding.
+#[cfg(feature = "alloc")]
+impl<T, O> BitVec<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// Decodes a bit-image into a new bit-vector.
+	///
+	/// Unlike [`BitSlice::from_encoded`], this accepts images of any element
+	/// width, bit-ordering, and byte order, and converts their bits into the
+	/// layout of `BitVec<T, O>`. The bits keep their indices.
+	///
+	/// Bytes after the end of the image are ignored.
+	///
+	/// ## Errors
+	///
+	/// This fails if the image is malformed or damaged.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bits = bits![u64, Msb0; 1, 1, 0, 1];
+	/// let mut buf = vec![0; bits.encoded_len()];
+	/// bits.encode_into(&mut buf).unwrap();
+	///
+	/// let bv = BitVec::<u8, Lsb0>::decode(&buf).unwrap();
+	/// assert_eq!(bv, bits);
+	/// ```
+	///
+	/// [`BitSlice::from_encoded`]: crate::slice::BitSlice::from_encoded
+	#[inline]
+	pub fn decode(bytes: &[u8]) -> Result<Self, ImageError> {
+		let (header, data) = Header::open(bytes)?;
+		let span = header.head .. header.head + header.len;
+		let mut out = Self::with_capacity(header.len);
+		//  Each ordering reads an element’s bits continuously through one byte
+		//  order: `Lsb0` through little-endian, and `Msb0` through big-endian.
+		//  Elements stored in the other byte order are reversed to match.
+		let natural = match header.order {
+			Order::Lsb0 => !header.big_endian,
+			Order::Msb0 => header.big_endian,
+		};
+		let mut swapped = Vec::new();
+		let data = if natural || header.width == 8 {
+			data
+		}
+		else {
+			swapped.extend_from_slice(data);
+			for elem in swapped.chunks_exact_mut(header.width / 8) {
+				elem.reverse();
+			}
+			&swapped[..]
+		};
+		match header.order {
+			Order::Lsb0 => out.extend_from_bitslice(
+				&BitSlice::<u8, Lsb0>::from_slice(data)[span],
+			),
+			Order::Msb0 => out.extend_from_bitslice(
+				&BitSlice::<u8, Msb0>::from_slice(data)[span],
+			),
+		}
+		Ok(out)
+	}
+
+	/// Reads a bit-image from a byte stream, and decodes it into a new
+	/// bit-vector.
+	///
+	/// This reads exactly the bytes of one image, and so can be called
+	/// repeatedly to read consecutive images from one stream. See
+	/// [`.decode()`] for how the image is converted.
+	///
+	/// ## Errors
+	///
+	/// This forwards any error produced by `reader`. An [`ImageError`] is
+	/// reported as [`io::ErrorKind::UnexpectedEof`] if the stream ended early,
+	/// and as [`io::ErrorKind::InvalidData`] otherwise.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut file = Vec::new();
+	/// bits![1, 0, 0, 1].write_to(&mut file).unwrap();
+	/// bits![0, 1].write_to(&mut file).unwrap();
+	///
+	/// let mut reader = &file[..];
+	/// let first = BitVec::<u8>::read_from(&mut reader).unwrap();
+	/// let second = BitVec::<u8>::read_from(&mut reader).unwrap();
+	/// assert_eq!(first, bits![1, 0, 0, 1]);
+	/// assert_eq!(second, bits![0, 1]);
+	/// assert!(reader.is_empty());
+	/// ```
+	///
+	/// [`.decode()`]: Self::decode
+	/// [`ImageError`]: crate::image::ImageError
+	/// [`io::ErrorKind::InvalidData`]: std::io::ErrorKind::InvalidData
+	/// [`io::ErrorKind::UnexpectedEof`]: std::io::ErrorKind::UnexpectedEof
+	#[inline]
+	#[cfg(feature = "std")]
+	pub fn read_from<R>(mut reader: R) -> io::Result<Self>
+	where R: io::Read {
+		let mut head = [0; HEADER_LEN];
+		reader.read_exact(&mut head)?;
+		let needed = HEADER_LEN + Header::decode(&head)?.data_len();
+		let mut bytes = head.to_vec();
+		//  A damaged length must not cause a huge allocation up front, so the
+		//  buffer grows only as the stream supplies data.
+		reader
+			.by_ref()
+			.take((needed - HEADER_LEN) as u64)
+			.read_to_end(&mut bytes)?;
+		if bytes.len() < needed {
+			return Err(ImageError::Truncated {
+				needed,
+				len: bytes.len(),
+			}
+			.into());
+		}
+		Ok(Self::decode(&bytes)?)
+	}
+}
+
+#[doc = include_str!("../doc/image/ImageError.md")]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ImageError {
+	/// The buffer is shorter than the image.
+	Truncated {
+		/// The number of bytes that the image requires.
+		needed: usize,
+		/// The number of bytes in the buffer.
+		len:    usize,
+	},
+	/// The buffer does not begin with [`MAGIC`].
+	Magic,
+	/// The image was written in an unknown version of the format.
+	Version(u8),
+	/// The image records an element width other than 8, 16, 32, 64, or 128
+	/// bits.
+	Width(u8),
+	/// The image records an unknown bit-ordering.
+	Order(u8),
+	/// The image sets reserved header bits, or begins its bits outside the
+	/// first element.
+	Corrupt,
+	/// The image records more bits than a bit-slice can hold.
+	TooLong,
+	/// The checksum stored in the image does not match its contents.
+	Checksum {
+		/// The checksum stored in the header.
+		stored:   u32,
+		/// The checksum computed from the header and element data.
+		computed: u32,
+	},
+	/// The image is valid, but its memory layout cannot be viewed as the
+	/// requested bit-slice type.
+	Incompatible,
+	/// The element data is not aligned for the requested storage type.
+	Misaligned,
+}
+
+#[cfg(not(tarpaulin_include))]
+impl Display for ImageError {
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Truncated { needed, len } => write!(
+				fmt,
+				"bit-image requires {} bytes, but only {} are available",
+				needed, len,
+			),
+			Self::Magic => fmt.write_str("data is not a bit-image"),
+			Self::Version(version) => {
+				write!(fmt, "unsupported bit-image version {}", version)
+			},
+			Self::Width(width) => {
+				write!(fmt, "unsupported bit-image element width {}", width)
+			},
+			Self::Order(order) => {
+				write!(fmt, "unsupported bit-image bit-ordering {}", order)
+			},
+			Self::Corrupt => fmt.write_str("bit-image header is corrupt"),
+			Self::TooLong => {
+				fmt.write_str("bit-image is too long for a bit-slice")
+			},
+			Self::Checksum { stored, computed } => write!(
+				fmt,
+				"bit-image checksum {:#010x} does not match its contents \
+				 ({:#010x})",
+				stored, computed,
+			),
+			Self::Incompatible => fmt.write_str(
+				"bit-image layout does not match the requested bit-slice type",
+			),
+			Self::Misaligned => {
+				fmt.write_str("bit-image element data is misaligned")
+			},
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ImageError {}
+
+#[cfg(feature = "std")]
+impl From<ImageError> for io::Error {
+	#[inline]
+	fn from(err: ImageError) -> Self {
+		let kind = match err {
+			ImageError::Truncated { .. } => io::ErrorKind::UnexpectedEof,
+			_ => io::ErrorKind::InvalidData,
+		};
+		io::Error::new(kind, err)
+	}
+}
+
+/// The bit-orderings that a bit-image can record.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Order {
+	/// `Lsb0`.
+	Lsb0 = 0,
+	/// `Msb0`.
+	Msb0 = 1,
+}
+
+impl Order {
+	/// Finds the tag of a bit-ordering, if it has one.
+	fn of<O>() -> Option<Self>
+	where O: BitOrder {
+		let id = TypeId::of::<O>();
+		if id == TypeId::of::<Lsb0>() {
+			Some(Self::Lsb0)
+		}
+		else if id == TypeId::of::<Msb0>() {
+			Some(Self::Msb0)
+		}
+		else {
+			None
+		}
+	}
+}
+
+/// The contents of a bit-image header.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Header {
+	/// The width of each element, in bits.
+	width:      usize,
+	/// The ordering of bits within each element.
+	order:      Order,
+	/// Whether each element is stored most-significant byte first.
+	big_endian: bool,
+	/// The number of live bits.
+	len:        usize,
+	/// The index of the first live bit in the first element.
+	head:       usize,
+	/// The checksum of the rest of the header and the element data.
+	checksum:   u32,
+}
+
+impl Header {
+	/// Describes the image of a bit-slice, without its checksum.
+	///
+	/// Bit-slices with their own orderings are re-laid out as `Lsb0` elements,
+	/// so that any reader can decode them.
+	fn describe<T, O>(bits: &BitSlice<T, O>) -> Self
+	where
+		T: BitStore,
+		O: BitOrder,
+	{
+		let (order, head) = match Order::of::<O>() {
+			Some(order) if !bits.is_empty() => {
+				(order, bits.as_bitspan().head().into_inner() as usize)
+			},
+			Some(order) => (order, 0),
+			None => (Order::Lsb0, 0),
+		};
+		let width = bits_of::<T::Mem>();
+		Self {
+			width,
+			order,
+			//  Bytes have no byte order, so their images are always marked as
+			//  little-endian, and are identical on every machine.
+			big_endian: width > 8 && cfg!(target_endian = "big"),
+			len: bits.len(),
+			head,
+			checksum: 0,
+		}
+	}
+
+	/// Computes the number of bytes of element data.
+	fn data_len(&self) -> usize {
+		let bits = self.head + self.len;
+		(bits + self.width - 1) / self.width * (self.width / 8)
+	}
+
+	/// Serializes the header.
+	fn encode(&self) -> [u8; HEADER_LEN] {
+		let mut out = [0; HEADER_LEN];
+		out[.. 4].copy_from_slice(&MAGIC);
+		out[4] = VERSION;
+		out[5] = self.width as u8;
+		out[6] = self.order as u8;
+		out[7] = if self.big_endian { BIG_ENDIAN } else { 0 };
+		out[8 .. 16].copy_from_slice(&(self.len as u64).to_le_bytes());
+		out[16] = self.head as u8;
+		out[CHECKED_LEN ..].copy_from_slice(&self.checksum.to_le_bytes());
+		out
+	}
+
+	/// Deserializes and validates a header, without checking its checksum.
+	fn decode(bytes: &[u8]) -> Result<Self, ImageError> {
+		let head = bytes.get(.. HEADER_LEN).ok_or(ImageError::Truncated {
+			needed: HEADER_LEN,
+			len:    bytes.len(),
+		})?;
+		if head[.. 4] != MAGIC {
+			return Err(ImageError::Magic);
+		}
+		if head[4] != VERSION {
+			return Err(ImageError::Version(head[4]));
+		}
+		let width = match head[5] {
+			8 | 16 | 32 | 64 | 128 => head[5] as usize,
+			other => return Err(ImageError::Width(other)),
+		};
+		let order = match head[6] {
+			0 => Order::Lsb0,
+			1 => Order::Msb0,
+			other => return Err(ImageError::Order(other)),
+		};
+		let head_idx = head[16] as usize;
+		if head[7] & !BIG_ENDIAN != 0
+			|| head[17 .. CHECKED_LEN] != [0; 3]
+			|| head_idx >= width
+		{
+			return Err(ImageError::Corrupt);
+		}
+		let mut len = [0; 8];
+		len.copy_from_slice(&head[8 .. 16]);
+		let len = usize::try_from(u64::from_le_bytes(len))
+			.ok()
+			.filter(|&len| len <= BitSlice::<u8, Lsb0>::MAX_BITS)
+			.ok_or(ImageError::TooLong)?;
+		let mut checksum = [0; 4];
+		checksum.copy_from_slice(&head[CHECKED_LEN ..]);
+		Ok(Self {
+			width,
+			order,
+			big_endian: head[7] & BIG_ENDIAN != 0,
+			len,
+			head: head_idx,
+			checksum: u32::from_le_bytes(checksum),
+		})
+	}
+
+	/// Decodes a header, and finds and verifies the element data after it.
+	fn open(bytes: &[u8]) -> Result<(Self, &[u8]), ImageError> {
+		let header = Self::decode(bytes)?;
+		let needed = HEADER_LEN + header.data_len();
+		let data =
+			bytes
+				.get(HEADER_LEN .. needed)
+				.ok_or(ImageError::Truncated {
+					needed,
+					len: bytes.len(),
+				})?;
+		let computed = header.checksum(data);
+		if computed != header.checksum {
+			return Err(ImageError::Checksum {
+				stored: header.checksum,
+				computed,
+			});
+		}
+		Ok((header, data))
+	}
+
+	/// Computes the checksum of the header and its element data.
+	fn checksum(&self, data: &[u8]) -> u32 {
+		let mut crc = Crc32::new();
+		crc.update(&self.encode()[.. CHECKED_LEN]);
+		crc.update(data);
+		crc.finish()
+	}
+}
+
+/// Produces the element data of a bit-image, in chunks of whole elements.
+fn write_data<T, O, E>(
+	bits: &BitSlice<T, O>,
+	header: &Header,
+	sink: impl FnMut(&[u8]) -> Result<(), E>,
+) -> Result<(), E>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	if Order::of::<O>().is_some() {
+		write_elements(bits.domain().map(|elem| elem.as_u128()), header, sink)
+	}
+	else {
+		let elems = bits
+			.chunks(header.width)
+			.map(|chunk| chunk.iter_ones().fold(0, |elem, idx| elem | 1 << idx));
+		write_elements(elems, header, sink)
+	}
+}
+
+/// Serializes elements in the byte order recorded in a header.
+fn write_elements<E>(
+	elems: impl Iterator<Item = u128>,
+	header: &Header,
+	mut sink: impl FnMut(&[u8]) -> Result<(), E>,
+) -> Result<(), E> {
+	let step = header.width / 8;
+	let mut buf = [0; CHUNK];
+	let mut fill = 0;
+	for elem in elems {
+		if fill == CHUNK {
+			sink(&buf)?;
+			fill = 0;
+		}
+		let dest = &mut buf[fill .. fill + step];
+		if header.big_endian {
+			dest.copy_from_slice(&elem.to_be_bytes()[16 - step ..]);
+		}
+		else {
+			dest.copy_from_slice(&elem.to_le_bytes()[.. step]);
+		}
+		fill += step;
+	}
+	sink(&buf[.. fill])
+}
+
+/// The lookup table for the reflected CRC-32 polynomial used by zlib, PNG,
+/// and Ethernet.
+const CRC_TABLE: [u32; 256] = crc_table();
+
+/// Builds [`CRC_TABLE`].
+const fn crc_table() -> [u32; 256] {
+	let mut table = [0; 256];
+	let mut idx = 0;
+	while idx < 256 {
+		let mut crc = idx as u32;
+		let mut bit = 0;
+		while bit < 8 {
+			crc = if crc & 1 == 1 {
+				0xEDB8_8320 ^ (crc >> 1)
+			}
+			else {
+				crc >> 1
+			};
+			bit += 1;
+		}
+		table[idx] = crc;
+		idx += 1;
+	}
+	table
+}
+
+/// A running CRC-32 checksum.
+struct Crc32(u32);
+
+impl Crc32 {
+	/// Starts a new checksum.
+	fn new() -> Self {
+		Self(!0)
+	}
+
+	/// Adds bytes to the checksum.
+	fn update(&mut self, bytes: &[u8]) {
+		for &byte in bytes {
+			let idx = (self.0 ^ byte as u32) as u8 as usize;
+			self.0 = CRC_TABLE[idx] ^ (self.0 >> 8);
+		}
+	}
+
+	/// Produces the checksum of all bytes added so far.
+	fn finish(&self) -> u32 {
+		!self.0
+	}
+}
@@ -0,0 +1,155 @@
+//! Unit tests for bit-images.
+
+#![cfg(test)]
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+
+use super::*;
+
+#[test]
+fn golden() {
+	let mut buf = [0; 32];
+	let len = bits![u8, Msb0; 1, 0, 1].encode_into(&mut buf).unwrap();
+	assert_eq!(buf[.. len], [
+		b'B', b'I', b'T', b'V', 1, 8, 1, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		0x1A, 0xCE, 0x47, 0x97, 0xA0,
+	]);
+	assert_eq!(BitSlice::<u8, Msb0>::from_encoded(&buf).unwrap(), bits![
+		1, 0, 1
+	]);
+	assert_eq!(
+		bits![u8, Msb0; 1, 0, 1].encode_into(&mut buf[.. 24]),
+		Err(ImageError::Truncated {
+			needed: 25,
+			len:    24,
+		})
+	);
+}
+
+#[test]
+fn views() {
+	let mut data = [0u32; 8];
+	let bytes = unsafe {
+		core::slice::from_raw_parts_mut(data.as_mut_ptr().cast::<u8>(), 32)
+	};
+	let bits = bits![u32, Lsb0; 0, 1, 1, 0, 1];
+	let len = bits[1 ..].encode_into(bytes).unwrap();
+	assert_eq!(len, 28);
+	let bytes =
+		unsafe { core::slice::from_raw_parts(data.as_ptr().cast::<u8>(), len) };
+
+	let view = BitSlice::<u32, Lsb0>::from_encoded(bytes).unwrap();
+	assert_eq!(view, bits![1, 1, 0, 1]);
+	assert_eq!(view.as_bitspan().head().into_inner(), 1);
+
+	assert_eq!(
+		BitSlice::<u32, Msb0>::from_encoded(bytes),
+		Err(ImageError::Incompatible)
+	);
+	assert_eq!(
+		BitSlice::<u16, Lsb0>::from_encoded(bytes),
+		Err(ImageError::Incompatible)
+	);
+}
+
+#[test]
+fn damage() {
+	let mut buf = [0; 40];
+	let len = bits![u16, Lsb0; 1; 12].encode_into(&mut buf).unwrap();
+	let image = &buf[.. len];
+
+	let mut bad = [0; 40];
+	bad[.. len].copy_from_slice(image);
+	bad[25] ^= 1;
+	assert!(matches!(
+		BitSlice::<u16, Lsb0>::from_encoded(&bad[.. len]),
+		Err(ImageError::Checksum { .. })
+	));
+
+	for (idx, byte, err) in [
+		(0, 0, ImageError::Magic),
+		(4, 2, ImageError::Version(2)),
+		(5, 12, ImageError::Width(12)),
+		(6, 2, ImageError::Order(2)),
+		(7, 2, ImageError::Corrupt),
+		(16, 16, ImageError::Corrupt),
+		(17, 1, ImageError::Corrupt),
+	] {
+		bad[.. len].copy_from_slice(image);
+		bad[idx] = byte;
+		assert_eq!(BitSlice::<u16, Lsb0>::from_encoded(&bad[.. len]), Err(err));
+	}
+
+	assert_eq!(
+		BitSlice::<u16, Lsb0>::from_encoded(&image[.. len - 1]),
+		Err(ImageError::Truncated {
+			needed: len,
+			len:    len - 1,
+		})
+	);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn conversions() {
+	let src = bitvec![u64, Msb0; 1, 0, 0, 1, 1, 0, 1, 0, 1, 1, 1, 0, 0, 0, 1];
+	let src = &src[3 ..];
+	let mut buf = vec![0; src.encoded_len()];
+	src.encode_into(&mut buf).unwrap();
+
+	assert_eq!(BitVec::<u8, Lsb0>::decode(&buf).unwrap(), src);
+	assert_eq!(BitVec::<u16, Msb0>::decode(&buf).unwrap(), src);
+	assert_eq!(BitVec::<u64, Msb0>::decode(&buf).unwrap(), src);
+	assert_eq!(BitVec::<usize, Lsb0>::decode(&buf).unwrap(), src);
+	#[cfg(feature = "u128")]
+	{
+		let wide = BitVec::<u128, Lsb0>::decode(&buf).unwrap();
+		assert_eq!(wide, src);
+
+		let mut wide_buf = vec![0; wide.encoded_len()];
+		wide.encode_into(&mut wide_buf).unwrap();
+		assert_eq!(wide_buf[5], 128);
+		assert_eq!(BitVec::<u8, Msb0>::decode(&wide_buf).unwrap(), src);
+	}
+
+	//  Rewrite the image in the opposite byte order, and read it again.
+	let mut swapped = buf.clone();
+	swapped[7] ^= BIG_ENDIAN;
+	for elem in swapped[HEADER_LEN ..].chunks_exact_mut(8) {
+		elem.reverse();
+	}
+	let mut header = Header::decode(&swapped).unwrap();
+	header.checksum = header.checksum(&swapped[HEADER_LEN ..]);
+	swapped[.. HEADER_LEN].copy_from_slice(&header.encode());
+	assert_eq!(BitVec::<u32, Lsb0>::decode(&swapped).unwrap(), src);
+
+	let empty = BitVec::<u8, Msb0>::new();
+	let mut buf = vec![0; empty.encoded_len()];
+	assert_eq!(empty.encode_into(&mut buf), Ok(HEADER_LEN));
+	assert!(BitVec::<u32, Lsb0>::decode(&buf).unwrap().is_empty());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn streams() {
+	let first = bitvec![u32, Lsb0; 1; 100];
+	let second = bitvec![u8, Msb0; 0, 1, 0];
+	let mut file = Vec::new();
+	first.write_to(&mut file).unwrap();
+	second.write_to(&mut file).unwrap();
+	assert_eq!(file.len(), first.encoded_len() + second.encoded_len());
+
+	let mut reader = &file[..];
+	assert_eq!(BitVec::<u8, Lsb0>::read_from(&mut reader).unwrap(), first);
+	assert_eq!(BitVec::<u16, Msb0>::read_from(&mut reader).unwrap(), second);
+	let err = BitVec::<u8, Lsb0>::read_from(&mut reader).unwrap_err();
+	assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+	let err = BitVec::<u8, Lsb0>::read_from(&file[.. 30]).unwrap_err();
+	assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+	let mut bad = file.clone();
+	bad[0] = 0;
+	let err = BitVec::<u8, Lsb0>::read_from(&bad[..]).unwrap_err();
+	assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
@@ -26,14 +26,25 @@ pub mod macros;
 
 pub mod access;
 pub mod array;
+pub mod atomic;
+pub mod bloom;
 pub mod boxed;
+pub mod codec;
 pub mod domain;
 pub mod field;
+pub mod image;
 pub mod index;
 pub mod mem;
+pub mod mmap;
 pub mod order;
+pub mod par;
 pub mod ptr;
-mod serdes;
+pub mod rank;
+pub mod roaring;
+pub mod runs;
+pub mod serdes;
+pub mod set;
+mod simd;
 pub mod slice;
 pub mod store;
 pub mod vec;
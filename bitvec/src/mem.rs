@@ -42,6 +42,24 @@ impl BitRegister for u64 {
 
 register!(usize);
 
+/** `u128` is only a register when the `u128` feature is enabled.
+
+`bitvec` stores the index of a bit-slice’s first bit in the low bits of its
+element pointer, and so requires each element to be aligned to its own size.
+Older compilers and some targets only align `u128` to 8 bytes; this fails to
+compile there.
+**/
+#[cfg(feature = "u128")]
+impl BitRegister for u128 {
+	const ALL: Self = !0;
+}
+
+#[cfg(feature = "u128")]
+const _: () = assert!(
+	aligned_to_size::<u128>(),
+	"`u128` storage requires `u128` to be aligned to 16 bytes",
+);
+
 /// Counts the number of bits in a value of type `T`.
 pub const fn bits_of<T>() -> usize {
 	core::mem::size_of::<T>().saturating_mul(<u8>::BITS as usize)
@@ -126,6 +144,38 @@ element!("64", u64 => AtomicU64);
 
 element!("ptr", usize => AtomicUsize);
 
+#[cfg(feature = "u128")]
+impl BitElement<u128> {
+	/// Creates a new element wrapper from a raw integer.
+	pub const fn new(elem: u128) -> Self {
+		Self { elem }
+	}
+}
+
+#[cfg(feature = "u128")]
+impl BitElement<Cell<u128>> {
+	/// Creates a new element wrapper from a raw integer.
+	pub const fn new(elem: u128) -> Self {
+		Self {
+			elem: Cell::new(elem),
+		}
+	}
+}
+
+#[cfg(all(
+	feature = "u128",
+	target_arch = "x86_64",
+	target_feature = "cmpxchg16b",
+))]
+impl BitElement<radium::types::AtomicU128> {
+	/// Creates a new element wrapper from a raw integer.
+	pub const fn new(elem: u128) -> Self {
+		Self {
+			elem: radium::types::AtomicU128::new(elem),
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -155,5 +205,12 @@ mod tests {
 			assert!(aligned_to_size::<BitSafeU64>());
 			assert!(layout_eq::<u64, BitSafeU64>());
 		}
+
+		#[cfg(feature = "u128")]
+		{
+			assert!(aligned_to_size::<u128>());
+			assert!(aligned_to_size::<BitSafeU128>());
+			assert!(layout_eq::<u128, BitSafeU128>());
+		}
 	}
 }
@@ -0,0 +1,391 @@
+#![cfg(feature = "mmap")]
+#![doc = include_str!("../doc/mmap.md")]
+
+use core::{
+	cmp,
+	fmt::{
+		self,
+		Debug,
+		Formatter,
+	},
+	marker::PhantomData,
+	mem,
+	ops::{
+		Deref,
+		DerefMut,
+	},
+};
+use std::{
+	fs::File,
+	io,
+};
+
+use memmap2::{
+	MmapMut,
+	MmapOptions,
+};
+use wyz::comu::Address;
+
+use crate::{
+	mem::bits_of,
+	order::{
+		BitOrder,
+		Lsb0,
+	},
+	ptr::check_alignment,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+mod tests;
+
+#[doc = include_str!("../doc/mmap/MappedBitSlice.md")]
+pub struct MappedBitSlice<T = u8, O = Lsb0>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// The file that backs the bit-slice.
+	file: File,
+	/// A writable, shared mapping of the whole file.
+	map:  MmapMut,
+	/// The number of live bits at the front of the mapping.
+	len:  usize,
+	/// The storage type and bit ordering of the mapped memory.
+	_typ: PhantomData<(T, O)>,
+}
+
+/// Constructors and accessors.
+impl<T, O> MappedBitSlice<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// Maps a file as a bit-slice of `len` bits.
+	///
+	/// If the file is too short to hold `len` bits, it is extended with zeroed
+	/// bytes. Longer files are mapped in full, and their excess bytes become
+	/// spare capacity.
+	///
+	/// The bit length is not stored in the file. The caller must record it
+	/// elsewhere if the file is to be mapped again later.
+	///
+	/// ## Parameters
+	///
+	/// - `file`: A file opened for both reading and writing.
+	/// - `len`: The number of bits in the mapped bit-slice.
+	///
+	/// ## Errors
+	///
+	/// This fails if the file cannot be resized or mapped, or if the mapping is
+	/// too large to be a `BitSlice<T, O>`.
+	///
+	/// ## Safety
+	///
+	/// The caller must ensure that, for as long as the returned value lives,
+	/// no other process, thread, or mapping modifies or truncates the file.
+	/// Changes made to the file from outside of this mapping are changes to
+	/// memory that Rust considers to be exclusively borrowed, and truncating
+	/// the file makes accesses to the mapping fault. This is the same contract
+	/// as [`MmapOptions::map_mut`].
+	///
+	/// [`MmapOptions::map_mut`]: memmap2::MmapOptions::map_mut
+	#[inline]
+	pub unsafe fn new(file: File, len: usize) -> io::Result<Self> {
+		let needed = bytes_for::<T>(len)?;
+		if file.metadata()?.len() < needed {
+			file.set_len(needed)?;
+		}
+		let map = map::<T, O>(&file)?;
+		if len > map.len() / mem::size_of::<T>() * bits_of::<T::Mem>() {
+			return Err(too_long());
+		}
+		Ok(Self {
+			file,
+			map,
+			len,
+			_typ: PhantomData,
+		})
+	}
+
+	/// Gets the number of live bits.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Tests if there are no live bits.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Gets the number of bits that the current mapping can hold without
+	/// growing the file.
+	#[inline]
+	pub fn capacity(&self) -> usize {
+		self.elements() * bits_of::<T::Mem>()
+	}
+
+	/// Views the mapped bits as a bit-slice.
+	#[inline]
+	pub fn as_bitslice(&self) -> &BitSlice<T, O> {
+		let elts = unsafe {
+			core::slice::from_raw_parts(
+				self.map.as_ptr().cast::<T>(),
+				self.elements(),
+			)
+		};
+		unsafe {
+			BitSlice::from_slice_unchecked(elts).get_unchecked(.. self.len)
+		}
+	}
+
+	/// Views the mapped bits as a mutable bit-slice.
+	#[inline]
+	pub fn as_mut_bitslice(&mut self) -> &mut BitSlice<T, O> {
+		let len = self.len;
+		let elts = unsafe {
+			core::slice::from_raw_parts_mut(
+				self.map.as_mut_ptr().cast::<T>(),
+				self.elements(),
+			)
+		};
+		unsafe {
+			BitSlice::from_slice_unchecked_mut(elts).get_unchecked_mut(.. len)
+		}
+	}
+
+	/// Gets the backing file.
+	#[inline]
+	pub fn file(&self) -> &File {
+		&self.file
+	}
+
+	/// Flushes the mapping to disk and returns the backing file.
+	///
+	/// ## Errors
+	///
+	/// This fails if the mapping cannot be flushed.
+	#[inline]
+	pub fn into_file(self) -> io::Result<File> {
+		self.map.flush()?;
+		Ok(self.file)
+	}
+
+	/// Counts the whole `T` elements in the mapping.
+	fn elements(&self) -> usize {
+		self.map.len() / mem::size_of::<T>()
+	}
+}
+
+/// Resizing and persistence.
+impl<T, O> MappedBitSlice<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// Ensures that at least `additional` more bits can be held without
+	/// growing the file.
+	///
+	/// Like `BitVec`, the capacity at least doubles each time that it grows,
+	/// so that repeated pushes only remap the file a logarithmic number of
+	/// times.
+	///
+	/// ## Errors
+	///
+	/// This fails if the file cannot be extended or remapped. If the file was
+	/// extended but could not be mapped again, the bit-slice becomes empty.
+	#[inline]
+	pub fn reserve(&mut self, additional: usize) -> io::Result<()> {
+		let needed = self
+			.len
+			.checked_add(additional)
+			.filter(|&bits| bits <= BitSlice::<T, O>::MAX_BITS)
+			.ok_or_else(too_long)?;
+		let capacity = self.capacity();
+		if needed <= capacity {
+			return Ok(());
+		}
+		let target = cmp::max(needed, capacity.saturating_mul(2));
+		self.remap(bytes_for::<T>(cmp::min(
+			target,
+			BitSlice::<T, O>::MAX_BITS,
+		))?)
+	}
+
+	/// Resizes the bit-slice in place, so that its length is `new_len`.
+	///
+	/// If `new_len` is greater than the current length, the new bits are set
+	/// to `value`, growing the file if necessary. If it is less, the excess
+	/// bits are discarded, but the file keeps its size.
+	///
+	/// ## Errors
+	///
+	/// This fails if the file needs to grow, and cannot be extended or
+	/// remapped.
+	#[inline]
+	pub fn resize(&mut self, new_len: usize, value: bool) -> io::Result<()> {
+		let old_len = self.len;
+		if new_len > old_len {
+			self.reserve(new_len - old_len)?;
+		}
+		self.len = new_len;
+		if new_len > old_len {
+			self.as_mut_bitslice()[old_len ..].fill(value);
+		}
+		Ok(())
+	}
+
+	/// Appends a single bit, growing the file if necessary.
+	///
+	/// ## Errors
+	///
+	/// This fails if the file needs to grow, and cannot be extended or
+	/// remapped.
+	#[inline]
+	pub fn push(&mut self, value: bool) -> io::Result<()> {
+		self.resize(self.len + 1, value)
+	}
+
+	/// Shortens the bit-slice, keeping the first `new_len` bits.
+	///
+	/// This has no effect if `new_len` is not less than the current length. The
+	/// file keeps its size.
+	#[inline]
+	pub fn truncate(&mut self, new_len: usize) {
+		self.len = cmp::min(self.len, new_len);
+	}
+
+	/// Shrinks the file to the smallest size that holds the live bits.
+	///
+	/// ## Errors
+	///
+	/// This fails if the file cannot be truncated or remapped.
+	#[inline]
+	pub fn shrink_to_fit(&mut self) -> io::Result<()> {
+		self.remap(bytes_for::<T>(self.len)?)
+	}
+
+	/// Writes all modified bits to disk, and waits for the write to complete.
+	///
+	/// ## Errors
+	///
+	/// This fails if the operating system reports an error while flushing.
+	#[inline]
+	pub fn flush(&self) -> io::Result<()> {
+		self.map.flush()
+	}
+
+	/// Begins writing all modified bits to disk, without waiting for the
+	/// write to complete.
+	///
+	/// ## Errors
+	///
+	/// This fails if the operating system reports an error while scheduling
+	/// the write.
+	#[inline]
+	pub fn flush_async(&self) -> io::Result<()> {
+		self.map.flush_async()
+	}
+
+	/// Resizes the backing file to `bytes` bytes and maps it again.
+	fn remap(&mut self, bytes: u64) -> io::Result<()> {
+		self.map.flush()?;
+		//  Some platforms refuse to resize a file while it is mapped, so the
+		//  old mapping is released first.
+		drop(mem::replace(&mut self.map, MmapMut::map_anon(0)?));
+		let resized = self.file.set_len(bytes);
+		//  Whether or not the resize succeeded, map the file at its current
+		//  size, and drop any live bits that no longer fit.
+		//  The caller of `new` guaranteed exclusive use of the file.
+		let remapped =
+			unsafe { map::<T, O>(&self.file) }.map(|map| self.map = map);
+		self.len = cmp::min(self.len, self.capacity());
+		resized.and(remapped)
+	}
+}
+
+impl<T, O> Deref for MappedBitSlice<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	type Target = BitSlice<T, O>;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		self.as_bitslice()
+	}
+}
+
+impl<T, O> DerefMut for MappedBitSlice<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.as_mut_bitslice()
+	}
+}
+
+impl<T, O> Debug for MappedBitSlice<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_struct("MappedBitSlice")
+			.field("file", &self.file)
+			.field("len", &self.len)
+			.field("capacity", &self.capacity())
+			.finish()
+	}
+}
+
+/// Maps a whole file, and checks that it can be viewed as a `BitSlice<T, O>`.
+///
+/// ## Safety
+///
+/// The file must not be modified or truncated except through the mapping for
+/// as long as the mapping lives.
+unsafe fn map<T, O>(file: &File) -> io::Result<MmapMut>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	let map = MmapOptions::new().map_mut(file)?;
+	let addr = Address::try_from(map.as_ptr().cast::<T>()).map_err(|_| {
+		io::Error::new(io::ErrorKind::InvalidData, "mapped a null address")
+	})?;
+	check_alignment(addr)
+		.map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+	let elts = unsafe {
+		core::slice::from_raw_parts(
+			addr.to_const(),
+			map.len() / mem::size_of::<T>(),
+		)
+	};
+	BitSlice::<T, O>::try_from_slice(elts)
+		.map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+	Ok(map)
+}
+
+/// Computes the number of bytes needed to store `bits` bits in `T` elements.
+fn bytes_for<T>(bits: usize) -> io::Result<u64>
+where T: BitStore {
+	let elts = crate::mem::elts::<T::Mem>(bits);
+	elts.checked_mul(mem::size_of::<T>())
+		.map(|bytes| bytes as u64)
+		.ok_or_else(too_long)
+}
+
+/// Reports a bit length that cannot be mapped.
+fn too_long() -> io::Error {
+	io::Error::new(
+		io::ErrorKind::InvalidInput,
+		"bit length is too large to map",
+	)
+}
@@ -0,0 +1,133 @@
+//! Unit tests for memory-mapped bit-slices.
+
+#![cfg(test)]
+
+use std::{
+	fs::{
+		self,
+		OpenOptions,
+	},
+	path::PathBuf,
+	process,
+	sync::atomic::{
+		AtomicUsize,
+		Ordering,
+	},
+};
+
+use super::*;
+use crate::order::Msb0;
+
+/// A temporary file that is deleted when dropped.
+struct Scratch(PathBuf);
+
+impl Scratch {
+	/// Creates an empty file with a name unique to this test run.
+	fn new() -> (Self, File) {
+		static COUNT: AtomicUsize = AtomicUsize::new(0);
+		let path = std::env::temp_dir().join(format!(
+			"bitvec-mmap-{}-{}.bits",
+			process::id(),
+			COUNT.fetch_add(1, Ordering::Relaxed),
+		));
+		let file = OpenOptions::new()
+			.read(true)
+			.write(true)
+			.create(true)
+			.truncate(true)
+			.open(&path)
+			.unwrap();
+		(Self(path), file)
+	}
+
+	/// Reads the file contents from disk.
+	fn read(&self) -> Vec<u8> {
+		fs::read(&self.0).unwrap()
+	}
+}
+
+impl Drop for Scratch {
+	fn drop(&mut self) {
+		let _ = fs::remove_file(&self.0);
+	}
+}
+
+/// Maps a scratch file.
+fn map<T, O>(file: File, len: usize) -> MappedBitSlice<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	//  Each scratch file has a unique name, and is only used by one test.
+	unsafe { MappedBitSlice::new(file, len) }.unwrap()
+}
+
+#[test]
+fn map_and_persist() {
+	let (scratch, file) = Scratch::new();
+	let mut bits = map::<u8, Msb0>(file, 20);
+	assert_eq!(bits.len(), 20);
+	assert_eq!(bits.capacity(), 24);
+	assert!(bits.not_any());
+
+	bits.set(0, true);
+	bits[12 ..].fill(true);
+	bits.flush().unwrap();
+	assert_eq!(scratch.read(), [0x80, 0x0F, 0xF0]);
+
+	let file = bits.into_file().unwrap();
+	let bits = map::<u8, Msb0>(file, 16);
+	assert_eq!(bits.capacity(), 24);
+	assert_eq!(bits.count_ones(), 5);
+	assert_eq!(bits.as_bitslice(), bits![
+		1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1
+	]);
+}
+
+#[test]
+fn grow_and_shrink() {
+	let (scratch, file) = Scratch::new();
+	let mut bits = map::<u16, Lsb0>(file, 0);
+	assert!(bits.is_empty());
+	assert_eq!(bits.capacity(), 0);
+
+	for idx in 0 .. 100 {
+		bits.push(idx % 3 == 0).unwrap();
+	}
+	assert_eq!(bits.len(), 100);
+	assert_eq!(bits.count_ones(), 34);
+	assert!(bits.capacity() >= 100);
+	assert_eq!(bits.capacity() % 16, 0);
+
+	bits.resize(130, true).unwrap();
+	assert!(bits[100 ..].all());
+	bits.truncate(17);
+	bits.resize(20, false).unwrap();
+	assert_eq!(bits.count_ones(), 6);
+
+	bits.shrink_to_fit().unwrap();
+	assert_eq!(bits.capacity(), 32);
+	assert_eq!(scratch.read().len(), 4);
+	assert_eq!(bits.count_ones(), 6);
+}
+
+#[test]
+fn existing_contents() {
+	let (scratch, file) = Scratch::new();
+	fs::write(&scratch.0, [0xA5, 0x3C, 0xFF]).unwrap();
+
+	//  A trailing partial element is not mapped.
+	let mut bits = map::<u16, Lsb0>(file, 16);
+	assert_eq!(bits.capacity(), 16);
+	assert_eq!(bits.count_ones(), 8);
+
+	bits.set(15, true);
+	let file = bits.into_file().unwrap();
+	let elem = u16::from_ne_bytes([0xA5, 0x3C]) | 0x8000;
+	let [lo, hi] = elem.to_ne_bytes();
+	assert_eq!(scratch.read(), [lo, hi, 0xFF]);
+
+	let bits = map::<u8, Lsb0>(file, 24);
+	assert_eq!(bits.count_ones(), 17);
+	assert!(bits[16 ..].all());
+}
@@ -245,6 +245,9 @@ where O: BitOrder {
 
 	#[cfg(target_pointer_width = "64")]
 	verify_for_type::<u64, O>(verbose);
+
+	#[cfg(feature = "u128")]
+	verify_for_type::<u128, O>(verbose);
 }
 
 /// Verification does not access memory, and is both useless and slow in Miri.
@@ -461,6 +464,12 @@ mod tests {
 		fn verify_usize() {
 			verify_for_type::<usize, Lsb0>(cfg!(feature = "verbose"));
 		}
+
+		#[test]
+		#[cfg(all(feature = "u128", not(tarpaulin)))]
+		fn verify_u128() {
+			verify_for_type::<u128, Lsb0>(cfg!(feature = "verbose"));
+		}
 	}
 
 	mod msb0 {
@@ -494,6 +503,12 @@ mod tests {
 		fn verify_usize() {
 			verify_for_type::<usize, Msb0>(cfg!(feature = "verbose"));
 		}
+
+		#[test]
+		#[cfg(all(feature = "u128", not(tarpaulin)))]
+		fn verify_u128() {
+			verify_for_type::<u128, Msb0>(cfg!(feature = "verbose"));
+		}
 	}
 
 	mod hilo {
@@ -527,5 +542,11 @@ mod tests {
 		fn verify_usize() {
 			verify_for_type::<usize, HiLo>(cfg!(feature = "verbose"));
 		}
+
+		#[test]
+		#[cfg(all(feature = "u128", not(tarpaulin)))]
+		fn verify_u128() {
+			verify_for_type::<u128, HiLo>(cfg!(feature = "verbose"));
+		}
 	}
 }
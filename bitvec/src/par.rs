@@ -0,0 +1,549 @@
+#![cfg(feature = "rayon")]
+#![doc = include_str!("../doc/par.md")]
+
+use core::{
+	cmp,
+	mem,
+};
+
+use rayon::iter::{
+	IndexedParallelIterator,
+	ParallelIterator,
+	plumbing::{
+		Consumer,
+		Producer,
+		ProducerCallback,
+		UnindexedConsumer,
+		bridge,
+	},
+};
+
+use crate::{
+	mem::bits_of,
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+mod tests;
+
+/// The number of bits handed to each task by the bulk operations.
+///
+/// This is large enough that the per-task overhead is negligible next to the
+/// work done on the bits, and small enough that large bit-slices still divide
+/// evenly across many threads.
+const GRAIN: usize = 1 << 16;
+
+/// Parallel operations.
+impl<T, O> BitSlice<T, O>
+where
+	T: BitStore + Sync,
+	O: BitOrder,
+{
+	/// Iterates in parallel over subslices that do not share any memory
+	/// element.
+	///
+	/// `chunk_size` is rounded up to a whole number of `T` elements, and the
+	/// chunks are aligned to memory rather than to the start of `self`: the
+	/// first chunk runs from the start of `self` to the first chunk boundary,
+	/// and may be shorter. Bit-slices that begin at the start of an element,
+	/// such as every `BitVec` and `BitArray`, are chunked exactly like
+	/// [`.chunks()`] with the rounded size.
+	///
+	/// ## Panics
+	///
+	/// This panics if `chunk_size` is `0`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	/// use rayon::prelude::*;
+	///
+	/// let data = [0u8, 1, 2, 3];
+	/// let bits = &data.view_bits::<Lsb0>()[4 ..];
+	/// let lens = bits
+	///   .par_chunks(12)
+	///   .map(|chunk| chunk.len())
+	///   .collect::<Vec<_>>();
+	/// assert_eq!(lens, [12, 16]);
+	/// ```
+	///
+	/// [`.chunks()`]: Self::chunks
+	#[inline]
+	pub fn par_chunks(&self, chunk_size: usize) -> ParChunks<'_, T, O> {
+		ParChunks {
+			inner: AlignedChunks::new(self, chunk_size),
+		}
+	}
+
+	/// Iterates in parallel over mutable subslices that do not share any
+	/// memory element.
+	///
+	/// This has the same division logic as [`.par_chunks()`]. Because no two
+	/// chunks touch the same element, they do not need to be marked as
+	/// aliased, and each task has direct, unsynchronized access to its memory.
+	///
+	/// ## Panics
+	///
+	/// This panics if `chunk_size` is `0`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	/// use rayon::prelude::*;
+	///
+	/// let mut bv = bitvec![u16, Msb0; 0; 100];
+	/// bv.par_chunks_mut(32).for_each(|chunk| chunk.set(0, true));
+	/// assert_eq!(bv.iter_ones().collect::<Vec<_>>(), [0, 32, 64, 96]);
+	/// ```
+	///
+	/// [`.par_chunks()`]: Self::par_chunks
+	#[inline]
+	pub fn par_chunks_mut(
+		&mut self,
+		chunk_size: usize,
+	) -> ParChunksMut<'_, T, O> {
+		ParChunksMut {
+			inner: AlignedChunksMut::new(self, chunk_size),
+		}
+	}
+
+	/// Counts the number of bits set to `1`, in parallel.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bv = bitvec![1; 1000];
+	/// assert_eq!(bv[3 ..].par_count_ones(), 997);
+	/// ```
+	#[inline]
+	pub fn par_count_ones(&self) -> usize {
+		self.par_chunks(GRAIN).map(Self::count_ones).sum()
+	}
+
+	/// Counts the number of bits cleared to `0`, in parallel.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bv = bitvec![1; 1000];
+	/// assert_eq!(bv[3 ..].par_count_zeros(), 0);
+	/// ```
+	#[inline]
+	pub fn par_count_zeros(&self) -> usize {
+		self.par_chunks(GRAIN).map(Self::count_zeros).sum()
+	}
+
+	/// Enumerates the index of each bit set to `1`, in parallel.
+	///
+	/// The indices are produced in ascending order, so collecting them yields
+	/// the same sequence as [`.iter_ones()`].
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	/// use rayon::prelude::*;
+	///
+	/// let bits = bits![0, 1, 0, 0, 1];
+	/// let ones = bits.par_iter_ones().collect::<Vec<_>>();
+	/// assert_eq!(ones, [1, 4]);
+	/// ```
+	///
+	/// [`.iter_ones()`]: Self::iter_ones
+	#[inline]
+	pub fn par_iter_ones(&self) -> impl '_ + ParallelIterator<Item = usize> {
+		let chunks = self.par_chunks(GRAIN);
+		let (width, pad) = (chunks.inner.width, chunks.inner.pad);
+		chunks.enumerate().flat_map_iter(move |(idx, chunk)| {
+			let start = chunk_start(idx, width, pad);
+			chunk.iter_ones().map(move |bit| start + bit)
+		})
+	}
+
+	/// Writes a new value into every bit, in parallel.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![0; 1000];
+	/// bv[5 ..].par_fill(true);
+	/// assert_eq!(bv.count_ones(), 995);
+	/// ```
+	#[inline]
+	pub fn par_fill(&mut self, value: bool) {
+		self.par_chunks_mut(GRAIN)
+			.for_each(|chunk| chunk.fill(value));
+	}
+
+	/// Merges another bit-slice into `self` with Boolean AND, in parallel.
+	///
+	/// This has the same behavior as the `&=` operator, including when `rhs`
+	/// is shorter than `self`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![1; 1000];
+	/// bv.par_bitand_assign(&bitvec![0, 1].repeat(500));
+	/// assert_eq!(bv.count_ones(), 500);
+	/// ```
+	#[inline]
+	pub fn par_bitand_assign<T2, O2>(&mut self, rhs: &BitSlice<T2, O2>)
+	where
+		T2: BitStore + Sync,
+		O2: BitOrder,
+	{
+		self.par_zip(rhs, |this, that| *this &= that);
+	}
+
+	/// Merges another bit-slice into `self` with Boolean OR, in parallel.
+	///
+	/// This has the same behavior as the `|=` operator, including when `rhs`
+	/// is shorter than `self`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![0; 1000];
+	/// bv.par_bitor_assign(&bitvec![0, 1].repeat(500));
+	/// assert_eq!(bv.count_ones(), 500);
+	/// ```
+	#[inline]
+	pub fn par_bitor_assign<T2, O2>(&mut self, rhs: &BitSlice<T2, O2>)
+	where
+		T2: BitStore + Sync,
+		O2: BitOrder,
+	{
+		self.par_zip(rhs, |this, that| *this |= that);
+	}
+
+	/// Merges another bit-slice into `self` with Boolean XOR, in parallel.
+	///
+	/// This has the same behavior as the `^=` operator, including when `rhs`
+	/// is shorter than `self`.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![1; 1000];
+	/// bv.par_bitxor_assign(&bitvec![0, 1].repeat(500));
+	/// assert_eq!(bv.count_ones(), 500);
+	/// ```
+	#[inline]
+	pub fn par_bitxor_assign<T2, O2>(&mut self, rhs: &BitSlice<T2, O2>)
+	where
+		T2: BitStore + Sync,
+		O2: BitOrder,
+	{
+		self.par_zip(rhs, |this, that| *this ^= that);
+	}
+
+	/// Applies a Boolean-assignment operator to each chunk of `self` and the
+	/// matching span of `rhs`, in parallel.
+	///
+	/// Chunks that extend past the end of `rhs` receive a shortened span, so
+	/// that each operator zero-extends `rhs` exactly as it does when applied
+	/// to the whole bit-slice.
+	fn par_zip<T2, O2>(
+		&mut self,
+		rhs: &BitSlice<T2, O2>,
+		op: impl Fn(&mut Self, &BitSlice<T2, O2>) + Sync + Send,
+	) where
+		T2: BitStore + Sync,
+		O2: BitOrder,
+	{
+		let chunks = self.par_chunks_mut(GRAIN);
+		let (width, pad) = (chunks.inner.width, chunks.inner.pad);
+		chunks.enumerate().for_each(|(idx, chunk)| {
+			let start = cmp::min(chunk_start(idx, width, pad), rhs.len());
+			let end = cmp::min(start + chunk.len(), rhs.len());
+			op(chunk, &rhs[start .. end]);
+		});
+	}
+}
+
+/// A parallel iterator over subslices that do not share any memory element.
+///
+/// This is created by [`BitSlice::par_chunks`].
+#[derive(Clone, Debug)]
+pub struct ParChunks<'a, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// The chunking producer.
+	inner: AlignedChunks<'a, T, O>,
+}
+
+/// A parallel iterator over mutable subslices that do not share any memory
+/// element.
+///
+/// This is created by [`BitSlice::par_chunks_mut`].
+#[derive(Debug)]
+pub struct ParChunksMut<'a, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// The chunking producer.
+	inner: AlignedChunksMut<'a, T, O>,
+}
+
+/// Implements the parallel-iterator traits for a chunking iterator.
+macro_rules! par_iter {
+	($($t:ident => $item:ty),+ $(,)?) => { $(
+		impl<'a, T, O> ParallelIterator for $t<'a, T, O>
+		where
+			T: BitStore + Sync,
+			O: BitOrder,
+		{
+			type Item = $item;
+
+			#[inline]
+			fn drive_unindexed<C>(self, consumer: C) -> C::Result
+			where C: UnindexedConsumer<Self::Item> {
+				bridge(self, consumer)
+			}
+
+			#[inline]
+			fn opt_len(&self) -> Option<usize> {
+				Some(self.inner.len())
+			}
+		}
+
+		impl<'a, T, O> IndexedParallelIterator for $t<'a, T, O>
+		where
+			T: BitStore + Sync,
+			O: BitOrder,
+		{
+			#[inline]
+			fn drive<C>(self, consumer: C) -> C::Result
+			where C: Consumer<Self::Item> {
+				bridge(self, consumer)
+			}
+
+			#[inline]
+			fn len(&self) -> usize {
+				self.inner.len()
+			}
+
+			#[inline]
+			fn with_producer<CB>(self, callback: CB) -> CB::Output
+			where CB: ProducerCallback<Self::Item> {
+				callback.callback(self.inner)
+			}
+		}
+	)+ };
+}
+
+par_iter! {
+	ParChunks => &'a BitSlice<T, O>,
+	ParChunksMut => &'a mut BitSlice<T, O>,
+}
+
+/// Computes the index, within a chunked bit-slice, at which a chunk begins.
+fn chunk_start(idx: usize, width: usize, pad: usize) -> usize {
+	(idx * width).saturating_sub(pad)
+}
+
+/// Rounds a requested chunk size up to a whole number of memory elements.
+fn chunk_width<T>(chunk_size: usize) -> usize
+where T: BitStore {
+	assert_ne!(chunk_size, 0, "Chunk width cannot be 0");
+	let bits = bits_of::<T::Mem>();
+	(chunk_size + bits - 1) / bits * bits
+}
+
+/// Implements the chunk arithmetic and sequential iteration shared by the two
+/// chunking producers.
+macro_rules! aligned {
+	($($t:ident => $item:ty, $split:ident);+ $(;)?) => { $(
+		impl<'a, T, O> $t<'a, T, O>
+		where
+			T: BitStore,
+			O: BitOrder,
+		{
+			/// Prepares to chunk a bit-slice.
+			fn new(slice: $item, chunk_size: usize) -> Self {
+				let pad = slice.as_bitspan().head().into_inner() as usize;
+				Self {
+					slice,
+					width: chunk_width::<T>(chunk_size),
+					pad,
+				}
+			}
+
+			/// Counts the remaining chunks.
+			fn len(&self) -> usize {
+				match self.slice.len() {
+					0 => 0,
+					len => (len + self.pad + self.width - 1) / self.width,
+				}
+			}
+
+			/// Finds the index in the remaining bit-slice at which a chunk
+			/// begins.
+			fn boundary(&self, idx: usize) -> usize {
+				let start = chunk_start(idx, self.width, self.pad);
+				cmp::min(start, self.slice.len())
+			}
+
+			/// Splits the remaining bit-slice at a chunk boundary.
+			fn split(self, idx: usize) -> (Self, Self) {
+				let mid = self.boundary(idx);
+				//  Chunk boundaries fall between memory elements, so the two
+				//  halves do not alias.
+				let (left, right) = unsafe { self.slice.$split(mid) };
+				let left = Self {
+					slice: left,
+					..self
+				};
+				let right = Self {
+					slice: right,
+					pad: 0,
+					..self
+				};
+				(left, right)
+			}
+		}
+
+		impl<'a, T, O> Iterator for $t<'a, T, O>
+		where
+			T: BitStore,
+			O: BitOrder,
+		{
+			type Item = $item;
+
+			#[inline]
+			fn next(&mut self) -> Option<Self::Item> {
+				if self.slice.is_empty() {
+					return None;
+				}
+				let (out, rest) = mem::take(self).split(1);
+				*self = rest;
+				Some(out.slice)
+			}
+
+			#[inline]
+			fn size_hint(&self) -> (usize, Option<usize>) {
+				let len = self.len();
+				(len, Some(len))
+			}
+		}
+
+		impl<'a, T, O> DoubleEndedIterator for $t<'a, T, O>
+		where
+			T: BitStore,
+			O: BitOrder,
+		{
+			#[inline]
+			fn next_back(&mut self) -> Option<Self::Item> {
+				let last = self.len().checked_sub(1)?;
+				let (rest, out) = mem::take(self).split(last);
+				*self = rest;
+				Some(out.slice)
+			}
+		}
+
+		impl<T, O> ExactSizeIterator for $t<'_, T, O>
+		where
+			T: BitStore,
+			O: BitOrder,
+		{
+			#[inline]
+			fn len(&self) -> usize {
+				Self::len(self)
+			}
+		}
+
+		impl<T, O> Default for $t<'_, T, O>
+		where
+			T: BitStore,
+			O: BitOrder,
+		{
+			#[inline]
+			fn default() -> Self {
+				Self {
+					slice: Default::default(),
+					width: 1,
+					pad: 0,
+				}
+			}
+		}
+
+		impl<'a, T, O> Producer for $t<'a, T, O>
+		where
+			T: BitStore + Sync,
+			O: BitOrder,
+		{
+			type IntoIter = Self;
+			type Item = $item;
+
+			#[inline]
+			fn into_iter(self) -> Self::IntoIter {
+				self
+			}
+
+			#[inline]
+			fn split_at(self, index: usize) -> (Self, Self) {
+				self.split(index)
+			}
+		}
+	)+ };
+}
+
+/// Divides a bit-slice into chunks whose interior edges fall between memory
+/// elements.
+#[derive(Clone, Debug)]
+struct AlignedChunks<'a, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// The bit-slice that has not yet been yielded.
+	slice: &'a BitSlice<T, O>,
+	/// The width of each chunk, in bits. This is a multiple of the element
+	/// width.
+	width: usize,
+	/// The number of bits between the start of the chunk containing the front
+	/// of `slice` and the front of `slice`.
+	pad:   usize,
+}
+
+/// Divides a mutable bit-slice into chunks whose interior edges fall between
+/// memory elements.
+#[derive(Debug)]
+struct AlignedChunksMut<'a, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// The bit-slice that has not yet been yielded.
+	slice: &'a mut BitSlice<T, O>,
+	/// The width of each chunk, in bits. This is a multiple of the element
+	/// width.
+	width: usize,
+	/// The number of bits between the start of the chunk containing the front
+	/// of `slice` and the front of `slice`.
+	pad:   usize,
+}
+
+aligned! {
+	AlignedChunks => &'a BitSlice<T, O>, split_at_unchecked;
+	AlignedChunksMut => &'a mut BitSlice<T, O>, split_at_unchecked_mut_noalias;
+}
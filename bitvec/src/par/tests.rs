@@ -0,0 +1,82 @@
+//! Unit tests for parallel processing.
+
+#![cfg(test)]
+
+use rand::random;
+use rayon::prelude::*;
+
+use crate::prelude::*;
+
+#[test]
+fn chunking() {
+	let data = random::<[u16; 32]>();
+	let bits = data.view_bits::<Lsb0>();
+	for (start, end) in [(0, 512), (3, 512), (16, 400), (17, 17), (5, 9)] {
+		let bits = &bits[start .. end];
+		for size in [1, 16, 20, 48, 1000] {
+			let chunks = bits.par_chunks(size).collect::<Vec<_>>();
+			assert_eq!(chunks.len(), bits.par_chunks(size).len());
+			assert_eq!(
+				chunks.iter().map(|c| c.len()).sum::<usize>(),
+				bits.len()
+			);
+			//  Every interior edge falls between elements.
+			for chunk in chunks.iter().skip(1) {
+				assert_eq!(chunk.as_bitptr().bit().into_inner(), 0);
+			}
+			let rejoined = chunks.iter().flat_map(|c| c.iter().by_vals());
+			assert!(rejoined.eq(bits.iter().by_vals()));
+
+			let rev = bits.par_chunks(size).rev().collect::<Vec<_>>();
+			assert!(
+				rev.iter()
+					.rev()
+					.map(|c| c.len())
+					.eq(chunks.iter().map(|c| c.len()))
+			);
+		}
+	}
+
+	let mut data = [0u32; 8];
+	data.view_bits_mut::<Msb0>()[7 ..]
+		.par_chunks_mut(64)
+		.for_each(|chunk| chunk.set(0, true));
+	assert_eq!(data, [1 << 24, 0, 1 << 31, 0, 1 << 31, 0, 1 << 31, 0]);
+}
+
+#[test]
+fn bulk() {
+	let data = random::<[u64; 32]>().repeat(100);
+	let bits = &data.view_bits::<Msb0>()[13 ..];
+	assert_eq!(bits.par_count_ones(), bits.count_ones());
+	assert_eq!(bits.par_count_zeros(), bits.count_zeros());
+	assert!(
+		bits.par_iter_ones()
+			.collect::<Vec<_>>()
+			.into_iter()
+			.eq(bits.iter_ones())
+	);
+
+	let mut bv = bits.to_bitvec();
+	bv[1000 .. 150_000].par_fill(true);
+	assert!(bv[1000 .. 150_000].all());
+	assert_eq!(bv[.. 1000], bits[.. 1000]);
+	assert_eq!(bv[150_000 ..], bits[150_000 ..]);
+
+	let other = random::<[u8; 32]>().repeat(1000);
+	let other = &other.view_bits::<Lsb0>()[3 .. 200_000];
+	for &short in &[false, true] {
+		let rhs = if short { &other[.. 70_000] } else { other };
+		let mut a = bits.to_bitvec();
+		let mut b = a.clone();
+		a.par_bitand_assign(rhs);
+		b &= rhs;
+		assert_eq!(a, b);
+		a.par_bitor_assign(rhs);
+		b |= rhs;
+		assert_eq!(a, b);
+		a.par_bitxor_assign(rhs);
+		b ^= rhs;
+		assert_eq!(a, b);
+	}
+}
@@ -0,0 +1,297 @@
+#![cfg(feature = "alloc")]
+#![doc = include_str!("../doc/rank.md")]
+
+use alloc::{
+	boxed::Box,
+	vec::Vec,
+};
+use core::ops::Deref;
+
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+mod tests;
+
+/// The number of bits covered by each entry in the block directory.
+const BLOCK: usize = 512;
+
+/// The number of blocks covered by each entry in the superblock directory.
+///
+/// The block directory counts relative to its superblock in a `u16`, so the
+/// last block of a superblock must begin before bit `2^16`.
+const BLOCKS_PER_SUPER: usize = 128;
+
+/// The number of ones, or zeros, between consecutive select samples.
+const SAMPLE: usize = 4096;
+
+#[doc = include_str!("../doc/rank/RankSelect.md")]
+#[derive(Clone, Debug)]
+pub struct RankSelect<B> {
+	/// The indexed bit-slice.
+	bits:   B,
+	/// The number of ones before each superblock.
+	supers: Box<[usize]>,
+	/// The number of ones before each block, counted from the start of its
+	/// superblock.
+	blocks: Box<[u16]>,
+	/// The block holding every `SAMPLE`th one.
+	ones:   Box<[usize]>,
+	/// The block holding every `SAMPLE`th zero.
+	zeros:  Box<[usize]>,
+	/// The number of ones in the whole bit-slice.
+	total:  usize,
+}
+
+impl<B, T, O> RankSelect<B>
+where
+	B: Deref<Target = BitSlice<T, O>>,
+	T: BitStore,
+	O: BitOrder,
+{
+	/// Builds the rank and select directories for a bit-slice.
+	///
+	/// This takes time linear in the length of the bit-slice.
+	///
+	/// ## Parameters
+	///
+	/// - `bits`: Any handle to a bit-slice, such as `&BitSlice` or `BitBox`.
+	///
+	/// ## Returns
+	///
+	/// A wrapper over `bits` which answers rank and select queries.
+	#[inline]
+	pub fn new(bits: B) -> Self {
+		let count = (bits.len() + BLOCK - 1) / BLOCK;
+		let mut supers = Vec::with_capacity(count / BLOCKS_PER_SUPER + 1);
+		let mut blocks = Vec::with_capacity(count);
+		let mut ones = Vec::new();
+		let mut zeros = Vec::new();
+		let mut total = 0;
+
+		for (idx, chunk) in bits.chunks(BLOCK).enumerate() {
+			if idx % BLOCKS_PER_SUPER == 0 {
+				supers.push(total);
+			}
+			blocks.push((total - supers[supers.len() - 1]) as u16);
+
+			total += chunk.count_ones();
+			//  Record this block for every sample point that it contains.
+			while ones.len() * SAMPLE < total {
+				ones.push(idx);
+			}
+			while zeros.len() * SAMPLE < idx * BLOCK + chunk.len() - total {
+				zeros.push(idx);
+			}
+		}
+
+		Self {
+			bits,
+			supers: supers.into_boxed_slice(),
+			blocks: blocks.into_boxed_slice(),
+			ones: ones.into_boxed_slice(),
+			zeros: zeros.into_boxed_slice(),
+			total,
+		}
+	}
+
+	/// Views the indexed bit-slice.
+	#[inline]
+	pub fn as_bitslice(&self) -> &BitSlice<T, O> {
+		&self.bits
+	}
+
+	/// Discards the directories, returning the bit-slice handle.
+	#[inline]
+	pub fn into_inner(self) -> B {
+		self.bits
+	}
+
+	/// Gets the number of bits in the indexed bit-slice.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.bits.len()
+	}
+
+	/// Tests if the indexed bit-slice is empty.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.bits.is_empty()
+	}
+
+	/// Counts the ones in the indexed bit-slice.
+	///
+	/// Unlike [`BitSlice::count_ones`], this takes constant time.
+	///
+	/// [`BitSlice::count_ones`]: crate::slice::BitSlice::count_ones
+	#[inline]
+	pub fn count_ones(&self) -> usize {
+		self.total
+	}
+
+	/// Counts the zeros in the indexed bit-slice.
+	///
+	/// Unlike [`BitSlice::count_zeros`], this takes constant time.
+	///
+	/// [`BitSlice::count_zeros`]: crate::slice::BitSlice::count_zeros
+	#[inline]
+	pub fn count_zeros(&self) -> usize {
+		self.len() - self.total
+	}
+
+	/// Counts the ones before an index.
+	///
+	/// ## Parameters
+	///
+	/// - `index`: An index in `0 ..= self.len()`.
+	///
+	/// ## Returns
+	///
+	/// The number of ones in `self[.. index]`.
+	///
+	/// ## Panics
+	///
+	/// This panics if `index` is greater than the length of the bit-slice.
+	#[inline]
+	pub fn rank1(&self, index: usize) -> usize {
+		let len = self.len();
+		assert!(
+			index <= len,
+			"rank index {} out of range for length {}",
+			index,
+			len,
+		);
+		if index == len {
+			return self.total;
+		}
+		let block = index / BLOCK;
+		let start = block * BLOCK;
+		self.ones_before(block)
+			+ unsafe { self.bits.get_unchecked(start .. index) }.count_ones()
+	}
+
+	/// Counts the zeros before an index.
+	///
+	/// ## Parameters
+	///
+	/// - `index`: An index in `0 ..= self.len()`.
+	///
+	/// ## Returns
+	///
+	/// The number of zeros in `self[.. index]`.
+	///
+	/// ## Panics
+	///
+	/// This panics if `index` is greater than the length of the bit-slice.
+	#[inline]
+	pub fn rank0(&self, index: usize) -> usize {
+		index - self.rank1(index)
+	}
+
+	/// Finds the index of a one by its rank.
+	///
+	/// ## Parameters
+	///
+	/// - `rank`: The number of ones that precede the desired one.
+	///
+	/// ## Returns
+	///
+	/// The index of the one with `rank` ones before it, if the bit-slice has
+	/// more than `rank` ones. `self.rank1(self.select1(k).unwrap()) == k`.
+	#[inline]
+	pub fn select1(&self, rank: usize) -> Option<usize> {
+		if rank >= self.total {
+			return None;
+		}
+		let block = self.find_block(rank, &self.ones, |b| self.ones_before(b));
+		let start = block * BLOCK;
+		self.block(block)
+			.iter_ones()
+			.nth(rank - self.ones_before(block))
+			.map(|idx| start + idx)
+	}
+
+	/// Finds the index of a zero by its rank.
+	///
+	/// ## Parameters
+	///
+	/// - `rank`: The number of zeros that precede the desired zero.
+	///
+	/// ## Returns
+	///
+	/// The index of the zero with `rank` zeros before it, if the bit-slice has
+	/// more than `rank` zeros. `self.rank0(self.select0(k).unwrap()) == k`.
+	#[inline]
+	pub fn select0(&self, rank: usize) -> Option<usize> {
+		if rank >= self.count_zeros() {
+			return None;
+		}
+		let zeros_before = |b: usize| b * BLOCK - self.ones_before(b);
+		let block = self.find_block(rank, &self.zeros, zeros_before);
+		let start = block * BLOCK;
+		self.block(block)
+			.iter_zeros()
+			.nth(rank - zeros_before(block))
+			.map(|idx| start + idx)
+	}
+
+	/// Counts the ones before the start of a block.
+	#[inline]
+	fn ones_before(&self, block: usize) -> usize {
+		self.supers[block / BLOCKS_PER_SUPER] + self.blocks[block] as usize
+	}
+
+	/// Views the bits covered by a block.
+	#[inline]
+	fn block(&self, block: usize) -> &BitSlice<T, O> {
+		let start = block * BLOCK;
+		let end = (start + BLOCK).min(self.len());
+		unsafe { self.bits.get_unchecked(start .. end) }
+	}
+
+	/// Finds the last block which has no more than `rank` matching bits before
+	/// it.
+	///
+	/// The select samples bound the search to the blocks between two sample
+	/// points, which the binary search then narrows to one.
+	#[inline]
+	fn find_block(
+		&self,
+		rank: usize,
+		samples: &[usize],
+		before: impl Fn(usize) -> usize,
+	) -> usize {
+		let sample = rank / SAMPLE;
+		let mut lo = samples[sample];
+		let mut hi = samples
+			.get(sample + 1)
+			.copied()
+			.unwrap_or(self.blocks.len() - 1);
+		while lo < hi {
+			let mid = lo + (hi - lo + 1) / 2;
+			if before(mid) <= rank {
+				lo = mid;
+			}
+			else {
+				hi = mid - 1;
+			}
+		}
+		lo
+	}
+}
+
+impl<B, T, O> Deref for RankSelect<B>
+where
+	B: Deref<Target = BitSlice<T, O>>,
+	T: BitStore,
+	O: BitOrder,
+{
+	type Target = BitSlice<T, O>;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		self.as_bitslice()
+	}
+}
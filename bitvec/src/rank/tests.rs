@@ -0,0 +1,92 @@
+#![cfg(test)]
+
+use rand::prelude::*;
+
+use super::*;
+use crate::prelude::*;
+
+/// Checks queries against a naïve scan of the bit-slice.
+///
+/// Long bit-slices are checked at a sample of indices and ranks, to keep the
+/// test fast in debug builds.
+fn check<T, O>(bits: &BitSlice<T, O>)
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	let rs = RankSelect::new(bits);
+	let ones = bits.iter_ones().collect::<Vec<_>>();
+	let zeros = bits.iter_zeros().collect::<Vec<_>>();
+	assert_eq!(rs.len(), bits.len());
+	assert_eq!(rs.count_ones(), ones.len());
+	assert_eq!(rs.count_zeros(), zeros.len());
+
+	let step = bits.len() / 5000 + 1;
+	for idx in (0 ..= bits.len()).step_by(step).chain([bits.len()]) {
+		let rank = ones.partition_point(|&one| one < idx);
+		assert_eq!(rs.rank1(idx), rank, "rank1({})", idx);
+		assert_eq!(rs.rank0(idx), idx - rank, "rank0({})", idx);
+	}
+
+	for (rank, &idx) in ones.iter().enumerate().step_by(step) {
+		assert_eq!(rs.select1(rank), Some(idx), "select1({})", rank);
+	}
+	for (rank, &idx) in zeros.iter().enumerate().step_by(step) {
+		assert_eq!(rs.select0(rank), Some(idx), "select0({})", rank);
+	}
+	assert_eq!(rs.select1(ones.len()), None);
+	assert_eq!(rs.select0(zeros.len()), None);
+	if let (Some(&last), Some(rank)) = (ones.last(), ones.len().checked_sub(1)) {
+		assert_eq!(rs.select1(rank), Some(last));
+	}
+	if let (Some(&last), Some(rank)) = (zeros.last(), zeros.len().checked_sub(1))
+	{
+		assert_eq!(rs.select0(rank), Some(last));
+	}
+}
+
+/// Produces a bit-vector in which each bit is set with the given probability.
+fn random(len: usize, density: f64) -> BitVec<usize, Lsb0> {
+	let mut rng = thread_rng();
+	(0 .. len).map(|_| rng.gen_bool(density)).collect()
+}
+
+#[test]
+fn against_naive() {
+	for &len in &[0, 1, 511, 512, 513, 4097, 70_000, 200_000] {
+		for &density in &[0.0, 0.001, 0.5, 0.999, 1.0] {
+			check(random(len, density).as_bitslice());
+		}
+	}
+}
+
+#[test]
+fn other_types() {
+	let bv = random(150_000, 0.3);
+	let mut bytes = BitVec::<u8, Msb0>::new();
+	bytes.extend(bv.iter().by_vals());
+	check(&bytes[3 ..]);
+	check(&bv.as_bitslice()[17 .. 140_017]);
+
+	let bb = bytes.into_boxed_bitslice();
+	let rs = RankSelect::new(bb.clone());
+	assert_eq!(rs.rank1(bb.len()), bb.count_ones());
+	assert_eq!(rs.select0(0), bb.first_zero());
+	assert_eq!(rs.into_inner(), bb);
+}
+
+#[test]
+fn overhead() {
+	let bv = random(1 << 20, 0.5);
+	let rs = RankSelect::new(bv.as_bitslice());
+	let bytes = rs.supers.len() * core::mem::size_of::<usize>()
+		+ rs.blocks.len() * core::mem::size_of::<u16>()
+		+ (rs.ones.len() + rs.zeros.len()) * core::mem::size_of::<usize>();
+	assert!(bytes * 8 * 20 < bv.len(), "{} bytes of overhead", bytes);
+}
+
+#[test]
+#[should_panic = "rank index 11 out of range for length 10"]
+fn rank_out_of_range() {
+	RankSelect::new(bits![0; 10]).rank1(11);
+}
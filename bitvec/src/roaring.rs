@@ -0,0 +1,255 @@
+#![cfg(feature = "alloc")]
+#![doc = include_str!("../doc/roaring.md")]
+
+use alloc::{
+	boxed::Box,
+	vec,
+	vec::Vec,
+};
+
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+mod chunk;
+mod iter;
+mod ops;
+mod tests;
+mod traits;
+
+use self::chunk::{
+	ARRAY_MAX,
+	CHUNK_BITS,
+	Chunk,
+	Dense,
+};
+pub use self::iter::IterOnes;
+
+#[doc = include_str!("../doc/roaring/RoaringBitmap.md")]
+#[derive(Clone, Default)]
+pub struct RoaringBitmap {
+	/// The non-empty chunks, sorted by the high half of the positions that
+	/// they hold.
+	chunks: Vec<(u16, Chunk)>,
+}
+
+impl RoaringBitmap {
+	/// Creates an empty bitmap.
+	#[inline]
+	pub fn new() -> Self {
+		Self { chunks: Vec::new() }
+	}
+
+	/// Counts the positions in the bitmap.
+	///
+	/// This is a `u64`, as a bitmap can hold all `2^32` positions.
+	#[inline]
+	pub fn len(&self) -> u64 {
+		self.chunks
+			.iter()
+			.map(|(_, chunk)| chunk.len() as u64)
+			.sum()
+	}
+
+	/// Tests if the bitmap holds no positions.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.chunks.is_empty()
+	}
+
+	/// Tests if a position is in the bitmap.
+	#[inline]
+	pub fn contains(&self, pos: u32) -> bool {
+		let (key, low) = split(pos);
+		self.find(key)
+			.map(|idx| self.chunks[idx].1.contains(low))
+			.unwrap_or(false)
+	}
+
+	/// Adds a position to the bitmap.
+	///
+	/// ## Returns
+	///
+	/// `true` if the position was not already in the bitmap.
+	#[inline]
+	pub fn insert(&mut self, pos: u32) -> bool {
+		let (key, low) = split(pos);
+		match self.find(key) {
+			Ok(idx) => self.chunks[idx].1.insert(low),
+			Err(idx) => {
+				self.chunks.insert(idx, (key, Chunk::Array(vec![low])));
+				true
+			},
+		}
+	}
+
+	/// Removes a position from the bitmap.
+	///
+	/// ## Returns
+	///
+	/// `true` if the position was in the bitmap.
+	#[inline]
+	pub fn remove(&mut self, pos: u32) -> bool {
+		let (key, low) = split(pos);
+		let idx = match self.find(key) {
+			Ok(idx) => idx,
+			Err(_) => return false,
+		};
+		let chunk = &mut self.chunks[idx].1;
+		let removed = chunk.remove(low);
+		if chunk.is_empty() {
+			self.chunks.remove(idx);
+		}
+		removed
+	}
+
+	/// Removes every position from the bitmap.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.chunks.clear();
+	}
+
+	/// Finds the lowest position in the bitmap.
+	#[inline]
+	pub fn min(&self) -> Option<u32> {
+		self.chunks
+			.first()
+			.map(|(key, chunk)| join(*key, chunk.min()))
+	}
+
+	/// Finds the highest position in the bitmap.
+	#[inline]
+	pub fn max(&self) -> Option<u32> {
+		self.chunks
+			.last()
+			.map(|(key, chunk)| join(*key, chunk.max()))
+	}
+
+	/// Iterates over the positions in the bitmap, in ascending order.
+	///
+	/// This is named for [`BitSlice::iter_ones`], which produces the same
+	/// sequence from the equivalent bit-slice.
+	///
+	/// [`BitSlice::iter_ones`]: crate::slice::BitSlice::iter_ones
+	#[inline]
+	pub fn iter_ones(&self) -> IterOnes<'_> {
+		IterOnes::new(&self.chunks)
+	}
+
+	/// Converts every chunk to its smallest representation.
+	///
+	/// Chunks that are edited by [`.insert()`] and [`.remove()`] only change
+	/// representation when they outgrow it, so a bitmap built up one position
+	/// at a time may hold long runs in arrays or dense chunks. This
+	/// recompresses them.
+	///
+	/// [`.insert()`]: Self::insert
+	/// [`.remove()`]: Self::remove
+	#[inline]
+	pub fn optimize(&mut self) {
+		for (_, chunk) in &mut self.chunks {
+			chunk.optimize();
+		}
+	}
+
+	/// Builds a bitmap holding the index of every `1` bit in a bit-slice.
+	///
+	/// ## Panics
+	///
+	/// This panics if the bit-slice is longer than `2^32` bits.
+	#[inline]
+	pub fn from_bitslice<T, O>(bits: &BitSlice<T, O>) -> Self
+	where
+		T: BitStore,
+		O: BitOrder,
+	{
+		assert!(
+			bits.len() as u64 <= 1 << 32,
+			"a bit-slice of {} bits cannot be indexed by `u32`",
+			bits.len(),
+		);
+		let chunks = bits
+			.chunks(CHUNK_BITS)
+			.enumerate()
+			.filter_map(|(key, bits)| {
+				let chunk = if bits.count_ones() <= ARRAY_MAX {
+					let array = bits.iter_ones().map(|idx| idx as u16).collect();
+					Chunk::from_sorted(array)
+				}
+				else {
+					let mut dense = Box::new(Dense::ZERO);
+					dense[.. bits.len()].clone_from_bitslice(bits);
+					Chunk::from_dense(dense)
+				};
+				chunk.map(|chunk| (key as u16, chunk))
+			})
+			.collect();
+		Self { chunks }
+	}
+
+	/// Builds a bit-vector with a `1` bit at each position in the bitmap.
+	///
+	/// The bit-vector ends with the highest position in the bitmap, and is
+	/// empty if the bitmap is empty.
+	///
+	/// ## Panics
+	///
+	/// This panics if the bit-vector would be too long to exist. On 32-bit
+	/// targets, this happens when the bitmap holds positions near `u32::MAX`.
+	#[inline]
+	pub fn to_bitvec<T, O>(&self) -> BitVec<T, O>
+	where
+		T: BitStore,
+		O: BitOrder,
+	{
+		let len = self.max().map_or(0, |max| {
+			usize::try_from(max)
+				.ok()
+				.and_then(|max| max.checked_add(1))
+				.expect("bitmap is too large to be a bit-vector")
+		});
+		let mut bv = BitVec::repeat(false, len);
+		for (key, chunk) in &self.chunks {
+			let base = join(*key, 0) as usize;
+			let bits = &mut bv[base ..];
+			match chunk {
+				Chunk::Array(array) => {
+					for &low in array {
+						bits.set(low as usize, true);
+					}
+				},
+				Chunk::Run(runs) => {
+					for &(start, last) in runs {
+						bits[start as usize ..= last as usize].fill(true);
+					}
+				},
+				Chunk::Dense { bits: dense, .. } => {
+					let len = bits.len().min(CHUNK_BITS);
+					bits[.. len].clone_from_bitslice(&dense[.. len]);
+				},
+			}
+		}
+		bv
+	}
+
+	/// Finds the chunk for a key, or where it would be inserted.
+	#[inline]
+	fn find(&self, key: u16) -> Result<usize, usize> {
+		self.chunks.binary_search_by_key(&key, |(key, _)| *key)
+	}
+}
+
+/// Splits a position into its chunk key and its index within the chunk.
+#[inline]
+fn split(pos: u32) -> (u16, u16) {
+	((pos >> 16) as u16, pos as u16)
+}
+
+/// Joins a chunk key and an index within the chunk into a position.
+#[inline]
+fn join(key: u16, low: u16) -> u32 {
+	((key as u32) << 16) | low as u32
+}
@@ -0,0 +1,426 @@
+//! The containers that hold each `2^16`-position chunk of a bitmap.
+
+use alloc::{
+	boxed::Box,
+	vec::Vec,
+};
+
+use tap::Pipe;
+
+use crate::{
+	array::BitArray,
+	order::Lsb0,
+};
+
+/// The number of positions in a chunk.
+pub(super) const CHUNK_BITS: usize = 1 << 16;
+
+/// The most positions that an array chunk may hold before it becomes larger
+/// than a dense chunk.
+pub(super) const ARRAY_MAX: usize = 4096;
+
+/// The most runs that a run chunk may hold before it becomes larger than a
+/// dense chunk.
+const RUN_MAX: usize = 2048;
+
+/// The storage of a dense chunk.
+pub(super) type Dense = BitArray<[u64; 1024], Lsb0>;
+
+/// One `2^16`-position chunk of a bitmap.
+///
+/// Chunks are never empty: a bitmap removes any chunk that becomes empty.
+#[derive(Clone, Debug)]
+pub(super) enum Chunk {
+	/// A sorted list of the positions in the chunk.
+	Array(Vec<u16>),
+	/// A sorted list of the disjoint, non-adjacent, inclusive ranges of
+	/// positions in the chunk.
+	Run(Vec<(u16, u16)>),
+	/// A bit-array with a bit set for each position in the chunk.
+	Dense {
+		/// The bits of the chunk.
+		bits: Box<Dense>,
+		/// The number of set bits.
+		len:  u32,
+	},
+}
+
+/// A set operation between two chunks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Op {
+	/// Intersection.
+	And,
+	/// Union.
+	Or,
+	/// Symmetric difference.
+	Xor,
+	/// Difference.
+	Sub,
+}
+
+impl Op {
+	/// Applies the operation to a pair of words.
+	#[inline]
+	pub(super) fn apply(self, lhs: u64, rhs: u64) -> u64 {
+		match self {
+			Self::And => lhs & rhs,
+			Self::Or => lhs | rhs,
+			Self::Xor => lhs ^ rhs,
+			Self::Sub => lhs & !rhs,
+		}
+	}
+
+	/// Tests if the result includes a position that is only in the left
+	/// operand.
+	#[inline]
+	pub(super) fn keeps_left(self) -> bool {
+		self.apply(1, 0) != 0
+	}
+
+	/// Tests if the result includes a position that is only in the right
+	/// operand.
+	#[inline]
+	pub(super) fn keeps_right(self) -> bool {
+		self.apply(0, 1) != 0
+	}
+
+	/// Tests if the result includes a position that is in both operands.
+	#[inline]
+	fn keeps_both(self) -> bool {
+		self.apply(1, 1) != 0
+	}
+}
+
+impl Chunk {
+	/// Builds a chunk from a sorted list of positions.
+	#[inline]
+	pub(super) fn from_sorted(array: Vec<u16>) -> Option<Self> {
+		match array.len() {
+			0 => None,
+			n if n <= ARRAY_MAX => Some(Self::Array(array)),
+			_ => Self::from_dense(Self::Array(array).to_dense()),
+		}
+	}
+
+	/// Builds a chunk from a bit-array, in whichever representation is
+	/// smallest.
+	#[inline]
+	pub(super) fn from_dense(bits: Box<Dense>) -> Option<Self> {
+		let len = bits.count_ones();
+		if len == 0 {
+			return None;
+		}
+		let mut carry = 0;
+		let runs = bits
+			.as_raw_slice()
+			.iter()
+			.map(|&word| {
+				let starts = word & !((word << 1) | carry);
+				carry = word >> 63;
+				starts.count_ones() as usize
+			})
+			.sum::<usize>();
+
+		//  Compare the sizes, in bytes, of each representation.
+		let array_size = if len <= ARRAY_MAX {
+			len * 2
+		}
+		else {
+			usize::MAX
+		};
+		let dense_size = CHUNK_BITS / 8;
+		Some(if runs * 4 < array_size.min(dense_size) {
+			let mut out = Vec::with_capacity(runs);
+			let mut rest = &bits[..];
+			let mut base = 0;
+			while let Some(start) = rest.first_one() {
+				let len =
+					rest[start ..].first_zero().unwrap_or(rest.len() - start);
+				out.push((
+					(base + start) as u16,
+					(base + start + len - 1) as u16,
+				));
+				base += start + len;
+				rest = &rest[start + len ..];
+			}
+			Self::Run(out)
+		}
+		else if array_size < dense_size {
+			Self::Array(bits.iter_ones().map(|idx| idx as u16).collect())
+		}
+		else {
+			Self::Dense {
+				bits,
+				len: len as u32,
+			}
+		})
+	}
+
+	/// Copies the chunk into a bit-array.
+	#[inline]
+	pub(super) fn to_dense(&self) -> Box<Dense> {
+		match self {
+			Self::Array(array) => {
+				let mut out = Box::new(Dense::ZERO);
+				for &low in array {
+					out.set(low as usize, true);
+				}
+				out
+			},
+			Self::Run(runs) => {
+				let mut out = Box::new(Dense::ZERO);
+				for &(start, last) in runs {
+					out[start as usize ..= last as usize].fill(true);
+				}
+				out
+			},
+			Self::Dense { bits, .. } => bits.clone(),
+		}
+	}
+
+	/// Converts the chunk to its smallest representation.
+	#[inline]
+	pub(super) fn optimize(&mut self) {
+		if let Some(chunk) = Self::from_dense(self.to_dense()) {
+			*self = chunk;
+		}
+	}
+
+	/// Counts the positions in the chunk.
+	#[inline]
+	pub(super) fn len(&self) -> u32 {
+		match self {
+			Self::Array(array) => array.len() as u32,
+			Self::Run(runs) => runs
+				.iter()
+				.map(|&(start, last)| (last - start) as u32 + 1)
+				.sum(),
+			Self::Dense { len, .. } => *len,
+		}
+	}
+
+	/// Tests if the chunk holds no positions.
+	#[inline]
+	pub(super) fn is_empty(&self) -> bool {
+		match self {
+			Self::Array(array) => array.is_empty(),
+			Self::Run(runs) => runs.is_empty(),
+			Self::Dense { len, .. } => *len == 0,
+		}
+	}
+
+	/// Finds the lowest position in the chunk.
+	#[inline]
+	pub(super) fn min(&self) -> u16 {
+		match self {
+			Self::Array(array) => array[0],
+			Self::Run(runs) => runs[0].0,
+			Self::Dense { bits, .. } => bits.first_one().unwrap_or(0) as u16,
+		}
+	}
+
+	/// Finds the highest position in the chunk.
+	#[inline]
+	pub(super) fn max(&self) -> u16 {
+		match self {
+			Self::Array(array) => array[array.len() - 1],
+			Self::Run(runs) => runs[runs.len() - 1].1,
+			Self::Dense { bits, .. } => bits.last_one().unwrap_or(0) as u16,
+		}
+	}
+
+	/// Tests if a position is in the chunk.
+	#[inline]
+	pub(super) fn contains(&self, low: u16) -> bool {
+		match self {
+			Self::Array(array) => array.binary_search(&low).is_ok(),
+			Self::Run(runs) => {
+				let idx = runs.partition_point(|&(start, _)| start <= low);
+				idx > 0 && runs[idx - 1].1 >= low
+			},
+			Self::Dense { bits, .. } => bits[low as usize],
+		}
+	}
+
+	/// Adds a position to the chunk, returning whether it was absent.
+	#[inline]
+	pub(super) fn insert(&mut self, low: u16) -> bool {
+		match self {
+			Self::Array(array) => {
+				let idx = match array.binary_search(&low) {
+					Ok(_) => return false,
+					Err(idx) => idx,
+				};
+				array.insert(idx, low);
+				if array.len() > ARRAY_MAX {
+					*self = Self::Dense {
+						bits: self.to_dense(),
+						len:  ARRAY_MAX as u32 + 1,
+					};
+				}
+			},
+			Self::Run(runs) => {
+				let idx = runs.partition_point(|&(start, _)| start <= low);
+				if idx > 0 && runs[idx - 1].1 >= low {
+					return false;
+				}
+				let joins_prev = idx > 0 && runs[idx - 1].1 + 1 == low;
+				let joins_next = idx < runs.len() && runs[idx].0 - 1 == low;
+				match (joins_prev, joins_next) {
+					(true, true) => {
+						runs[idx - 1].1 = runs[idx].1;
+						runs.remove(idx);
+					},
+					(true, false) => runs[idx - 1].1 = low,
+					(false, true) => runs[idx].0 = low,
+					(false, false) => runs.insert(idx, (low, low)),
+				}
+				if runs.len() > RUN_MAX {
+					self.optimize();
+				}
+			},
+			Self::Dense { bits, len } => {
+				if bits.replace(low as usize, true) {
+					return false;
+				}
+				*len += 1;
+			},
+		}
+		true
+	}
+
+	/// Removes a position from the chunk, returning whether it was present.
+	#[inline]
+	pub(super) fn remove(&mut self, low: u16) -> bool {
+		match self {
+			Self::Array(array) => match array.binary_search(&low) {
+				Ok(idx) => {
+					array.remove(idx);
+				},
+				Err(_) => return false,
+			},
+			Self::Run(runs) => {
+				let idx = runs.partition_point(|&(start, _)| start <= low);
+				if idx == 0 || runs[idx - 1].1 < low {
+					return false;
+				}
+				let (start, last) = runs[idx - 1];
+				if start == last {
+					runs.remove(idx - 1);
+				}
+				else if low == start {
+					runs[idx - 1].0 = low + 1;
+				}
+				else if low == last {
+					runs[idx - 1].1 = low - 1;
+				}
+				else {
+					runs[idx - 1].1 = low - 1;
+					runs.insert(idx, (low + 1, last));
+					if runs.len() > RUN_MAX {
+						self.optimize();
+					}
+				}
+			},
+			Self::Dense { bits, len } => {
+				if !bits.replace(low as usize, false) {
+					return false;
+				}
+				*len -= 1;
+				if *len as usize <= ARRAY_MAX {
+					*self = Self::Array(
+						bits.iter_ones().map(|idx| idx as u16).collect(),
+					);
+				}
+			},
+		}
+		true
+	}
+
+	/// Combines two chunks with a set operation.
+	///
+	/// Returns `None` if the result is empty.
+	#[inline]
+	pub(super) fn combine(&self, other: &Self, op: Op) -> Option<Self> {
+		match (self, other, op) {
+			(Self::Array(lhs), Self::Array(rhs), _) => {
+				Self::from_sorted(merge(lhs, rhs, op))
+			},
+			(Self::Array(array), other, Op::And)
+			| (other, Self::Array(array), Op::And) => array
+				.iter()
+				.copied()
+				.filter(|&low| other.contains(low))
+				.collect::<Vec<_>>()
+				.pipe(Self::from_sorted),
+			(Self::Array(array), other, Op::Sub) => array
+				.iter()
+				.copied()
+				.filter(|&low| !other.contains(low))
+				.collect::<Vec<_>>()
+				.pipe(Self::from_sorted),
+			_ => {
+				let mut out = self.to_dense();
+				let rhs = other.to_dense();
+				for (lhs, &rhs) in
+					out.as_raw_mut_slice().iter_mut().zip(rhs.as_raw_slice())
+				{
+					*lhs = op.apply(*lhs, rhs);
+				}
+				Self::from_dense(out)
+			},
+		}
+	}
+}
+
+/// Combines two sorted lists of positions with a set operation.
+fn merge(lhs: &[u16], rhs: &[u16], op: Op) -> Vec<u16> {
+	let mut out = Vec::with_capacity(lhs.len() + rhs.len());
+	let (mut left, mut right) = (lhs.iter().peekable(), rhs.iter().peekable());
+	while let (Some(&&l), Some(&&r)) = (left.peek(), right.peek()) {
+		if l < r {
+			left.next();
+			if op.keeps_left() {
+				out.push(l);
+			}
+		}
+		else if r < l {
+			right.next();
+			if op.keeps_right() {
+				out.push(r);
+			}
+		}
+		else {
+			left.next();
+			right.next();
+			if op.keeps_both() {
+				out.push(l);
+			}
+		}
+	}
+	if op.keeps_left() {
+		out.extend(left);
+	}
+	if op.keeps_right() {
+		out.extend(right);
+	}
+	out
+}
+
+impl PartialEq for Chunk {
+	#[inline]
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Self::Array(lhs), Self::Array(rhs)) => lhs == rhs,
+			(Self::Run(lhs), Self::Run(rhs)) => lhs == rhs,
+			(Self::Dense { bits: lhs, .. }, Self::Dense { bits: rhs, .. }) => {
+				lhs == rhs
+			},
+			_ => {
+				self.len() == other.len() && self.to_dense() == other.to_dense()
+			},
+		}
+	}
+}
+
+impl Eq for Chunk {}
@@ -0,0 +1,112 @@
+//! Iteration over the positions in a bitmap.
+
+use core::{
+	iter::FusedIterator,
+	slice,
+};
+
+use super::{
+	chunk::Chunk,
+	join,
+};
+use crate::{
+	order::Lsb0,
+	slice::IterOnes as BitIterOnes,
+};
+
+#[doc = include_str!("../../doc/roaring/IterOnes.md")]
+#[derive(Clone, Debug)]
+pub struct IterOnes<'a> {
+	/// The chunks that have not yet been entered.
+	chunks: slice::Iter<'a, (u16, Chunk)>,
+	/// The key of the chunk being iterated.
+	key:    u16,
+	/// The iterator over the chunk being iterated.
+	inner:  Option<ChunkIter<'a>>,
+}
+
+impl<'a> IterOnes<'a> {
+	/// Iterates over a bitmap’s chunks.
+	#[inline]
+	pub(super) fn new(chunks: &'a [(u16, Chunk)]) -> Self {
+		Self {
+			chunks: chunks.iter(),
+			key:    0,
+			inner:  None,
+		}
+	}
+}
+
+impl Iterator for IterOnes<'_> {
+	type Item = u32;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some(low) = self.inner.as_mut().and_then(Iterator::next) {
+				return Some(join(self.key, low));
+			}
+			let (key, chunk) = self.chunks.next()?;
+			self.key = *key;
+			self.inner = Some(ChunkIter::new(chunk));
+		}
+	}
+}
+
+impl FusedIterator for IterOnes<'_> {}
+
+/// Iterates over the positions in one chunk.
+#[derive(Clone, Debug)]
+enum ChunkIter<'a> {
+	/// Walks an array chunk.
+	Array(slice::Iter<'a, u16>),
+	/// Walks a run chunk.
+	Run {
+		/// The runs that have not yet been entered.
+		runs: slice::Iter<'a, (u16, u16)>,
+		/// The next position in the current run.
+		next: u32,
+		/// The last position in the current run.
+		last: u32,
+	},
+	/// Walks a dense chunk.
+	Dense(BitIterOnes<'a, u64, Lsb0>),
+}
+
+impl<'a> ChunkIter<'a> {
+	/// Begins iterating over a chunk.
+	#[inline]
+	fn new(chunk: &'a Chunk) -> Self {
+		match chunk {
+			Chunk::Array(array) => Self::Array(array.iter()),
+			Chunk::Run(runs) => Self::Run {
+				runs: runs.iter(),
+				next: 1,
+				last: 0,
+			},
+			Chunk::Dense { bits, .. } => Self::Dense(bits.iter_ones()),
+		}
+	}
+}
+
+impl Iterator for ChunkIter<'_> {
+	type Item = u16;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		match self {
+			Self::Array(iter) => iter.next().copied(),
+			Self::Run { runs, next, last } => {
+				if *next > *last {
+					let &(start, end) = runs.next()?;
+					*next = start as u32;
+					*last = end as u32;
+				}
+				let out = *next as u16;
+				*next += 1;
+				Some(out)
+			},
+			Self::Dense(iter) => iter.next().map(|idx| idx as u16),
+		}
+	}
+}
@@ -0,0 +1,129 @@
+//! Set-operation trait implementations for bitmaps.
+
+use alloc::vec::Vec;
+use core::{
+	cmp::Ordering,
+	ops::{
+		BitAnd,
+		BitAndAssign,
+		BitOr,
+		BitOrAssign,
+		BitXor,
+		BitXorAssign,
+		Sub,
+		SubAssign,
+	},
+};
+
+use super::{
+	RoaringBitmap,
+	chunk::Op,
+};
+
+impl RoaringBitmap {
+	/// Combines two bitmaps with a set operation, chunk by chunk.
+	fn combine(&self, other: &Self, op: Op) -> Self {
+		let mut chunks = Vec::new();
+		let mut lhs = self.chunks.iter().peekable();
+		let mut rhs = other.chunks.iter().peekable();
+		while let (Some(&(lkey, lchunk)), Some(&(rkey, rchunk))) =
+			(lhs.peek(), rhs.peek())
+		{
+			match lkey.cmp(rkey) {
+				Ordering::Less => {
+					if op.keeps_left() {
+						chunks.push((*lkey, lchunk.clone()));
+					}
+					lhs.next();
+				},
+				Ordering::Greater => {
+					if op.keeps_right() {
+						chunks.push((*rkey, rchunk.clone()));
+					}
+					rhs.next();
+				},
+				Ordering::Equal => {
+					if let Some(chunk) = lchunk.combine(rchunk, op) {
+						chunks.push((*lkey, chunk));
+					}
+					lhs.next();
+					rhs.next();
+				},
+			}
+		}
+		if op.keeps_left() {
+			chunks.extend(lhs.cloned());
+		}
+		if op.keeps_right() {
+			chunks.extend(rhs.cloned());
+		}
+		Self { chunks }
+	}
+}
+
+/// Implements a set operation, and its assigning form, for each combination of
+/// owned and borrowed bitmaps.
+macro_rules! set_op {
+	($($trait:ident :: $func:ident, $assign:ident :: $assign_func:ident => $op:ident);+ $(;)?) => { $(
+		impl $trait<&RoaringBitmap> for &RoaringBitmap {
+			type Output = RoaringBitmap;
+
+			#[inline]
+			fn $func(self, rhs: &RoaringBitmap) -> Self::Output {
+				self.combine(rhs, Op::$op)
+			}
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl $trait<RoaringBitmap> for &RoaringBitmap {
+			type Output = RoaringBitmap;
+
+			#[inline]
+			fn $func(self, rhs: RoaringBitmap) -> Self::Output {
+				self.$func(&rhs)
+			}
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl $trait<&RoaringBitmap> for RoaringBitmap {
+			type Output = Self;
+
+			#[inline]
+			fn $func(self, rhs: &Self) -> Self::Output {
+				(&self).$func(rhs)
+			}
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl $trait<RoaringBitmap> for RoaringBitmap {
+			type Output = Self;
+
+			#[inline]
+			fn $func(self, rhs: Self) -> Self::Output {
+				(&self).$func(&rhs)
+			}
+		}
+
+		impl $assign<&RoaringBitmap> for RoaringBitmap {
+			#[inline]
+			fn $assign_func(&mut self, rhs: &Self) {
+				*self = self.combine(rhs, Op::$op);
+			}
+		}
+
+		#[cfg(not(tarpaulin_include))]
+		impl $assign<RoaringBitmap> for RoaringBitmap {
+			#[inline]
+			fn $assign_func(&mut self, rhs: Self) {
+				*self = self.combine(&rhs, Op::$op);
+			}
+		}
+	)+ };
+}
+
+set_op! {
+	BitAnd::bitand, BitAndAssign::bitand_assign => And;
+	BitOr::bitor, BitOrAssign::bitor_assign => Or;
+	BitXor::bitxor, BitXorAssign::bitxor_assign => Xor;
+	Sub::sub, SubAssign::sub_assign => Sub;
+}
@@ -0,0 +1,159 @@
+#![cfg(test)]
+
+use std::collections::BTreeSet;
+
+use rand::prelude::*;
+
+use super::*;
+use crate::prelude::*;
+
+/// Builds a bitmap and a reference set with the same contents.
+///
+/// The positions are clustered into a few chunks, with a dense region, a run
+/// of consecutive positions, and a sparse scattering, so that every chunk
+/// representation appears.
+fn sample() -> (RoaringBitmap, BTreeSet<u32>) {
+	let mut rng = thread_rng();
+	let mut set = BTreeSet::new();
+	let base = rng.gen_range(0 .. 1000) << 16;
+	for _ in 0 .. 10_000 {
+		set.insert(base + rng.gen_range(0 .. 1 << 16));
+	}
+	let start = rng.gen_range(0 .. 1 << 20) + (2000 << 16);
+	set.extend(start .. start + rng.gen_range(1 .. 100_000));
+	for _ in 0 .. 500 {
+		set.insert(rng.gen());
+	}
+	set.insert(u32::MAX);
+	let bitmap = set.iter().collect::<RoaringBitmap>();
+	(bitmap, set)
+}
+
+fn check(bitmap: &RoaringBitmap, set: &BTreeSet<u32>) {
+	assert_eq!(bitmap.len(), set.len() as u64);
+	assert!(bitmap.iter_ones().eq(set.iter().copied()));
+	assert_eq!(bitmap.min(), set.iter().next().copied());
+	assert_eq!(bitmap.max(), set.iter().next_back().copied());
+	for &pos in set.iter().step_by(97) {
+		let next = pos.wrapping_add(1);
+		assert!(bitmap.contains(pos));
+		assert_eq!(bitmap.contains(next), set.contains(&next));
+	}
+}
+
+#[test]
+fn insert_remove() {
+	let (mut bitmap, mut set) = sample();
+	check(&bitmap, &set);
+	assert!(!bitmap.insert(u32::MAX));
+
+	let mut optimized = bitmap.clone();
+	optimized.optimize();
+	assert!(
+		optimized
+			.chunks
+			.iter()
+			.any(|(_, c)| matches!(c, Chunk::Run(_)))
+	);
+	assert!(
+		optimized
+			.chunks
+			.iter()
+			.any(|(_, c)| matches!(c, Chunk::Dense { .. }))
+	);
+	assert!(
+		optimized
+			.chunks
+			.iter()
+			.any(|(_, c)| matches!(c, Chunk::Array(_)))
+	);
+	assert_eq!(optimized, bitmap);
+	check(&optimized, &set);
+
+	let mut rng = thread_rng();
+	let present = set.iter().copied().collect::<Vec<_>>();
+	for _ in 0 .. 5_000 {
+		let pos = *present.choose(&mut rng).unwrap();
+		let removed = set.remove(&pos);
+		assert_eq!(bitmap.remove(pos), removed);
+		assert_eq!(optimized.remove(pos), removed);
+		let pos = pos ^ 1;
+		let inserted = set.insert(pos);
+		assert_eq!(bitmap.insert(pos), inserted);
+		assert_eq!(optimized.insert(pos), inserted);
+	}
+	check(&bitmap, &set);
+	check(&optimized, &set);
+	assert_eq!(bitmap, optimized);
+
+	for pos in set.iter().copied().collect::<Vec<_>>() {
+		assert!(bitmap.remove(pos));
+	}
+	assert!(bitmap.is_empty());
+	assert!(bitmap.chunks.is_empty());
+	assert!(!bitmap.remove(0));
+}
+
+#[test]
+fn set_operations() {
+	for _ in 0 .. 3 {
+		let (mut a, sa) = sample();
+		let (b, sb) = sample();
+		a.optimize();
+		let shared = sa.iter().copied().step_by(3).collect::<BTreeSet<_>>();
+		let c = shared.iter().collect::<RoaringBitmap>();
+
+		for (lhs, rhs, slhs, srhs) in [
+			(&a, &b, &sa, &sb),
+			(&a, &c, &sa, &shared),
+			(&c, &a, &shared, &sa),
+		] {
+			check(&(lhs | rhs), &(slhs | srhs));
+			check(&(lhs & rhs), &(slhs & srhs));
+			check(&(lhs ^ rhs), &(slhs ^ srhs));
+			check(&(lhs - rhs), &(slhs - srhs));
+		}
+
+		let mut d = a.clone();
+		d -= &c;
+		d |= c.clone();
+		assert_eq!(d, a);
+		d ^= &a;
+		assert!(d.is_empty());
+		d |= &b;
+		d &= a.clone();
+		check(&d, &(&sa & &sb));
+	}
+}
+
+#[test]
+fn bitslice_conversions() {
+	let mut rng = thread_rng();
+	let mut bv = BitVec::<u16, Msb0>::repeat(false, 300_000);
+	bv[70_000 .. 140_000].fill(true);
+	for _ in 0 .. 20_000 {
+		let idx = rng.gen_range(140_000 .. 210_000);
+		bv.set(idx, true);
+	}
+	for _ in 0 .. 100 {
+		let idx = rng.gen_range(210_000 .. 300_000);
+		bv.set(idx, true);
+	}
+
+	let bitmap = RoaringBitmap::from(bv.as_bitslice());
+	assert!(
+		bitmap
+			.iter_ones()
+			.map(|pos| pos as usize)
+			.eq(bv.iter_ones())
+	);
+	assert_eq!(bitmap.len(), bv.count_ones() as u64);
+
+	let back = BitVec::<u16, Msb0>::from(&bitmap);
+	assert_eq!(back, bv[.. bv.last_one().unwrap() + 1]);
+	let other = bitmap.to_bitvec::<u64, Lsb0>();
+	assert_eq!(other, back);
+
+	assert!(RoaringBitmap::from_bitslice(bits![0; 100]).is_empty());
+	assert!(RoaringBitmap::new().to_bitvec::<u8, Lsb0>().is_empty());
+}
@@ -0,0 +1,112 @@
+//! General trait implementations for bitmaps.
+
+use core::{
+	fmt::{
+		self,
+		Debug,
+		Formatter,
+	},
+	iter::FromIterator,
+};
+
+use super::{
+	IterOnes,
+	RoaringBitmap,
+};
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+impl Eq for RoaringBitmap {}
+
+impl PartialEq for RoaringBitmap {
+	#[inline]
+	fn eq(&self, other: &Self) -> bool {
+		self.chunks == other.chunks
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl Debug for RoaringBitmap {
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_set().entries(self.iter_ones()).finish()
+	}
+}
+
+impl Extend<u32> for RoaringBitmap {
+	#[inline]
+	fn extend<I>(&mut self, iter: I)
+	where I: IntoIterator<Item = u32> {
+		for pos in iter {
+			self.insert(pos);
+		}
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<'a> Extend<&'a u32> for RoaringBitmap {
+	#[inline]
+	fn extend<I>(&mut self, iter: I)
+	where I: IntoIterator<Item = &'a u32> {
+		self.extend(iter.into_iter().copied());
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl FromIterator<u32> for RoaringBitmap {
+	#[inline]
+	fn from_iter<I>(iter: I) -> Self
+	where I: IntoIterator<Item = u32> {
+		let mut out = Self::new();
+		out.extend(iter);
+		out
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<'a> FromIterator<&'a u32> for RoaringBitmap {
+	#[inline]
+	fn from_iter<I>(iter: I) -> Self
+	where I: IntoIterator<Item = &'a u32> {
+		iter.into_iter().copied().collect()
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<'a> IntoIterator for &'a RoaringBitmap {
+	type IntoIter = IterOnes<'a>;
+	type Item = u32;
+
+	#[inline]
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter_ones()
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T, O> From<&BitSlice<T, O>> for RoaringBitmap
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn from(bits: &BitSlice<T, O>) -> Self {
+		Self::from_bitslice(bits)
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T, O> From<&RoaringBitmap> for BitVec<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn from(bitmap: &RoaringBitmap) -> Self {
+		bitmap.to_bitvec()
+	}
+}
@@ -0,0 +1,255 @@
+#![cfg(feature = "alloc")]
+#![doc = include_str!("../doc/runs.md")]
+
+use alloc::vec::Vec;
+use core::{
+	iter::{
+		FromIterator,
+		FusedIterator,
+	},
+	ops::Range,
+	slice,
+};
+
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+mod tests;
+
+#[doc = include_str!("../doc/runs/BitRuns.md")]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BitRuns {
+	/// The value of the first run. Each subsequent run has the opposite value
+	/// of the one before it.
+	first: bool,
+	/// The index one past the end of each run, in ascending order.
+	ends:  Vec<usize>,
+	/// The number of `1` bits in all runs.
+	ones:  usize,
+}
+
+impl BitRuns {
+	/// Creates an empty run-length sequence.
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Encodes the runs of a bit-slice.
+	///
+	/// This walks the bit-slice with [`BitSlice::iter_runs`], so it takes time
+	/// proportional to the number of runs rather than to the number of bits
+	/// in `Lsb0` and `Msb0` bit-slices.
+	///
+	/// [`BitSlice::iter_runs`]: crate::slice::BitSlice::iter_runs
+	#[inline]
+	pub fn from_bitslice<T, O>(bits: &BitSlice<T, O>) -> Self
+	where
+		T: BitStore,
+		O: BitOrder,
+	{
+		let mut out = Self::new();
+		for (bit, range) in bits.iter_runs() {
+			out.push_run(bit, range.len());
+		}
+		out
+	}
+
+	/// Gets the number of bits in the sequence.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.ends.last().copied().unwrap_or(0)
+	}
+
+	/// Tests if the sequence holds no bits.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.ends.is_empty()
+	}
+
+	/// Counts the runs in the sequence.
+	#[inline]
+	pub fn run_count(&self) -> usize {
+		self.ends.len()
+	}
+
+	/// Counts the bits in the sequence that are set to `1`.
+	///
+	/// This takes constant time.
+	#[inline]
+	pub fn count_ones(&self) -> usize {
+		self.ones
+	}
+
+	/// Counts the bits in the sequence that are cleared to `0`.
+	///
+	/// This takes constant time.
+	#[inline]
+	pub fn count_zeros(&self) -> usize {
+		self.len() - self.ones
+	}
+
+	/// Gets the value of the bit at an index.
+	///
+	/// This takes time logarithmic in the number of runs.
+	///
+	/// ## Returns
+	///
+	/// The bit at `index`, or `None` if `index` is not less than the length
+	/// of the sequence.
+	#[inline]
+	pub fn get(&self, index: usize) -> Option<bool> {
+		let run = self.ends.partition_point(|&end| end <= index);
+		if run == self.ends.len() {
+			return None;
+		}
+		Some(self.value_of(run))
+	}
+
+	/// Appends a single bit to the end of the sequence.
+	#[inline]
+	pub fn push(&mut self, bit: bool) {
+		self.push_run(bit, 1);
+	}
+
+	/// Appends `len` copies of a bit to the end of the sequence.
+	///
+	/// If the sequence already ends with a run of `bit`, that run is
+	/// lengthened rather than a new one begun.
+	#[inline]
+	pub fn push_run(&mut self, bit: bool, len: usize) {
+		if len == 0 {
+			return;
+		}
+		let end = self.len() + len;
+		if bit {
+			self.ones += len;
+		}
+		match self.ends.len() {
+			0 => {
+				self.first = bit;
+				self.ends.push(end);
+			},
+			n if self.value_of(n - 1) == bit => self.ends[n - 1] = end,
+			_ => self.ends.push(end),
+		}
+	}
+
+	/// Iterates over the runs in the sequence.
+	///
+	/// Each run is yielded as its bit-value and the range of indices that it
+	/// covers, exactly as [`BitSlice::iter_runs`] does for the bit-slice that
+	/// this sequence encodes.
+	///
+	/// [`BitSlice::iter_runs`]: crate::slice::BitSlice::iter_runs
+	#[inline]
+	pub fn iter_runs(&self) -> Runs<'_> {
+		Runs {
+			ends:  self.ends.iter(),
+			bit:   self.first,
+			start: 0,
+		}
+	}
+
+	/// Expands the sequence into a bit-vector.
+	#[inline]
+	pub fn to_bitvec<T, O>(&self) -> BitVec<T, O>
+	where
+		T: BitStore,
+		O: BitOrder,
+	{
+		let mut bv = BitVec::with_capacity(self.len());
+		for (bit, range) in self.iter_runs() {
+			bv.resize(range.end, bit);
+		}
+		bv
+	}
+
+	/// Gets the value of a run from its index.
+	#[inline]
+	fn value_of(&self, run: usize) -> bool {
+		self.first ^ (run % 2 == 1)
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T, O> From<&BitSlice<T, O>> for BitRuns
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn from(bits: &BitSlice<T, O>) -> Self {
+		Self::from_bitslice(bits)
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T, O> From<&BitRuns> for BitVec<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn from(runs: &BitRuns) -> Self {
+		runs.to_bitvec()
+	}
+}
+
+impl Extend<bool> for BitRuns {
+	#[inline]
+	fn extend<I>(&mut self, iter: I)
+	where I: IntoIterator<Item = bool> {
+		for bit in iter {
+			self.push(bit);
+		}
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl FromIterator<bool> for BitRuns {
+	#[inline]
+	fn from_iter<I>(iter: I) -> Self
+	where I: IntoIterator<Item = bool> {
+		let mut out = Self::new();
+		out.extend(iter);
+		out
+	}
+}
+
+#[doc = include_str!("../doc/runs/Runs.md")]
+#[derive(Clone, Debug)]
+pub struct Runs<'a> {
+	/// The ends of the runs that have not yet been yielded.
+	ends:  slice::Iter<'a, usize>,
+	/// The value of the next run.
+	bit:   bool,
+	/// The start of the next run.
+	start: usize,
+}
+
+impl Iterator for Runs<'_> {
+	type Item = (bool, Range<usize>);
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let end = *self.ends.next()?;
+		let out = (self.bit, self.start .. end);
+		self.bit = !self.bit;
+		self.start = end;
+		Some(out)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.ends.size_hint()
+	}
+}
+
+impl ExactSizeIterator for Runs<'_> {}
+
+impl FusedIterator for Runs<'_> {}
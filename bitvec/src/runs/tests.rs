@@ -0,0 +1,59 @@
+#![cfg(test)]
+
+use rand::prelude::*;
+
+use super::*;
+use crate::prelude::*;
+
+#[test]
+fn encode_decode() {
+	let mut rng = thread_rng();
+	let mut bv = BitVec::<u16, Msb0>::new();
+	while bv.len() < 10_000 {
+		let len = rng.gen_range(1 .. 300);
+		let bit = rng.gen::<bool>();
+		bv.resize(bv.len() + len, bit);
+	}
+
+	let runs = BitRuns::from(&bv[7 ..]);
+	let bits = &bv[7 ..];
+	assert_eq!(runs.len(), bits.len());
+	assert_eq!(runs.count_ones(), bits.count_ones());
+	assert_eq!(runs.count_zeros(), bits.count_zeros());
+	assert!(runs.iter_runs().eq(bits.iter_runs()));
+	assert_eq!(runs.iter_runs().len(), runs.run_count());
+	for (idx, bit) in bits.iter().by_vals().enumerate() {
+		assert_eq!(runs.get(idx), Some(bit));
+	}
+	assert_eq!(runs.get(bits.len()), None);
+
+	assert_eq!(runs.to_bitvec::<u16, Msb0>(), bits);
+	assert_eq!(BitVec::<u8, Lsb0>::from(&runs), bits);
+	assert_eq!(bits.iter().by_vals().collect::<BitRuns>(), runs);
+}
+
+#[test]
+fn push_runs() {
+	let mut runs = BitRuns::new();
+	assert!(runs.is_empty());
+	assert_eq!(runs.get(0), None);
+	assert!(runs.to_bitvec::<usize, Lsb0>().is_empty());
+
+	runs.push_run(true, 0);
+	assert!(runs.is_empty());
+	runs.push_run(true, 3);
+	runs.push(true);
+	runs.push_run(false, 2);
+	runs.push(true);
+	assert_eq!(runs.run_count(), 3);
+	assert_eq!(runs.len(), 7);
+	assert_eq!(runs.count_ones(), 5);
+	assert_eq!(runs.iter_runs().collect::<Vec<_>>(), [
+		(true, 0 .. 4),
+		(false, 4 .. 6),
+		(true, 6 .. 7),
+	]);
+	assert_eq!(runs.to_bitvec::<u8, Msb0>(), bits![1, 1, 1, 1, 0, 0, 1]);
+	assert_eq!(runs.get(4), Some(false));
+	assert_eq!(runs.get(6), Some(true));
+}
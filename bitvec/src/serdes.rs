@@ -2,6 +2,8 @@
 #![doc = include_str!("../doc/serdes.md")]
 
 mod array;
+mod bloom;
+pub mod packed;
 mod slice;
 mod utils;
 
@@ -100,6 +102,9 @@ mod tests {
 		assert_impl_all!(&BitSlice<u8, Lsb0>: Deserialize<'static>);
 		assert_impl_all!(&BitSlice<u8, Msb0>: Deserialize<'static>);
 		assert_impl_all!(&BitSlice<u8, LocalBits>: Deserialize<'static>);
+		assert_impl_all!(
+			super::packed::Packed<&BitSlice<u16, Lsb0>>: Deserialize<'static>
+		);
 
 		check_impl! {
 			Lsb0 @ u8, u16, u32, usize;
@@ -112,35 +117,29 @@ mod tests {
 			Msb0 @ RadiumU8, RadiumU16, RadiumU32, RadiumUsize;
 			LocalBits @ RadiumU8, RadiumU16, RadiumU32, RadiumUsize;
 		}
-		radium::if_atomic! {
-			if atomic(8) {
-				check_impl! {
-					Lsb0 @ AtomicU8;
-					Msb0 @ AtomicU8;
-					LocalBits @ AtomicU8;
-				}
-			}
-			if atomic(16) {
-				check_impl! {
-					Lsb0 @ AtomicU16;
-					Msb0 @ AtomicU16;
-					LocalBits @ AtomicU16;
-				}
-			}
-			if atomic(32) {
-				check_impl! {
-					Lsb0 @ AtomicU32;
-					Msb0 @ AtomicU32;
-					LocalBits @ AtomicU32;
-				}
-			}
-			if atomic(ptr) {
-				check_impl! {
-					Lsb0 @ AtomicUsize;
-					Msb0 @ AtomicUsize;
-					LocalBits @ AtomicUsize;
-				}
-			}
+		#[cfg(target_has_atomic = "8")]
+		check_impl! {
+			Lsb0 @ AtomicU8;
+			Msb0 @ AtomicU8;
+			LocalBits @ AtomicU8;
+		}
+		#[cfg(target_has_atomic = "16")]
+		check_impl! {
+			Lsb0 @ AtomicU16;
+			Msb0 @ AtomicU16;
+			LocalBits @ AtomicU16;
+		}
+		#[cfg(target_has_atomic = "32")]
+		check_impl! {
+			Lsb0 @ AtomicU32;
+			Msb0 @ AtomicU32;
+			LocalBits @ AtomicU32;
+		}
+		#[cfg(target_has_atomic = "ptr")]
+		check_impl! {
+			Lsb0 @ AtomicUsize;
+			Msb0 @ AtomicUsize;
+			LocalBits @ AtomicUsize;
 		}
 		#[cfg(target_pointer_width = "64")]
 		check_impl! {
@@ -148,13 +147,17 @@ mod tests {
 			Msb0 @ u64, RadiumU64;
 			LocalBits @ u64, RadiumU64;
 		}
-		#[cfg(target_pointer_width = "64")]
-		radium::if_atomic!(if atomic(64) {
-			check_impl! {
-				Lsb0 @ AtomicU64;
-				Msb0 @ AtomicU64;
-				LocalBits @ AtomicU64;
-			}
-		});
+		#[cfg(all(target_pointer_width = "64", target_has_atomic = "64"))]
+		check_impl! {
+			Lsb0 @ AtomicU64;
+			Msb0 @ AtomicU64;
+			LocalBits @ AtomicU64;
+		}
+		#[cfg(feature = "u128")]
+		check_impl! {
+			Lsb0 @ u128, RadiumU128;
+			Msb0 @ u128, RadiumU128;
+			LocalBits @ u128, RadiumU128;
+		}
 	}
 }
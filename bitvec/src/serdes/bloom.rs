@@ -0,0 +1,253 @@
+#![cfg(feature = "alloc")]
+#![doc = include_str!("../../doc/serdes/bloom.md")]
+
+use core::{
+	fmt::{
+		self,
+		Formatter,
+	},
+	hash::BuildHasher,
+	marker::PhantomData,
+};
+
+use serde::{
+	de::{
+		Deserialize,
+		Deserializer,
+		Error,
+		MapAccess,
+		SeqAccess,
+		Unexpected,
+		Visitor,
+	},
+	ser::{
+		Serialize,
+		SerializeStruct,
+		Serializer,
+	},
+};
+
+use super::packed::Packed;
+use crate::{
+	bloom::BloomFilter,
+	order::Lsb0,
+	vec::BitVec,
+};
+
+/// The fields of a bloom filter in wire format.
+static FIELDS: &[&str] = &["hashes", "bits"];
+
+impl<H> Serialize for BloomFilter<H> {
+	#[inline]
+	fn serialize<S>(&self, serializer: S) -> super::Result<S>
+	where S: Serializer {
+		let mut state = serializer.serialize_struct("BloomFilter", 2)?;
+
+		state.serialize_field("hashes", &self.hashes())?;
+		state.serialize_field("bits", &Packed(self.as_bitslice()))?;
+
+		state.end()
+	}
+}
+
+impl<'de, H> Deserialize<'de> for BloomFilter<H>
+where H: BuildHasher + Default
+{
+	#[inline]
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: Deserializer<'de> {
+		deserializer.deserialize_struct(
+			"BloomFilter",
+			FIELDS,
+			BloomVisitor::<H> {
+				hashes: None,
+				bits:   None,
+				_hash:  PhantomData,
+			},
+		)
+	}
+}
+
+/// The components of a bloom filter in wire format.
+enum Key {
+	/// Denotes the number of hashes set by each item.
+	Hashes,
+	/// Denotes the filter bits.
+	Bits,
+}
+
+/// Visits field tokens of a bloom filter.
+struct KeyVisitor;
+
+impl<'de> Deserialize<'de> for Key {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: Deserializer<'de> {
+		deserializer.deserialize_identifier(KeyVisitor)
+	}
+}
+
+impl<'de> Visitor<'de> for KeyVisitor {
+	type Value = Key;
+
+	fn expecting(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.write_str("field_identifier")
+	}
+
+	fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+	where E: Error {
+		match value {
+			"hashes" => Ok(Key::Hashes),
+			"bits" => Ok(Key::Bits),
+			_ => Err(E::unknown_field(value, FIELDS)),
+		}
+	}
+}
+
+/// Assembles a bloom filter from its fields.
+struct BloomVisitor<H> {
+	/// The number of hashes set by each item.
+	hashes: Option<u32>,
+	/// The filter bits.
+	bits:   Option<BitVec<usize, Lsb0>>,
+	/// The hasher that the filter will use.
+	_hash:  PhantomData<H>,
+}
+
+impl<H> BloomVisitor<H>
+where H: BuildHasher + Default
+{
+	/// Checks that all fields have been received and describe a usable
+	/// filter.
+	fn assemble<E>(mut self) -> Result<BloomFilter<H>, E>
+	where E: Error {
+		let hashes = self
+			.hashes
+			.take()
+			.ok_or_else(|| E::missing_field("hashes"))?;
+		let bits = self.bits.take().ok_or_else(|| E::missing_field("bits"))?;
+		if hashes == 0 {
+			return Err(E::invalid_value(
+				Unexpected::Unsigned(0),
+				&"at least one hash",
+			));
+		}
+		if bits.is_empty() {
+			return Err(E::invalid_length(0, &"at least one filter bit"));
+		}
+		Ok(BloomFilter::from_parts(bits, hashes, H::default()))
+	}
+}
+
+impl<'de, H> Visitor<'de> for BloomVisitor<H>
+where H: BuildHasher + Default
+{
+	type Value = BloomFilter<H>;
+
+	#[inline]
+	fn expecting(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.write_str("a bloom filter")
+	}
+
+	#[inline]
+	fn visit_seq<V>(mut self, mut seq: V) -> Result<Self::Value, V::Error>
+	where V: SeqAccess<'de> {
+		self.hashes = Some(
+			seq.next_element()?
+				.ok_or_else(|| <V::Error>::invalid_length(0, &self))?,
+		);
+		self.bits = Some(
+			seq.next_element::<Packed<BitVec<usize, Lsb0>>>()?
+				.ok_or_else(|| <V::Error>::invalid_length(1, &self))?
+				.0,
+		);
+
+		self.assemble()
+	}
+
+	#[inline]
+	fn visit_map<V>(mut self, mut map: V) -> Result<Self::Value, V::Error>
+	where V: MapAccess<'de> {
+		while let Some(key) = map.next_key()? {
+			match key {
+				Key::Hashes => {
+					if self.hashes.replace(map.next_value()?).is_some() {
+						return Err(<V::Error>::duplicate_field("hashes"));
+					}
+				},
+				Key::Bits => {
+					let Packed(bits) = map.next_value()?;
+					if self.bits.replace(bits).is_some() {
+						return Err(<V::Error>::duplicate_field("bits"));
+					}
+				},
+			}
+		}
+
+		self.assemble()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::hash::BuildHasherDefault;
+	use std::collections::hash_map::DefaultHasher;
+
+	use serde_test::{
+		Compact,
+		Token,
+		assert_de_tokens_error,
+	};
+
+	use crate::bloom::BloomFilter;
+
+	type Filter = BloomFilter<BuildHasherDefault<DefaultHasher>>;
+
+	#[test]
+	fn round_trip() {
+		let mut filter = Filter::with_size(100, 3, Default::default());
+		for word in ["alpha", "beta", "gamma"] {
+			filter.insert(word);
+		}
+
+		let json = serde_json::to_string(&filter).unwrap();
+		assert!(json.starts_with(r#"{"hashes":3,"bits":{"order":"#));
+		let back = serde_json::from_str::<Filter>(&json).unwrap();
+		assert_eq!(back.as_bitslice(), filter.as_bitslice());
+		assert!(back.contains("beta"));
+
+		let bytes = bincode::serialize(&filter).unwrap();
+		let back = bincode::deserialize::<Filter>(&bytes).unwrap();
+		assert_eq!(back.hashes(), 3);
+		assert_eq!(back.as_bitslice(), filter.as_bitslice());
+	}
+
+	#[test]
+	fn malformed() {
+		assert_de_tokens_error::<Compact<Filter>>(
+			&[
+				Token::Struct {
+					name: "BloomFilter",
+					len:  2,
+				},
+				Token::Str("hashes"),
+				Token::U32(0),
+				Token::Str("bits"),
+				Token::Struct {
+					name: "BitPacked",
+					len:  4,
+				},
+				Token::Str("order"),
+				Token::Str(core::any::type_name::<crate::order::Lsb0>()),
+				Token::Str("head"),
+				Token::U8(0),
+				Token::Str("bits"),
+				Token::U64(8),
+				Token::Str("data"),
+				Token::BorrowedBytes(&[0xFF]),
+				Token::StructEnd,
+				Token::StructEnd,
+			],
+			"invalid value: integer `0`, expected at least one hash",
+		);
+	}
+}
@@ -0,0 +1,720 @@
+#![doc = include_str!("../../doc/serdes/packed.md")]
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::{
+	any::{
+		self,
+		TypeId,
+	},
+	fmt::{
+		self,
+		Formatter,
+	},
+	mem,
+	ops::Range,
+};
+
+#[cfg(feature = "alloc")]
+use funty::Fundamental;
+use serde::de::{
+	Deserialize,
+	Deserializer,
+	Error,
+	MapAccess,
+	SeqAccess,
+	Visitor,
+};
+#[cfg(feature = "alloc")]
+use serde::ser::{
+	Serialize,
+	SerializeStruct,
+	Serializer,
+};
+use wyz::comu::Address;
+
+use super::{
+	FIELDS,
+	Field,
+	utils::TypeName,
+};
+#[cfg(feature = "alloc")]
+use crate::{
+	boxed::BitBox,
+	vec::BitVec,
+};
+use crate::{
+	mem::{
+		BitRegister,
+		bits_of,
+	},
+	order::{
+		BitOrder,
+		Lsb0,
+		Msb0,
+	},
+	ptr::check_alignment,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+#[doc = include_str!("../../doc/serdes/packed/Packed.md")]
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Packed<B>(pub B);
+
+/// Serializes a bit-slice, or any container of one, in the packed format.
+///
+/// This can be used as `#[serde(serialize_with = "...")]`, or together with
+/// [`deserialize`] as `#[serde(with = "bitvec::serdes::packed")]`.
+#[inline]
+#[cfg(feature = "alloc")]
+pub fn serialize<B, T, O, S>(bits: &B, serializer: S) -> super::Result<S>
+where
+	B: ?Sized + AsRef<BitSlice<T, O>>,
+	T: BitStore,
+	O: BitOrder,
+	S: Serializer,
+{
+	serialize_packed(bits.as_ref(), serializer)
+}
+
+/// Deserializes any type that [`Packed`] can deserialize from the packed
+/// format.
+///
+/// This can be used as `#[serde(deserialize_with = "...")]`, or together with
+/// [`serialize`] as `#[serde(with = "bitvec::serdes::packed")]`.
+#[inline]
+pub fn deserialize<'de, B, D>(deserializer: D) -> Result<B, D::Error>
+where
+	Packed<B>: Deserialize<'de>,
+	D: Deserializer<'de>,
+{
+	Packed::<B>::deserialize(deserializer).map(|Packed(inner)| inner)
+}
+
+#[cfg(feature = "alloc")]
+impl<T, O> Serialize for Packed<&BitSlice<T, O>>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn serialize<S>(&self, serializer: S) -> super::Result<S>
+	where S: Serializer {
+		serialize_packed(self.0, serializer)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T, O> Serialize for Packed<BitBox<T, O>>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn serialize<S>(&self, serializer: S) -> super::Result<S>
+	where S: Serializer {
+		serialize_packed(self.0.as_bitslice(), serializer)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T, O> Serialize for Packed<BitVec<T, O>>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn serialize<S>(&self, serializer: S) -> super::Result<S>
+	where S: Serializer {
+		serialize_packed(self.0.as_bitslice(), serializer)
+	}
+}
+
+impl<'de, T, O> Deserialize<'de> for Packed<&'de BitSlice<T, O>>
+where
+	T: BitRegister + BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: Deserializer<'de> {
+		let parts = Parts::<O>::deserialize(deserializer)?;
+		let span = parts.span()?;
+		let data = match parts.data {
+			Data::Borrowed(data) => data,
+			#[cfg(feature = "alloc")]
+			Data::Owned(_) => {
+				return Err(D::Error::custom(
+					"a packed bit-slice can only be borrowed from bytes stored \
+					 directly in the transport buffer",
+				));
+			},
+		};
+		let width = bits_of::<T>();
+		if width > 8 && natural_order::<O>() != Some(cfg!(target_endian = "big"))
+		{
+			return Err(D::Error::custom(format_args!(
+				"packed `{}` bits cannot be viewed as `u{}` elements on this \
+				 target",
+				any::type_name::<O>(),
+				width,
+			)));
+		}
+		let addr = Address::try_from(data.as_ptr().cast::<T>())
+			.ok()
+			.and_then(|addr| check_alignment(addr).ok())
+			.filter(|_| data.len() % mem::size_of::<T>() == 0)
+			.ok_or_else(|| {
+				D::Error::custom(format_args!(
+					"packed bytes are not aligned to `u{}` elements",
+					width,
+				))
+			})?;
+		let elts = unsafe {
+			core::slice::from_raw_parts(
+				addr.to_const(),
+				data.len() / mem::size_of::<T>(),
+			)
+		};
+		BitSlice::try_from_slice(elts)
+			.map(|bits| Packed(&bits[span]))
+			.map_err(D::Error::custom)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, T, O> Deserialize<'de> for Packed<BitBox<T, O>>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: Deserializer<'de> {
+		Packed::<BitVec<T, O>>::deserialize(deserializer)
+			.map(|Packed(bv)| Packed(bv.into_boxed_bitslice()))
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, T, O> Deserialize<'de> for Packed<BitVec<T, O>>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: Deserializer<'de> {
+		let parts = Parts::<O>::deserialize(deserializer)?;
+		let span = parts.span()?;
+		let bytes = BitSlice::<u8, O>::try_from_slice(parts.data.as_slice())
+			.map_err(D::Error::custom)?;
+		let mut out = BitVec::with_capacity(span.len());
+		out.extend_from_bitslice(&bytes[span]);
+		Ok(Packed(out))
+	}
+}
+
+/// Serializes a bit-slice in the packed format.
+#[cfg(feature = "alloc")]
+fn serialize_packed<T, O, S>(
+	bits: &BitSlice<T, O>,
+	serializer: S,
+) -> super::Result<S>
+where
+	T: BitStore,
+	O: BitOrder,
+	S: Serializer,
+{
+	let readable = serializer.is_human_readable();
+	let (head, data) = pack(bits);
+	let mut state = serializer.serialize_struct("BitPacked", FIELDS.len())?;
+
+	state.serialize_field("order", &any::type_name::<O>())?;
+	state.serialize_field("head", &head)?;
+	state.serialize_field("bits", &(bits.len() as u64))?;
+	if readable {
+		state.serialize_field("data", &Hex(&data))?;
+	}
+	else {
+		state.serialize_field("data", &Bytes(&data))?;
+	}
+
+	state.end()
+}
+
+/// Lays a bit-slice out as packed bytes, and finds the index of its first bit
+/// in them.
+///
+/// `Lsb0` and `Msb0` elements are split into bytes in the byte order through
+/// which their bits run continuously, so that the bytes have the same layout
+/// as a `BitSlice<u8, O>`. Other orderings are copied into a `BitVec<u8, O>`.
+#[cfg(feature = "alloc")]
+fn pack<T, O>(bits: &BitSlice<T, O>) -> (u8, Vec<u8>)
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	let step = mem::size_of::<T::Mem>();
+	let big_endian = match natural_order::<O>() {
+		Some(big_endian) => big_endian,
+		None if step == 1 => false,
+		None => {
+			let mut bv = BitVec::<u8, O>::with_capacity(bits.len());
+			bv.extend_from_bitslice(bits);
+			return (0, bv.into_vec());
+		},
+	};
+	let head = if bits.is_empty() {
+		0
+	}
+	else {
+		bits.as_bitspan().head().into_inner()
+	};
+	let domain = bits.domain();
+	let mut data = Vec::with_capacity(domain.len() * step);
+	for elem in domain.map(|elem| elem.as_u128()) {
+		if big_endian {
+			data.extend_from_slice(&elem.to_be_bytes()[16 - step ..]);
+		}
+		else {
+			data.extend_from_slice(&elem.to_le_bytes()[.. step]);
+		}
+	}
+	(head, data)
+}
+
+/// Finds the byte order in which the bits of an ordering’s elements run
+/// continuously: `Some(false)` for little-endian `Lsb0`, `Some(true)` for
+/// big-endian `Msb0`, and `None` for all others.
+fn natural_order<O>() -> Option<bool>
+where O: BitOrder {
+	let id = TypeId::of::<O>();
+	if id == TypeId::of::<Lsb0>() {
+		Some(false)
+	}
+	else if id == TypeId::of::<Msb0>() {
+		Some(true)
+	}
+	else {
+		None
+	}
+}
+
+/// Serializes bytes through Serde’s byte-array model.
+#[cfg(feature = "alloc")]
+struct Bytes<'a>(&'a [u8]);
+
+#[cfg(feature = "alloc")]
+impl Serialize for Bytes<'_> {
+	fn serialize<S>(&self, serializer: S) -> super::Result<S>
+	where S: Serializer {
+		serializer.serialize_bytes(self.0)
+	}
+}
+
+/// Serializes bytes as a string of lowercase hexadecimal digits.
+#[cfg(feature = "alloc")]
+struct Hex<'a>(&'a [u8]);
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Hex<'_> {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		self.0
+			.iter()
+			.try_for_each(|byte| write!(fmt, "{:02x}", byte))
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for Hex<'_> {
+	fn serialize<S>(&self, serializer: S) -> super::Result<S>
+	where S: Serializer {
+		serializer.collect_str(self)
+	}
+}
+
+/// The components of a bit-slice in the packed format.
+struct Parts<'de, O>
+where O: BitOrder
+{
+	/// The ordering of bits within each byte.
+	order: Option<TypeName<O>>,
+	/// The index of the first live bit in `data`.
+	head:  Option<u8>,
+	/// The number of live bits.
+	bits:  Option<u64>,
+	/// The bytes that hold the live bits.
+	data:  Option<Data<'de>>,
+}
+
+/// The components of a bit-slice, after all fields have been received.
+struct Complete<'de> {
+	/// The index of the first live bit in `data`.
+	head: u8,
+	/// The number of live bits.
+	bits: u64,
+	/// The bytes that hold the live bits.
+	data: Data<'de>,
+}
+
+impl<'de> Complete<'de> {
+	/// Checks that the live bits lie within the data, and finds their range.
+	fn span<E>(&self) -> Result<Range<usize>, E>
+	where E: Error {
+		let head = self.head as usize;
+		let have = self.data.as_slice().len().saturating_mul(8);
+		usize::try_from(self.bits)
+			.ok()
+			.and_then(|bits| bits.checked_add(head))
+			.filter(|&end| end <= have)
+			.map(|end| head .. end)
+			.ok_or_else(|| {
+				E::custom(format_args!(
+					"{} packed bits, starting at bit {}, do not fit in {} bytes",
+					self.bits,
+					head,
+					have / 8,
+				))
+			})
+	}
+}
+
+impl<'de, O> Parts<'de, O>
+where O: BitOrder
+{
+	/// Deserializes the fields of a packed bit-slice.
+	fn deserialize<D>(deserializer: D) -> Result<Complete<'de>, D::Error>
+	where D: Deserializer<'de> {
+		deserializer.deserialize_struct("BitPacked", FIELDS, Self {
+			order: None,
+			head:  None,
+			bits:  None,
+			data:  None,
+		})
+	}
+
+	/// Checks that all fields have been received.
+	fn assemble<E>(mut self) -> Result<Complete<'de>, E>
+	where E: Error {
+		self.order.take().ok_or_else(|| E::missing_field("order"))?;
+		Ok(Complete {
+			head: self.head.take().ok_or_else(|| E::missing_field("head"))?,
+			bits: self.bits.take().ok_or_else(|| E::missing_field("bits"))?,
+			data: self.data.take().ok_or_else(|| E::missing_field("data"))?,
+		})
+	}
+}
+
+impl<'de, O> Visitor<'de> for Parts<'de, O>
+where O: BitOrder
+{
+	type Value = Complete<'de>;
+
+	#[inline]
+	fn expecting(&self, fmt: &mut Formatter) -> fmt::Result {
+		write!(fmt, "packed `{}` bits", any::type_name::<O>())
+	}
+
+	#[inline]
+	fn visit_seq<V>(mut self, mut seq: V) -> Result<Self::Value, V::Error>
+	where V: SeqAccess<'de> {
+		self.order = Some(
+			seq.next_element()?
+				.ok_or_else(|| <V::Error>::invalid_length(0, &self))?,
+		);
+		self.head = Some(
+			seq.next_element()?
+				.ok_or_else(|| <V::Error>::invalid_length(1, &self))?,
+		);
+		self.bits = Some(
+			seq.next_element()?
+				.ok_or_else(|| <V::Error>::invalid_length(2, &self))?,
+		);
+		self.data = Some(
+			seq.next_element()?
+				.ok_or_else(|| <V::Error>::invalid_length(3, &self))?,
+		);
+
+		self.assemble()
+	}
+
+	#[inline]
+	fn visit_map<V>(mut self, mut map: V) -> Result<Self::Value, V::Error>
+	where V: MapAccess<'de> {
+		while let Some(key) = map.next_key()? {
+			match key {
+				Field::Order => {
+					if self.order.replace(map.next_value()?).is_some() {
+						return Err(<V::Error>::duplicate_field("order"));
+					}
+				},
+				Field::Head => {
+					if self.head.replace(map.next_value()?).is_some() {
+						return Err(<V::Error>::duplicate_field("head"));
+					}
+				},
+				Field::Bits => {
+					if self.bits.replace(map.next_value()?).is_some() {
+						return Err(<V::Error>::duplicate_field("bits"));
+					}
+				},
+				Field::Data => {
+					if self.data.replace(map.next_value()?).is_some() {
+						return Err(<V::Error>::duplicate_field("data"));
+					}
+				},
+			}
+		}
+
+		self.assemble()
+	}
+}
+
+/// The `data` field of a packed bit-slice.
+enum Data<'de> {
+	/// Bytes borrowed from the transport buffer.
+	Borrowed(&'de [u8]),
+	/// Bytes copied or decoded out of the transport buffer.
+	#[cfg(feature = "alloc")]
+	Owned(Vec<u8>),
+}
+
+impl Data<'_> {
+	/// Views the bytes, wherever they are stored.
+	fn as_slice(&self) -> &[u8] {
+		match self {
+			Self::Borrowed(data) => data,
+			#[cfg(feature = "alloc")]
+			Self::Owned(data) => data,
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for Data<'de> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where D: Deserializer<'de> {
+		if deserializer.is_human_readable() {
+			deserializer.deserialize_str(DataVisitor)
+		}
+		else {
+			deserializer.deserialize_bytes(DataVisitor)
+		}
+	}
+}
+
+/// Visits the `data` field of a packed bit-slice.
+struct DataVisitor;
+
+impl<'de> Visitor<'de> for DataVisitor {
+	type Value = Data<'de>;
+
+	fn expecting(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.write_str("packed bytes, or a string of hexadecimal digits")
+	}
+
+	fn visit_borrowed_bytes<E>(
+		self,
+		value: &'de [u8],
+	) -> Result<Self::Value, E>
+	where
+		E: Error,
+	{
+		Ok(Data::Borrowed(value))
+	}
+
+	#[cfg(feature = "alloc")]
+	fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+	where E: Error {
+		Ok(Data::Owned(value.to_vec()))
+	}
+
+	#[cfg(feature = "alloc")]
+	fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+	where E: Error {
+		Ok(Data::Owned(value))
+	}
+
+	#[cfg(feature = "alloc")]
+	fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+	where E: Error {
+		if value.len() % 2 != 0 {
+			return Err(E::invalid_length(
+				value.len(),
+				&"an even number of hexadecimal digits",
+			));
+		}
+		value
+			.as_bytes()
+			.chunks(2)
+			.map(|pair| {
+				let digit = |byte: u8| (byte as char).to_digit(16);
+				match (digit(pair[0]), digit(pair[1])) {
+					(Some(high), Some(low)) => Ok((high << 4 | low) as u8),
+					_ => Err(E::invalid_value(
+						serde::de::Unexpected::Str(value),
+						&"hexadecimal digits",
+					)),
+				}
+			})
+			.collect::<Result<Vec<u8>, E>>()
+			.map(Data::Owned)
+	}
+
+	#[cfg(feature = "alloc")]
+	fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+	where V: SeqAccess<'de> {
+		let mut data = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+		while let Some(byte) = seq.next_element()? {
+			data.push(byte);
+		}
+		Ok(Data::Owned(data))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[cfg(all(feature = "alloc", not(feature = "std")))]
+	use alloc::format;
+	use core::any;
+
+	use serde_test::{
+		Compact,
+		Configure,
+		Token,
+		assert_de_tokens,
+		assert_de_tokens_error,
+	};
+
+	use super::Packed;
+	use crate::prelude::*;
+
+	#[test]
+	fn borrowed() {
+		let bits = bits![u8, Msb0; 0, 0, 1, 0, 1, 1, 0, 1, 1, 1];
+		let tokens = &[
+			Token::Seq { len: Some(4) },
+			Token::BorrowedStr(any::type_name::<Msb0>()),
+			Token::U8(2),
+			Token::U64(8),
+			Token::BorrowedBytes(&[0x2D, 0xC0]),
+			Token::SeqEnd,
+		];
+		assert_de_tokens(&Packed(&bits[2 ..]).compact(), tokens);
+
+		assert_de_tokens_error::<Compact<Packed<&BitSlice<u8, Msb0>>>>(
+			&[
+				Token::Seq { len: Some(4) },
+				Token::BorrowedStr(any::type_name::<Msb0>()),
+				Token::U8(4),
+				Token::U64(13),
+				Token::BorrowedBytes(&[0x2D, 0xC0]),
+				Token::SeqEnd,
+			],
+			"13 packed bits, starting at bit 4, do not fit in 2 bytes",
+		);
+		#[cfg(feature = "alloc")]
+		assert_de_tokens_error::<Compact<Packed<&BitSlice<u8, Msb0>>>>(
+			&[
+				Token::Seq { len: Some(4) },
+				Token::BorrowedStr(any::type_name::<Msb0>()),
+				Token::U8(0),
+				Token::U64(8),
+				Token::Bytes(&[0x2D]),
+				Token::SeqEnd,
+			],
+			"a packed bit-slice can only be borrowed from bytes stored \
+			 directly in the transport buffer",
+		);
+	}
+
+	#[test]
+	#[cfg(all(feature = "alloc", target_endian = "little"))]
+	fn borrowed_wide() {
+		static DATA: [u32; 3] = [0x0000_00A5, 0x8000_0000, 0];
+		let bytes = unsafe {
+			core::slice::from_raw_parts(DATA.as_ptr().cast::<u8>(), 12)
+		};
+		let tokens = &[
+			Token::Seq { len: Some(4) },
+			Token::BorrowedStr(any::type_name::<Lsb0>()),
+			Token::U8(2),
+			Token::U64(62),
+			Token::BorrowedBytes(&bytes[.. 8]),
+			Token::SeqEnd,
+		];
+		assert_de_tokens(
+			&Packed(&DATA.view_bits::<Lsb0>()[2 .. 64]).compact(),
+			tokens,
+		);
+
+		let tokens = &[
+			Token::Seq { len: Some(4) },
+			Token::BorrowedStr(any::type_name::<Lsb0>()),
+			Token::U8(0),
+			Token::U64(16),
+			Token::BorrowedBytes(&bytes[1 .. 9]),
+			Token::SeqEnd,
+		];
+		assert_de_tokens_error::<Compact<Packed<&BitSlice<u32, Lsb0>>>>(
+			tokens,
+			"packed bytes are not aligned to `u32` elements",
+		);
+		assert_de_tokens_error::<Compact<Packed<&BitSlice<u32, Msb0>>>>(
+			&[
+				Token::Seq { len: Some(4) },
+				Token::BorrowedStr(any::type_name::<Msb0>()),
+				Token::U8(0),
+				Token::U64(16),
+				Token::BorrowedBytes(&bytes[.. 8]),
+				Token::SeqEnd,
+			],
+			&format!(
+				"packed `{}` bits cannot be viewed as `u32` elements on this \
+				 target",
+				any::type_name::<Msb0>(),
+			),
+		);
+	}
+
+	#[test]
+	#[cfg(feature = "alloc")]
+	fn formats() {
+		let mut bv = BitVec::<u16, Msb0>::from_element(0xB2F2);
+		bv.push(true);
+		let bits = &bv[1 ..];
+
+		let json = serde_json::to_string(&Packed(bits)).unwrap();
+		assert_eq!(
+			json,
+			format!(
+				r#"{{"order":"{}","head":1,"bits":16,"data":"32f28000"}}"#,
+				any::type_name::<Msb0>(),
+			),
+		);
+		assert!(json.len() < serde_json::to_string(bits).unwrap().len());
+		let Packed(copy) =
+			serde_json::from_str::<Packed<BitVec<u8, Msb0>>>(&json).unwrap();
+		assert_eq!(copy, bits);
+
+		let encoded = bincode::serialize(&Packed(bits)).unwrap();
+		let Packed(view) =
+			bincode::deserialize::<Packed<&BitSlice<u8, Msb0>>>(&encoded)
+				.unwrap();
+		assert_eq!(view, bits);
+		let Packed(copy) =
+			bincode::deserialize::<Packed<BitBox<u32, Msb0>>>(&encoded).unwrap();
+		assert_eq!(copy, bits);
+
+		let lsb = bitvec![u32, Lsb0; 1, 1, 0, 1];
+		let json = serde_json::to_string(&Packed(lsb.clone())).unwrap();
+		assert!(json.ends_with(r#""data":"0b000000"}"#));
+		let copy = super::deserialize::<BitVec<u8, Lsb0>, _>(
+			&mut serde_json::Deserializer::from_str(&json),
+		)
+		.unwrap();
+		assert_eq!(copy, lsb);
+	}
+}
@@ -214,7 +214,7 @@ where
 		let bits = self.bits.take().ok_or_else(|| E::missing_field("bits"))?;
 		let data = self.data.take().ok_or_else(|| E::missing_field("data"))?;
 
-		(self.func)(data, head, bits as usize).map_err(|_| todo!())
+		(self.func)(data, head, bits as usize).map_err(E::custom)
 	}
 }
 
@@ -0,0 +1,334 @@
+#![cfg(feature = "alloc")]
+#![doc = include_str!("../doc/set.md")]
+
+use core::{
+	cmp,
+	ops::{
+		Bound,
+		RangeBounds,
+	},
+};
+
+use crate::{
+	order::{
+		BitOrder,
+		Lsb0,
+	},
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+mod iter;
+mod tests;
+mod traits;
+
+pub use self::iter::{
+	Difference,
+	Intersection,
+	Iter,
+	SymmetricDifference,
+	Union,
+};
+
+#[doc = include_str!("../doc/set/BitSet.md")]
+pub struct BitSet<T = usize, O = Lsb0>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// The membership bit of each integer, up to the largest that has been
+	/// inserted. Integers past the end are not in the set.
+	bits: BitVec<T, O>,
+}
+
+/// Constructors and conversions.
+impl<T, O> BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// Creates an empty set.
+	#[inline]
+	pub fn new() -> Self {
+		Self {
+			bits: BitVec::new(),
+		}
+	}
+
+	/// Creates an empty set that can hold the integers `0 .. capacity`
+	/// without reallocating.
+	#[inline]
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			bits: BitVec::with_capacity(capacity),
+		}
+	}
+
+	/// Creates a set of the indices of the `1` bits in a bit-vector.
+	#[inline]
+	pub fn from_bitvec(bits: BitVec<T, O>) -> Self {
+		Self { bits }
+	}
+
+	/// Converts the set into a bit-vector whose `1` bits are its members.
+	///
+	/// The bit-vector may have any number of trailing `0` bits.
+	#[inline]
+	pub fn into_bitvec(self) -> BitVec<T, O> {
+		self.bits
+	}
+
+	/// Views the set as a bit-slice whose `1` bits are its members.
+	///
+	/// The bit-slice may have any number of trailing `0` bits.
+	#[inline]
+	pub fn as_bitslice(&self) -> &BitSlice<T, O> {
+		self.bits.as_bitslice()
+	}
+
+	/// Gets the number of integers that the set can hold without
+	/// reallocating.
+	#[inline]
+	pub fn capacity(&self) -> usize {
+		self.bits.capacity()
+	}
+
+	/// Releases memory that is not needed to hold the current members.
+	#[inline]
+	pub fn shrink_to_fit(&mut self) {
+		self.trim();
+		self.bits.shrink_to_fit();
+	}
+}
+
+/// Element access.
+impl<T, O> BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// Counts the members of the set.
+	///
+	/// This is a population count of the underlying bits, and so takes time
+	/// proportional to the largest member rather than being stored.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.bits.count_ones()
+	}
+
+	/// Tests if the set has no members.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.bits.not_any()
+	}
+
+	/// Tests if an integer is in the set.
+	#[inline]
+	pub fn contains(&self, value: usize) -> bool {
+		self.bits.get(value).map(|bit| *bit).unwrap_or(false)
+	}
+
+	/// Adds an integer to the set, growing it if necessary.
+	///
+	/// ## Returns
+	///
+	/// `true` if the integer was not already in the set.
+	///
+	/// ## Panics
+	///
+	/// This panics if `value` is not less than [`BitSlice::MAX_BITS`].
+	///
+	/// [`BitSlice::MAX_BITS`]: crate::slice::BitSlice::MAX_BITS
+	#[inline]
+	pub fn insert(&mut self, value: usize) -> bool {
+		if value >= self.bits.len() {
+			self.bits.resize(value + 1, false);
+		}
+		!self.bits.replace(value, true)
+	}
+
+	/// Removes an integer from the set.
+	///
+	/// The set keeps its storage; use [`.shrink_to_fit()`] to release it.
+	///
+	/// ## Returns
+	///
+	/// `true` if the integer was in the set.
+	///
+	/// [`.shrink_to_fit()`]: Self::shrink_to_fit
+	#[inline]
+	pub fn remove(&mut self, value: usize) -> bool {
+		value < self.bits.len() && self.bits.replace(value, false)
+	}
+
+	/// Removes all members from the set.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.bits.clear();
+	}
+
+	/// Gets the smallest member of the set.
+	#[inline]
+	pub fn first(&self) -> Option<usize> {
+		self.bits.first_one()
+	}
+
+	/// Gets the largest member of the set.
+	#[inline]
+	pub fn last(&self) -> Option<usize> {
+		self.bits.last_one()
+	}
+
+	/// Removes and returns the smallest member of the set.
+	#[inline]
+	pub fn pop_first(&mut self) -> Option<usize> {
+		let value = self.first()?;
+		self.bits.set(value, false);
+		Some(value)
+	}
+
+	/// Removes and returns the largest member of the set.
+	#[inline]
+	pub fn pop_last(&mut self) -> Option<usize> {
+		let value = self.last()?;
+		self.bits.truncate(value);
+		Some(value)
+	}
+
+	/// Keeps only the members for which `func` returns `true`.
+	#[inline]
+	pub fn retain<F>(&mut self, mut func: F)
+	where F: FnMut(usize) -> bool {
+		let mut start = 0;
+		while let Some(idx) = self.bits[start ..].first_one() {
+			let value = start + idx;
+			if !func(value) {
+				self.bits.set(value, false);
+			}
+			start = value + 1;
+		}
+	}
+}
+
+/// Iteration.
+impl<T, O> BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// Iterates over the members of the set, in ascending order.
+	#[inline]
+	pub fn iter(&self) -> Iter<'_, T, O> {
+		Iter::new(&self.bits, 0)
+	}
+
+	/// Iterates over the members of the set that lie within a range, in
+	/// ascending order.
+	///
+	/// Unlike `BTreeSet::range`, this accepts ranges that extend past the
+	/// largest member, or that are empty.
+	#[inline]
+	pub fn range<R>(&self, range: R) -> Iter<'_, T, O>
+	where R: RangeBounds<usize> {
+		let len = self.bits.len();
+		let start = match range.start_bound() {
+			Bound::Included(&start) => start,
+			Bound::Excluded(&start) => start.saturating_add(1),
+			Bound::Unbounded => 0,
+		};
+		let end = match range.end_bound() {
+			Bound::Included(&end) => end.saturating_add(1),
+			Bound::Excluded(&end) => end,
+			Bound::Unbounded => len,
+		};
+		let end = cmp::min(end, len);
+		let start = cmp::min(start, end);
+		Iter::new(&self.bits[start .. end], start)
+	}
+
+	/// Iterates over the integers that are in `self`, `other`, or both, in
+	/// ascending order.
+	#[inline]
+	pub fn union<'a>(&'a self, other: &'a Self) -> Union<'a, T, O> {
+		Union::new(self.iter(), other.iter())
+	}
+
+	/// Iterates over the integers that are in both `self` and `other`, in
+	/// ascending order.
+	#[inline]
+	pub fn intersection<'a>(
+		&'a self,
+		other: &'a Self,
+	) -> Intersection<'a, T, O> {
+		//  Only the shorter set's members need to be visited.
+		let (short, long) = if self.bits.len() <= other.bits.len() {
+			(self, other)
+		}
+		else {
+			(other, self)
+		};
+		Intersection::new(short.iter(), long)
+	}
+
+	/// Iterates over the integers that are in `self` but not in `other`, in
+	/// ascending order.
+	#[inline]
+	pub fn difference<'a>(&'a self, other: &'a Self) -> Difference<'a, T, O> {
+		Difference::new(self.iter(), other)
+	}
+
+	/// Iterates over the integers that are in exactly one of `self` and
+	/// `other`, in ascending order.
+	#[inline]
+	pub fn symmetric_difference<'a>(
+		&'a self,
+		other: &'a Self,
+	) -> SymmetricDifference<'a, T, O> {
+		SymmetricDifference::new(self.iter(), other.iter())
+	}
+}
+
+/// Set comparisons.
+impl<T, O> BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// Tests if every member of `self` is also in `other`.
+	#[inline]
+	pub fn is_subset(&self, other: &Self) -> bool {
+		let (common, rest) = self.split_common(other);
+		rest.not_any()
+			&& common
+				.iter_ones()
+				.all(|idx| unsafe { *other.bits.get_unchecked(idx) })
+	}
+
+	/// Tests if every member of `other` is also in `self`.
+	#[inline]
+	pub fn is_superset(&self, other: &Self) -> bool {
+		other.is_subset(self)
+	}
+
+	/// Tests if `self` and `other` have no members in common.
+	#[inline]
+	pub fn is_disjoint(&self, other: &Self) -> bool {
+		let (common, _) = self.split_common(other);
+		common
+			.iter_ones()
+			.all(|idx| !unsafe { *other.bits.get_unchecked(idx) })
+	}
+
+	/// Splits `self` into the bits that `other` also has, and the rest.
+	fn split_common(&self, other: &Self) -> (&BitSlice<T, O>, &BitSlice<T, O>) {
+		self.bits
+			.split_at(cmp::min(self.bits.len(), other.bits.len()))
+	}
+
+	/// Removes the trailing `0` bits, which do not affect membership.
+	fn trim(&mut self) {
+		let len = self.bits.last_one().map(|idx| idx + 1).unwrap_or(0);
+		self.bits.truncate(len);
+	}
+}
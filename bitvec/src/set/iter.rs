@@ -0,0 +1,372 @@
+//! Iteration over the members of sets, and over combinations of two sets.
+
+use core::{
+	cmp::Ordering,
+	iter::{
+		FusedIterator,
+		Peekable,
+	},
+};
+
+use super::BitSet;
+use crate::{
+	order::BitOrder,
+	slice::{
+		BitSlice,
+		IterOnes,
+	},
+	store::BitStore,
+};
+
+#[doc = include_str!("../../doc/set/Iter.md")]
+#[derive(Clone, Debug)]
+pub struct Iter<'a, T, O>
+where
+	T: 'a + BitStore,
+	O: BitOrder,
+{
+	/// The `1` bits of the region being iterated.
+	inner: IterOnes<'a, T, O>,
+	/// The integer that the first bit of the region represents.
+	base:  usize,
+}
+
+impl<'a, T, O> Iter<'a, T, O>
+where
+	T: 'a + BitStore,
+	O: BitOrder,
+{
+	/// Iterates over the `1` bits in a region of a set, whose first bit
+	/// represents `base`.
+	#[inline]
+	pub(super) fn new(bits: &'a BitSlice<T, O>, base: usize) -> Self {
+		Self {
+			inner: bits.iter_ones(),
+			base,
+		}
+	}
+}
+
+impl<T, O> Iterator for Iter<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	type Item = usize;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.inner.next().map(|idx| self.base + idx)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.inner.size_hint()
+	}
+
+	#[inline]
+	fn nth(&mut self, n: usize) -> Option<Self::Item> {
+		self.inner.nth(n).map(|idx| self.base + idx)
+	}
+
+	#[inline]
+	fn last(mut self) -> Option<Self::Item> {
+		self.next_back()
+	}
+}
+
+impl<T, O> DoubleEndedIterator for Iter<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.inner.next_back().map(|idx| self.base + idx)
+	}
+}
+
+impl<T, O> ExactSizeIterator for Iter<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn len(&self) -> usize {
+		self.inner.len()
+	}
+}
+
+impl<T, O> FusedIterator for Iter<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+}
+
+#[doc = include_str!("../../doc/set/Union.md")]
+#[derive(Clone, Debug)]
+pub struct Union<'a, T, O>
+where
+	T: 'a + BitStore,
+	O: BitOrder,
+{
+	/// The members of both sets, merged in ascending order.
+	inner: Merge<'a, T, O>,
+}
+
+impl<'a, T, O> Union<'a, T, O>
+where
+	T: 'a + BitStore,
+	O: BitOrder,
+{
+	/// Merges the members of two sets.
+	#[inline]
+	pub(super) fn new(one: Iter<'a, T, O>, two: Iter<'a, T, O>) -> Self {
+		Self {
+			inner: Merge::new(one, two),
+		}
+	}
+}
+
+impl<T, O> Iterator for Union<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	type Item = usize;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.inner.next().map(|(value, _)| value)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let (one, two) = self.inner.lens();
+		(one.max(two), one.checked_add(two))
+	}
+}
+
+impl<T, O> FusedIterator for Union<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+}
+
+#[doc = include_str!("../../doc/set/SymmetricDifference.md")]
+#[derive(Clone, Debug)]
+pub struct SymmetricDifference<'a, T, O>
+where
+	T: 'a + BitStore,
+	O: BitOrder,
+{
+	/// The members of both sets, merged in ascending order.
+	inner: Merge<'a, T, O>,
+}
+
+impl<'a, T, O> SymmetricDifference<'a, T, O>
+where
+	T: 'a + BitStore,
+	O: BitOrder,
+{
+	/// Merges the members of two sets.
+	#[inline]
+	pub(super) fn new(one: Iter<'a, T, O>, two: Iter<'a, T, O>) -> Self {
+		Self {
+			inner: Merge::new(one, two),
+		}
+	}
+}
+
+impl<T, O> Iterator for SymmetricDifference<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	type Item = usize;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.inner
+			.by_ref()
+			.find(|&(_, shared)| !shared)
+			.map(|(value, _)| value)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let (one, two) = self.inner.lens();
+		(0, one.checked_add(two))
+	}
+}
+
+impl<T, O> FusedIterator for SymmetricDifference<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+}
+
+#[doc = include_str!("../../doc/set/Intersection.md")]
+#[derive(Clone, Debug)]
+pub struct Intersection<'a, T, O>
+where
+	T: 'a + BitStore,
+	O: BitOrder,
+{
+	/// The members of one set.
+	inner: Iter<'a, T, O>,
+	/// The set that each member is tested against.
+	other: &'a BitSet<T, O>,
+}
+
+impl<'a, T, O> Intersection<'a, T, O>
+where
+	T: 'a + BitStore,
+	O: BitOrder,
+{
+	/// Filters the members of one set by their presence in another.
+	#[inline]
+	pub(super) fn new(inner: Iter<'a, T, O>, other: &'a BitSet<T, O>) -> Self {
+		Self { inner, other }
+	}
+}
+
+impl<T, O> Iterator for Intersection<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	type Item = usize;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let other = self.other;
+		self.inner.find(|&value| other.contains(value))
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, Some(self.inner.len()))
+	}
+}
+
+impl<T, O> FusedIterator for Intersection<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+}
+
+#[doc = include_str!("../../doc/set/Difference.md")]
+#[derive(Clone, Debug)]
+pub struct Difference<'a, T, O>
+where
+	T: 'a + BitStore,
+	O: BitOrder,
+{
+	/// The members of the set being subtracted from.
+	inner: Iter<'a, T, O>,
+	/// The set being subtracted.
+	other: &'a BitSet<T, O>,
+}
+
+impl<'a, T, O> Difference<'a, T, O>
+where
+	T: 'a + BitStore,
+	O: BitOrder,
+{
+	/// Filters the members of one set by their absence from another.
+	#[inline]
+	pub(super) fn new(inner: Iter<'a, T, O>, other: &'a BitSet<T, O>) -> Self {
+		Self { inner, other }
+	}
+}
+
+impl<T, O> Iterator for Difference<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	type Item = usize;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let other = self.other;
+		self.inner.find(|&value| !other.contains(value))
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, Some(self.inner.len()))
+	}
+}
+
+impl<T, O> FusedIterator for Difference<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+}
+
+/// Merges the members of two sets in ascending order.
+///
+/// Each item is a member, and whether it is in both sets.
+#[derive(Clone, Debug)]
+struct Merge<'a, T, O>
+where
+	T: 'a + BitStore,
+	O: BitOrder,
+{
+	/// The members of the first set.
+	one: Peekable<Iter<'a, T, O>>,
+	/// The members of the second set.
+	two: Peekable<Iter<'a, T, O>>,
+}
+
+impl<'a, T, O> Merge<'a, T, O>
+where
+	T: 'a + BitStore,
+	O: BitOrder,
+{
+	/// Begins merging two sets.
+	fn new(one: Iter<'a, T, O>, two: Iter<'a, T, O>) -> Self {
+		Self {
+			one: one.peekable(),
+			two: two.peekable(),
+		}
+	}
+
+	/// Counts the members that remain in each set.
+	fn lens(&self) -> (usize, usize) {
+		(self.one.len(), self.two.len())
+	}
+}
+
+impl<T, O> Iterator for Merge<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	type Item = (usize, bool);
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let order = match (self.one.peek(), self.two.peek()) {
+			(Some(one), Some(two)) => one.cmp(two),
+			(Some(_), None) => Ordering::Less,
+			(None, Some(_)) => Ordering::Greater,
+			(None, None) => return None,
+		};
+		match order {
+			Ordering::Less => self.one.next().map(|value| (value, false)),
+			Ordering::Greater => self.two.next().map(|value| (value, false)),
+			Ordering::Equal => {
+				self.two.next();
+				self.one.next().map(|value| (value, true))
+			},
+		}
+	}
+}
@@ -0,0 +1,114 @@
+//! Unit tests for bit-sets.
+
+#![cfg(test)]
+
+use alloc::vec::Vec;
+use core::hash::{
+	Hash,
+	Hasher,
+};
+use std::collections::{
+	BTreeSet,
+	hash_map::DefaultHasher,
+};
+
+use super::*;
+use crate::order::Msb0;
+
+#[test]
+fn membership() {
+	let mut set = BitSet::<u8, Msb0>::new();
+	assert!(set.is_empty());
+	assert!(!set.contains(100));
+	assert!(!set.remove(100));
+
+	assert!(set.insert(100));
+	assert!(set.insert(3));
+	assert!(!set.insert(100));
+	assert_eq!(set.len(), 2);
+	assert_eq!(set.as_bitslice().len(), 101);
+	assert!(set.contains(3) && set.contains(100) && !set.contains(4));
+	assert_eq!((set.first(), set.last()), (Some(3), Some(100)));
+
+	assert!(set.remove(100));
+	assert_eq!(set.last(), Some(3));
+	set.shrink_to_fit();
+	assert_eq!(set.as_bitslice().len(), 4);
+
+	set.extend([9, 1, 5]);
+	set.retain(|value| value != 5);
+	assert_eq!(set.iter().collect::<Vec<_>>(), [1, 3, 9]);
+	assert_eq!(set.pop_last(), Some(9));
+	assert_eq!(set.pop_first(), Some(1));
+	assert_eq!(set.iter().collect::<Vec<_>>(), [3]);
+	set.clear();
+	assert_eq!(set.pop_first(), None);
+}
+
+#[test]
+fn iteration() {
+	let set = [2, 3, 5, 7, 11, 13, 17, 19, 23]
+		.iter()
+		.collect::<BitSet<u16, Lsb0>>();
+	let iter = set.iter();
+	assert_eq!(iter.len(), 9);
+	assert_eq!(iter.clone().last(), Some(23));
+	assert_eq!(iter.rev().take(2).collect::<Vec<_>>(), [23, 19]);
+	assert_eq!(set.iter().nth(4), Some(11));
+
+	assert_eq!(set.range(5 .. 13).collect::<Vec<_>>(), [5, 7, 11]);
+	assert_eq!(set.range(5 ..= 13).collect::<Vec<_>>(), [5, 7, 11, 13]);
+	assert_eq!(set.range(20 ..).collect::<Vec<_>>(), [23]);
+	assert_eq!(set.range(.. 4).rev().collect::<Vec<_>>(), [3, 2]);
+	assert_eq!(set.range(100 .. 200).count(), 0);
+	let bounds = (Bound::Excluded(13), Bound::Included(19));
+	assert_eq!(set.range(bounds).collect::<Vec<_>>(), [17, 19]);
+	assert_eq!((&set).into_iter().sum::<usize>(), 100);
+}
+
+#[test]
+fn combinations() {
+	let one = [0usize, 2, 4, 6, 8, 60, 70].iter().collect::<BitSet>();
+	let two = [1usize, 2, 3, 4, 64].iter().collect::<BitSet>();
+	let (a, b) = (
+		one.iter().collect::<BTreeSet<_>>(),
+		two.iter().collect::<BTreeSet<_>>(),
+	);
+
+	assert!(one.union(&two).eq(a.union(&b).copied()));
+	assert!(one.intersection(&two).eq(a.intersection(&b).copied()));
+	assert!(two.intersection(&one).eq(b.intersection(&a).copied()));
+	assert!(one.difference(&two).eq(a.difference(&b).copied()));
+	assert!(two.difference(&one).eq(b.difference(&a).copied()));
+	assert!(
+		one.symmetric_difference(&two)
+			.eq(a.symmetric_difference(&b).copied())
+	);
+
+	assert!(!one.is_disjoint(&two));
+	let evens = (0 .. 10).step_by(2).collect::<BitSet>();
+	assert!(evens.is_subset(&one));
+	assert!(one.is_superset(&evens));
+	assert!(!one.is_subset(&evens));
+	assert!(evens.is_disjoint(&[1usize, 3, 99].iter().collect()));
+	assert!(BitSet::<usize>::new().is_subset(&evens));
+}
+
+#[test]
+fn equality() {
+	let mut one = BitSet::<u32, Lsb0>::from_bitvec(bitvec![u32, Lsb0; 0, 1, 0]);
+	let two = [1usize].iter().collect::<BitSet<u32, Lsb0>>();
+	assert_eq!(one, two);
+	let hash = |set: &BitSet<u32, Lsb0>| {
+		let mut hasher = DefaultHasher::new();
+		set.hash(&mut hasher);
+		hasher.finish()
+	};
+	assert_eq!(hash(&one), hash(&two));
+
+	one.insert(40);
+	assert_ne!(one, two);
+	one.remove(40);
+	assert_eq!(one, two);
+	assert_eq!(BitVec::from(two), bits![u32, Lsb0; 0, 1]);
+}
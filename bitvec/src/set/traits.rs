@@ -0,0 +1,193 @@
+//! General trait implementations for bit-sets.
+
+use core::{
+	cmp,
+	fmt::{
+		self,
+		Debug,
+		Formatter,
+	},
+	hash::{
+		Hash,
+		Hasher,
+	},
+	iter::FromIterator,
+};
+
+use super::{
+	BitSet,
+	Iter,
+};
+use crate::{
+	order::BitOrder,
+	store::BitStore,
+	vec::BitVec,
+};
+
+impl<T, O> Clone for BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn clone(&self) -> Self {
+		Self::from_bitvec(self.bits.clone())
+	}
+}
+
+impl<T, O> Default for BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T, O> Eq for BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+}
+
+/// Sets are equal when they have the same members, regardless of how many
+/// trailing `0` bits each one stores.
+impl<T, O> PartialEq for BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn eq(&self, other: &Self) -> bool {
+		let (this, that) = (self.as_bitslice(), other.as_bitslice());
+		let len = cmp::min(this.len(), that.len());
+		this[.. len] == that[.. len]
+			&& this[len ..].not_any()
+			&& that[len ..].not_any()
+	}
+}
+
+/// Hashes the members of the set, so that equal sets hash equally.
+impl<T, O> Hash for BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn hash<H>(&self, hasher: &mut H)
+	where H: Hasher {
+		hasher.write_usize(self.len());
+		for value in self {
+			hasher.write_usize(value);
+		}
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T, O> Debug for BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_set().entries(self.iter()).finish()
+	}
+}
+
+impl<T, O> Extend<usize> for BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn extend<I>(&mut self, iter: I)
+	where I: IntoIterator<Item = usize> {
+		for value in iter {
+			self.insert(value);
+		}
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<'a, T, O> Extend<&'a usize> for BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn extend<I>(&mut self, iter: I)
+	where I: IntoIterator<Item = &'a usize> {
+		self.extend(iter.into_iter().copied());
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T, O> FromIterator<usize> for BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn from_iter<I>(iter: I) -> Self
+	where I: IntoIterator<Item = usize> {
+		let mut out = Self::new();
+		out.extend(iter);
+		out
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<'a, T, O> FromIterator<&'a usize> for BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn from_iter<I>(iter: I) -> Self
+	where I: IntoIterator<Item = &'a usize> {
+		iter.into_iter().copied().collect()
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<'a, T, O> IntoIterator for &'a BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	type IntoIter = Iter<'a, T, O>;
+	type Item = usize;
+
+	#[inline]
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T, O> From<BitVec<T, O>> for BitSet<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn from(bits: BitVec<T, O>) -> Self {
+		Self::from_bitvec(bits)
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T, O> From<BitSet<T, O>> for BitVec<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn from(set: BitSet<T, O>) -> Self {
+		set.into_bitvec()
+	}
+}
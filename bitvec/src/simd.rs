@@ -0,0 +1,249 @@
+#![doc = include_str!("../doc/simd.md")]
+
+use core::{
+	mem,
+	slice,
+};
+
+use funty::Integral;
+
+use crate::{
+	devel as dvl,
+	store::BitStore,
+};
+
+mod scalar;
+mod tests;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod x86;
+
+/// The Boolean operations that can be applied across whole memory elements.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum BitOp {
+	/// `a & b`
+	And,
+	/// `a | b`
+	Or,
+	/// `a ^ b`
+	Xor,
+	/// `a & !b`
+	AndNot,
+}
+
+impl BitOp {
+	/// Applies the operation to a pair of memory elements.
+	#[inline]
+	pub(crate) fn apply<M>(self, a: M, b: M) -> M
+	where M: Integral {
+		match self {
+			Self::And => a & b,
+			Self::Or => a | b,
+			Self::Xor => a ^ b,
+			Self::AndNot => a & !b,
+		}
+	}
+}
+
+/// Counts the bits set to `1` in a run of whole memory elements.
+#[inline]
+pub(crate) fn count_ones<T>(elts: &[T]) -> usize
+where T: BitStore {
+	match as_bytes(elts) {
+		Some(bytes) => count_ones_bytes(bytes),
+		None => elts
+			.iter()
+			.map(BitStore::load_value)
+			.map(|elem| elem.count_ones() as usize)
+			.sum(),
+	}
+}
+
+/// Counts how many elements at the front of a run are entirely filled with
+/// `fill`.
+///
+/// Searches for the first `1` skip over elements filled with `0`, and vice
+/// versa, so this lets them jump directly to the element that they need to
+/// inspect.
+#[inline]
+pub(crate) fn leading_fill<T>(elts: &[T], fill: bool) -> usize
+where T: BitStore {
+	match as_bytes(elts) {
+		Some(bytes) => position_not_bytes(bytes, fill_byte(fill))
+			.map_or(elts.len(), |idx| idx / mem::size_of::<T>()),
+		None => {
+			let fill = fill_elem::<T>(fill);
+			elts.iter()
+				.position(|elem| elem.load_value() != fill)
+				.unwrap_or(elts.len())
+		},
+	}
+}
+
+/// Counts how many elements at the back of a run are entirely filled with
+/// `fill`.
+#[inline]
+pub(crate) fn trailing_fill<T>(elts: &[T], fill: bool) -> usize
+where T: BitStore {
+	let found = match as_bytes(elts) {
+		Some(bytes) => rposition_not_bytes(bytes, fill_byte(fill))
+			.map(|idx| idx / mem::size_of::<T>()),
+		None => {
+			let fill = fill_elem::<T>(fill);
+			elts.iter().rposition(|elem| elem.load_value() != fill)
+		},
+	};
+	found.map_or(elts.len(), |idx| elts.len() - idx - 1)
+}
+
+/// Tests whether two runs of whole memory elements hold the same values.
+#[inline]
+pub(crate) fn eq<T, U>(a: &[T], b: &[U]) -> bool
+where
+	T: BitStore,
+	U: BitStore<Mem = T::Mem>,
+{
+	match (as_bytes(a), as_bytes(b)) {
+		//  Slice equality on bytes is `memcmp`, which the platform already
+		//  vectorizes.
+		(Some(a), Some(b)) => a == b,
+		_ => {
+			a.len() == b.len()
+				&& a.iter()
+					.map(BitStore::load_value)
+					.eq(b.iter().map(BitStore::load_value))
+		},
+	}
+}
+
+/// Merges a run of whole memory elements into another with a Boolean
+/// operation.
+///
+/// Both runs must have the same length.
+#[inline]
+pub(crate) fn bitop_assign<T, U>(dst: &mut [T], src: &[U], op: BitOp)
+where
+	T: BitStore,
+	U: BitStore<Mem = T::Mem>,
+{
+	debug_assert_eq!(dst.len(), src.len(), "runs must have equal lengths");
+	if let Some(src) = as_bytes(src) {
+		if let Some(dst) = as_bytes_mut(dst) {
+			return bitop_assign_bytes(dst, src, op);
+		}
+	}
+	for (this, that) in dst.iter_mut().zip(src) {
+		this.store_value(op.apply(this.load_value(), that.load_value()));
+	}
+}
+
+/// Views a run of elements as raw bytes, if they are plain integers.
+///
+/// Elements that use `Cell` or atomic access must be read through their own
+/// accessors, and are not viewed.
+#[inline]
+fn as_bytes<T>(elts: &[T]) -> Option<&[u8]>
+where T: BitStore {
+	if !dvl::is_unsigned::<T>() {
+		return None;
+	}
+	Some(unsafe {
+		slice::from_raw_parts(elts.as_ptr().cast::<u8>(), mem::size_of_val(elts))
+	})
+}
+
+/// Views a run of elements as raw mutable bytes, if they are plain integers.
+#[inline]
+fn as_bytes_mut<T>(elts: &mut [T]) -> Option<&mut [u8]>
+where T: BitStore {
+	if !dvl::is_unsigned::<T>() {
+		return None;
+	}
+	Some(unsafe {
+		slice::from_raw_parts_mut(
+			elts.as_mut_ptr().cast::<u8>(),
+			mem::size_of_val(elts),
+		)
+	})
+}
+
+/// Produces a byte whose bits are all `fill`.
+#[inline]
+fn fill_byte(fill: bool) -> u8 {
+	if fill { !0 } else { 0 }
+}
+
+/// Produces a memory element whose bits are all `fill`.
+#[inline]
+fn fill_elem<T>(fill: bool) -> T::Mem
+where T: BitStore {
+	if fill {
+		!<T::Mem as Integral>::ZERO
+	}
+	else {
+		<T::Mem as Integral>::ZERO
+	}
+}
+
+/// Counts the bits set to `1` in a byte buffer.
+#[inline]
+fn count_ones_bytes(bytes: &[u8]) -> usize {
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	{
+		if x86::has_avx2() {
+			return unsafe { x86::count_ones_avx2(bytes) };
+		}
+		x86::count_ones_sse2(bytes)
+	}
+	#[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+	{
+		scalar::count_ones(bytes)
+	}
+}
+
+/// Finds the first byte in a buffer that is not `fill`.
+#[inline]
+fn position_not_bytes(bytes: &[u8], fill: u8) -> Option<usize> {
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	{
+		if x86::has_avx2() {
+			return unsafe { x86::position_not_avx2(bytes, fill) };
+		}
+		x86::position_not_sse2(bytes, fill)
+	}
+	#[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+	{
+		scalar::position_not(bytes, fill)
+	}
+}
+
+/// Finds the last byte in a buffer that is not `fill`.
+#[inline]
+fn rposition_not_bytes(bytes: &[u8], fill: u8) -> Option<usize> {
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	{
+		if x86::has_avx2() {
+			return unsafe { x86::rposition_not_avx2(bytes, fill) };
+		}
+		x86::rposition_not_sse2(bytes, fill)
+	}
+	#[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+	{
+		scalar::rposition_not(bytes, fill)
+	}
+}
+
+/// Merges a byte buffer into another with a Boolean operation.
+#[inline]
+fn bitop_assign_bytes(dst: &mut [u8], src: &[u8], op: BitOp) {
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	{
+		if x86::has_avx2() {
+			return unsafe { x86::bitop_assign_avx2(dst, src, op) };
+		}
+		x86::bitop_assign_sse2(dst, src, op)
+	}
+	#[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+	{
+		scalar::bitop_assign(dst, src, op)
+	}
+}
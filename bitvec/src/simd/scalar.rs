@@ -0,0 +1,72 @@
+//! Portable implementations of the bulk memory operations.
+//!
+//! These work on eight bytes at a time where they can, and are used on every
+//! target without a vectorized implementation, as well as for the leftover
+//! bytes that do not fill a vector register.
+
+use super::BitOp;
+
+/// The number of bytes processed together.
+const WORD: usize = 8;
+
+/// Counts the bits set to `1` in a byte buffer.
+pub(super) fn count_ones(bytes: &[u8]) -> usize {
+	let words = bytes.chunks_exact(WORD);
+	let rest = words.remainder();
+	words
+		.map(|word| load(word).count_ones() as usize)
+		.sum::<usize>()
+		+ rest
+			.iter()
+			.map(|byte| byte.count_ones() as usize)
+			.sum::<usize>()
+}
+
+/// Finds the first byte in a buffer that is not `fill`.
+pub(super) fn position_not(bytes: &[u8], fill: u8) -> Option<usize> {
+	let pattern = u64::from_ne_bytes([fill; WORD]);
+	let words = bytes.chunks_exact(WORD);
+	let rest = words.remainder();
+	for (idx, word) in words.enumerate() {
+		if load(word) != pattern {
+			let start = idx * WORD;
+			return word.iter().position(|&b| b != fill).map(|i| start + i);
+		}
+	}
+	let start = bytes.len() - rest.len();
+	rest.iter().position(|&b| b != fill).map(|i| start + i)
+}
+
+/// Finds the last byte in a buffer that is not `fill`.
+pub(super) fn rposition_not(bytes: &[u8], fill: u8) -> Option<usize> {
+	let pattern = u64::from_ne_bytes([fill; WORD]);
+	let words = bytes.rchunks_exact(WORD);
+	let rest = words.remainder();
+	for (idx, word) in words.enumerate() {
+		if load(word) != pattern {
+			let start = bytes.len() - (idx + 1) * WORD;
+			return word.iter().rposition(|&b| b != fill).map(|i| start + i);
+		}
+	}
+	rest.iter().rposition(|&b| b != fill)
+}
+
+/// Merges a byte buffer into another with a Boolean operation.
+pub(super) fn bitop_assign(dst: &mut [u8], src: &[u8], op: BitOp) {
+	//  Matching outside the loops leaves each one simple enough for the
+	//  compiler to vectorize on its own.
+	let pairs = dst.iter_mut().zip(src);
+	match op {
+		BitOp::And => pairs.for_each(|(a, b)| *a &= b),
+		BitOp::Or => pairs.for_each(|(a, b)| *a |= b),
+		BitOp::Xor => pairs.for_each(|(a, b)| *a ^= b),
+		BitOp::AndNot => pairs.for_each(|(a, b)| *a &= !b),
+	}
+}
+
+/// Reads an eight-byte chunk as an integer.
+fn load(word: &[u8]) -> u64 {
+	let mut bytes = [0; WORD];
+	bytes.copy_from_slice(word);
+	u64::from_ne_bytes(bytes)
+}
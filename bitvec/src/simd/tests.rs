@@ -0,0 +1,138 @@
+//! Unit tests for the bulk memory operations.
+
+#![cfg(test)]
+
+use rand::random;
+
+use super::*;
+
+/// Produces a buffer of random bytes, long enough to cover several vector
+/// registers as well as a leftover tail.
+fn buffer() -> [u8; 160] {
+	let mut out = [0; 160];
+	for chunk in out.chunks_exact_mut(32) {
+		chunk.copy_from_slice(&random::<[u8; 32]>());
+	}
+	out
+}
+
+#[test]
+fn scalar() {
+	let data = buffer();
+	for start in 0 .. 9 {
+		for end in (start .. data.len()).step_by(7) {
+			let bytes = &data[start .. end];
+			assert_eq!(
+				scalar::count_ones(bytes),
+				bytes.iter().map(|b| b.count_ones() as usize).sum::<usize>(),
+			);
+			for &fill in &[0, !0, bytes.first().copied().unwrap_or(0)] {
+				assert_eq!(
+					scalar::position_not(bytes, fill),
+					bytes.iter().position(|&b| b != fill),
+				);
+				assert_eq!(
+					scalar::rposition_not(bytes, fill),
+					bytes.iter().rposition(|&b| b != fill),
+				);
+			}
+		}
+	}
+
+	let mut zeros = [0u8; 45];
+	zeros[37] = 4;
+	assert_eq!(scalar::position_not(&zeros, 0), Some(37));
+	assert_eq!(scalar::rposition_not(&zeros, 0), Some(37));
+	assert_eq!(scalar::position_not(&zeros[.. 37], 0), None);
+}
+
+#[test]
+fn elements() {
+	let mut data = [0u32; 12];
+	data[5] = 1 << 20;
+	data[9] = !0;
+	assert_eq!(count_ones(&data[..]), 33);
+	assert_eq!(leading_fill(&data[..], false), 5);
+	assert_eq!(trailing_fill(&data[..], false), 2);
+	assert_eq!(leading_fill(&data[9 .. 10], true), 1);
+	assert_eq!(trailing_fill(&data[.. 9], true), 0);
+	assert_eq!(leading_fill(&data[.. 5], false), 5);
+
+	//  Shared elements are read one at a time.
+	let cells = data.map(core::cell::Cell::new);
+	assert_eq!(count_ones(&cells[..]), 33);
+	assert_eq!(leading_fill(&cells[..], false), 5);
+	assert_eq!(trailing_fill(&cells[..], false), 2);
+	assert!(eq(&cells[..], &data[..]));
+	assert!(!eq(&cells[1 ..], &data[.. 11]));
+
+	let mut dst = data;
+	bitop_assign(&mut dst[..], &cells[..], BitOp::Xor);
+	assert_eq!(dst, [0; 12]);
+	bitop_assign(&mut dst[..], &data[..], BitOp::Or);
+	assert_eq!(dst, data);
+	bitop_assign(&mut dst[..], &data[..], BitOp::AndNot);
+	assert_eq!(dst, [0; 12]);
+}
+
+#[test]
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+fn x86() {
+	let data = buffer();
+	let other = buffer();
+	let avx2 = x86::has_avx2();
+	for start in 0 .. 9 {
+		for end in (start .. data.len()).step_by(5) {
+			let bytes = &data[start .. end];
+			let ones = scalar::count_ones(bytes);
+			assert_eq!(x86::count_ones_sse2(bytes), ones);
+			if avx2 {
+				assert_eq!(unsafe { x86::count_ones_avx2(bytes) }, ones);
+			}
+
+			for &fill in &[0, !0, bytes.first().copied().unwrap_or(0)] {
+				let first = scalar::position_not(bytes, fill);
+				let last = scalar::rposition_not(bytes, fill);
+				assert_eq!(x86::position_not_sse2(bytes, fill), first);
+				assert_eq!(x86::rposition_not_sse2(bytes, fill), last);
+				if avx2 {
+					unsafe {
+						assert_eq!(x86::position_not_avx2(bytes, fill), first);
+						assert_eq!(x86::rposition_not_avx2(bytes, fill), last);
+					}
+				}
+			}
+
+			let src = &other[start .. end];
+			for &op in &[BitOp::And, BitOp::Or, BitOp::Xor, BitOp::AndNot] {
+				let mut expected = data;
+				scalar::bitop_assign(&mut expected[start .. end], src, op);
+				let mut actual = data;
+				x86::bitop_assign_sse2(&mut actual[start .. end], src, op);
+				assert_eq!(actual, expected);
+				if avx2 {
+					let mut actual = data;
+					unsafe {
+						x86::bitop_assign_avx2(
+							&mut actual[start .. end],
+							src,
+							op,
+						);
+					}
+					assert_eq!(actual, expected);
+				}
+			}
+		}
+	}
+
+	let mut ones = [!0u8; 100];
+	ones[70] = 0xEF;
+	assert_eq!(x86::position_not_sse2(&ones, !0), Some(70));
+	assert_eq!(x86::rposition_not_sse2(&ones, !0), Some(70));
+	if avx2 {
+		unsafe {
+			assert_eq!(x86::position_not_avx2(&ones, !0), Some(70));
+			assert_eq!(x86::rposition_not_avx2(&ones, !0), Some(70));
+		}
+	}
+}
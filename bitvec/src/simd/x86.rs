@@ -0,0 +1,227 @@
+//! Vectorized implementations of the bulk memory operations for `x86_64`.
+//!
+//! SSE2 is part of the `x86_64` baseline, so its routines are always safe to
+//! call. The AVX2 routines must only be called after [`has_avx2`] reports that
+//! the processor supports them.
+
+use core::arch::x86_64::*;
+
+use super::{
+	BitOp,
+	scalar,
+};
+
+/// The number of bytes in an SSE2 register.
+const XMM: usize = 16;
+/// The number of bytes in an AVX2 register.
+const YMM: usize = 32;
+
+/// Tests whether the AVX2 routines may be called.
+///
+/// With the standard library, this is detected when the program runs.
+/// Without it, this is fixed by the target features enabled at compile time.
+#[inline]
+pub(super) fn has_avx2() -> bool {
+	#[cfg(feature = "std")]
+	{
+		std::is_x86_feature_detected!("avx2")
+	}
+	#[cfg(not(feature = "std"))]
+	{
+		cfg!(target_feature = "avx2")
+	}
+}
+
+/// Counts the bits set to `1` in a byte buffer, sixteen bytes at a time.
+pub(super) fn count_ones_sse2(bytes: &[u8]) -> usize {
+	let chunks = bytes.chunks_exact(XMM);
+	let rest = chunks.remainder();
+	let mut total = 0;
+	unsafe {
+		let m1 = _mm_set1_epi8(0x55);
+		let m2 = _mm_set1_epi8(0x33);
+		let m4 = _mm_set1_epi8(0x0F);
+		let zero = _mm_setzero_si128();
+		for chunk in chunks {
+			let mut v = _mm_loadu_si128(chunk.as_ptr().cast());
+			//  Sum adjacent bits, then pairs, then nibbles, within each byte.
+			v = _mm_sub_epi8(v, _mm_and_si128(_mm_srli_epi64(v, 1), m1));
+			v = _mm_add_epi8(
+				_mm_and_si128(v, m2),
+				_mm_and_si128(_mm_srli_epi64(v, 2), m2),
+			);
+			v = _mm_and_si128(_mm_add_epi8(v, _mm_srli_epi64(v, 4)), m4);
+			//  Sum the bytes of each half into its low lane.
+			let sums = _mm_sad_epu8(v, zero);
+			total += _mm_cvtsi128_si64(sums) as usize
+				+ _mm_cvtsi128_si64(_mm_unpackhi_epi64(sums, sums)) as usize;
+		}
+	}
+	total + scalar::count_ones(rest)
+}
+
+/// Counts the bits set to `1` in a byte buffer, thirty-two bytes at a time.
+///
+/// ## Safety
+///
+/// The processor must support AVX2.
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn count_ones_avx2(bytes: &[u8]) -> usize {
+	let chunks = bytes.chunks_exact(YMM);
+	let rest = chunks.remainder();
+	#[rustfmt::skip]
+	let lut = _mm256_setr_epi8(
+		0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4,
+		0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4,
+	);
+	let low = _mm256_set1_epi8(0x0F);
+	let zero = _mm256_setzero_si256();
+	let mut acc = zero;
+	for chunk in chunks {
+		let v = _mm256_loadu_si256(chunk.as_ptr().cast());
+		//  Look up the population of each nibble, and sum them per byte.
+		let lo = _mm256_shuffle_epi8(lut, _mm256_and_si256(v, low));
+		let hi = _mm256_shuffle_epi8(
+			lut,
+			_mm256_and_si256(_mm256_srli_epi16(v, 4), low),
+		);
+		let pop = _mm256_add_epi8(lo, hi);
+		acc = _mm256_add_epi64(acc, _mm256_sad_epu8(pop, zero));
+	}
+	let total = _mm256_extract_epi64(acc, 0)
+		+ _mm256_extract_epi64(acc, 1)
+		+ _mm256_extract_epi64(acc, 2)
+		+ _mm256_extract_epi64(acc, 3);
+	total as usize + count_ones_sse2(rest)
+}
+
+/// Finds the first byte in a buffer that is not `fill`, sixteen bytes at a
+/// time.
+pub(super) fn position_not_sse2(bytes: &[u8], fill: u8) -> Option<usize> {
+	let chunks = bytes.chunks_exact(XMM);
+	let rest = chunks.remainder();
+	unsafe {
+		let pattern = _mm_set1_epi8(fill as i8);
+		for (idx, chunk) in chunks.enumerate() {
+			let v = _mm_loadu_si128(chunk.as_ptr().cast());
+			let same = _mm_movemask_epi8(_mm_cmpeq_epi8(v, pattern)) as u32;
+			if same != 0xFFFF {
+				return Some(idx * XMM + (!same).trailing_zeros() as usize);
+			}
+		}
+	}
+	let start = bytes.len() - rest.len();
+	scalar::position_not(rest, fill).map(|idx| start + idx)
+}
+
+/// Finds the first byte in a buffer that is not `fill`, thirty-two bytes at
+/// a time.
+///
+/// ## Safety
+///
+/// The processor must support AVX2.
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn position_not_avx2(bytes: &[u8], fill: u8) -> Option<usize> {
+	let chunks = bytes.chunks_exact(YMM);
+	let rest = chunks.remainder();
+	let pattern = _mm256_set1_epi8(fill as i8);
+	for (idx, chunk) in chunks.enumerate() {
+		let v = _mm256_loadu_si256(chunk.as_ptr().cast());
+		let same = _mm256_movemask_epi8(_mm256_cmpeq_epi8(v, pattern)) as u32;
+		if same != !0 {
+			return Some(idx * YMM + (!same).trailing_zeros() as usize);
+		}
+	}
+	let start = bytes.len() - rest.len();
+	position_not_sse2(rest, fill).map(|idx| start + idx)
+}
+
+/// Finds the last byte in a buffer that is not `fill`, sixteen bytes at a
+/// time.
+pub(super) fn rposition_not_sse2(bytes: &[u8], fill: u8) -> Option<usize> {
+	let chunks = bytes.rchunks_exact(XMM);
+	let rest = chunks.remainder();
+	unsafe {
+		let pattern = _mm_set1_epi8(fill as i8);
+		for (idx, chunk) in chunks.enumerate() {
+			let v = _mm_loadu_si128(chunk.as_ptr().cast());
+			let same = _mm_movemask_epi8(_mm_cmpeq_epi8(v, pattern)) as u16;
+			if same != !0 {
+				let start = bytes.len() - (idx + 1) * XMM;
+				return Some(start + XMM - 1 - (!same).leading_zeros() as usize);
+			}
+		}
+	}
+	scalar::rposition_not(rest, fill)
+}
+
+/// Finds the last byte in a buffer that is not `fill`, thirty-two bytes at a
+/// time.
+///
+/// ## Safety
+///
+/// The processor must support AVX2.
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn rposition_not_avx2(
+	bytes: &[u8],
+	fill: u8,
+) -> Option<usize> {
+	let chunks = bytes.rchunks_exact(YMM);
+	let rest = chunks.remainder();
+	let pattern = _mm256_set1_epi8(fill as i8);
+	for (idx, chunk) in chunks.enumerate() {
+		let v = _mm256_loadu_si256(chunk.as_ptr().cast());
+		let same = _mm256_movemask_epi8(_mm256_cmpeq_epi8(v, pattern)) as u32;
+		if same != !0 {
+			let start = bytes.len() - (idx + 1) * YMM;
+			return Some(start + YMM - 1 - (!same).leading_zeros() as usize);
+		}
+	}
+	rposition_not_sse2(rest, fill)
+}
+
+/// Merges a byte buffer into another with a Boolean operation, sixteen bytes
+/// at a time.
+pub(super) fn bitop_assign_sse2(dst: &mut [u8], src: &[u8], op: BitOp) {
+	let mut dsts = dst.chunks_exact_mut(XMM);
+	let mut srcs = src.chunks_exact(XMM);
+	for (a, b) in (&mut dsts).zip(&mut srcs) {
+		unsafe {
+			let va = _mm_loadu_si128(a.as_ptr().cast());
+			let vb = _mm_loadu_si128(b.as_ptr().cast());
+			let out = match op {
+				BitOp::And => _mm_and_si128(va, vb),
+				BitOp::Or => _mm_or_si128(va, vb),
+				BitOp::Xor => _mm_xor_si128(va, vb),
+				//  `andnot` inverts its *first* operand.
+				BitOp::AndNot => _mm_andnot_si128(vb, va),
+			};
+			_mm_storeu_si128(a.as_mut_ptr().cast(), out);
+		}
+	}
+	scalar::bitop_assign(dsts.into_remainder(), srcs.remainder(), op);
+}
+
+/// Merges a byte buffer into another with a Boolean operation, thirty-two
+/// bytes at a time.
+///
+/// ## Safety
+///
+/// The processor must support AVX2.
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn bitop_assign_avx2(dst: &mut [u8], src: &[u8], op: BitOp) {
+	let mut dsts = dst.chunks_exact_mut(YMM);
+	let mut srcs = src.chunks_exact(YMM);
+	for (a, b) in (&mut dsts).zip(&mut srcs) {
+		let va = _mm256_loadu_si256(a.as_ptr().cast());
+		let vb = _mm256_loadu_si256(b.as_ptr().cast());
+		let out = match op {
+			BitOp::And => _mm256_and_si256(va, vb),
+			BitOp::Or => _mm256_or_si256(va, vb),
+			BitOp::Xor => _mm256_xor_si256(va, vb),
+			BitOp::AndNot => _mm256_andnot_si256(vb, va),
+		};
+		_mm256_storeu_si256(a.as_mut_ptr().cast(), out);
+	}
+	bitop_assign_sse2(dsts.into_remainder(), srcs.remainder(), op);
+}
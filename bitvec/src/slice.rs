@@ -40,12 +40,17 @@ use crate::{
 		BitSpan,
 		BitSpanError,
 	},
+	simd::{
+		self,
+		BitOp,
+	},
 	store::BitStore,
 };
 
 mod api;
 mod iter;
 mod ops;
+pub(crate) mod parse;
 mod specialization;
 mod tests;
 mod traits;
@@ -53,6 +58,10 @@ mod traits;
 pub use self::{
 	api::*,
 	iter::*,
+	parse::{
+		ParseBitsError,
+		ParseBitsErrorKind,
+	},
 };
 
 #[repr(transparent)]
@@ -1064,12 +1073,10 @@ where
 			Domain::Enclave(elem) => elem.load_value().count_ones() as usize,
 			Domain::Region { head, body, tail } => {
 				head.map_or(0, |elem| elem.load_value().count_ones() as usize)
-					+ body
-						.iter()
-						.map(BitStore::load_value)
-						.map(|elem| elem.count_ones() as usize)
-						.sum::<usize>() + tail
-					.map_or(0, |elem| elem.load_value().count_ones() as usize)
+					+ simd::count_ones(body)
+					+ tail.map_or(0, |elem| {
+						elem.load_value().count_ones() as usize
+					})
 			},
 		}
 	}
@@ -1093,14 +1100,12 @@ where
 				| !elem.mask().into_inner())
 			.count_zeros() as usize,
 			Domain::Region { head, body, tail } => {
+				let body_bits = body.len() * mem::bits_of::<T::Mem>();
+				let body_zeros = body_bits - simd::count_ones(body);
 				head.map_or(0, |elem| {
 					(elem.load_value() | !elem.mask().into_inner()).count_zeros()
 						as usize
-				}) + body
-					.iter()
-					.map(BitStore::load_value)
-					.map(|elem| elem.count_zeros() as usize)
-					.sum::<usize>() + tail.map_or(0, |elem| {
+				}) + body_zeros + tail.map_or(0, |elem| {
 					(elem.load_value() | !elem.mask().into_inner()).count_zeros()
 						as usize
 				})
@@ -1186,6 +1191,38 @@ where
 		IterZeros::new(self)
 	}
 
+	/// Enumerates the runs of identical bits in a bit-slice.
+	///
+	/// Each run is yielded as its bit-value and the range of indices that it
+	/// covers. Adjacent runs always have opposite values, and together the runs
+	/// cover the entire bit-slice.
+	///
+	/// Like [`.iter_ones()`] and [`.iter_zeros()`], this seeks run boundaries
+	/// a whole element at a time in `Lsb0` and `Msb0` bit-slices, and does not
+	/// allocate.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bits = bits![0, 0, 1, 1, 1, 0, 1];
+	/// let mut runs = bits.iter_runs();
+	///
+	/// assert_eq!(runs.next(), Some((false, 0 .. 2)));
+	/// assert_eq!(runs.next(), Some((true, 2 .. 5)));
+	/// assert_eq!(runs.next_back(), Some((true, 6 .. 7)));
+	/// assert_eq!(runs.next(), Some((false, 5 .. 6)));
+	/// assert!(runs.next().is_none());
+	/// ```
+	///
+	/// [`.iter_ones()`]: Self::iter_ones
+	/// [`.iter_zeros()`]: Self::iter_zeros
+	#[inline]
+	pub fn iter_runs(&self) -> IterRuns<'_, T, O> {
+		IterRuns::new(self)
+	}
+
 	/// Finds the index of the first bit in the bit-slice set to `1`.
 	///
 	/// Returns `None` if there is no `true` bit in the bit-slice.
@@ -1487,6 +1524,13 @@ where
 			len,
 		);
 
+		if let Some(this) = self.coerce_mut::<T, Lsb0>() {
+			return this.sp_shift_start(by);
+		}
+		if let Some(this) = self.coerce_mut::<T, Msb0>() {
+			return this.sp_shift_start(by);
+		}
+
 		unsafe {
 			self.copy_within_unchecked(by .., 0);
 			self.get_unchecked_mut(len - by ..).fill(false);
@@ -1553,6 +1597,13 @@ where
 			len,
 		);
 
+		if let Some(this) = self.coerce_mut::<T, Lsb0>() {
+			return this.sp_shift_end(by);
+		}
+		if let Some(this) = self.coerce_mut::<T, Msb0>() {
+			return this.sp_shift_end(by);
+		}
+
 		unsafe {
 			self.copy_within_unchecked(.. len - by, by);
 			self.get_unchecked_mut(.. by).fill(false);
@@ -1566,6 +1617,56 @@ where
 	pub fn shift_right(&mut self, by: usize) {
 		self.shift_end(by);
 	}
+
+	/// Clears every bit of `self` that is set in another bit-slice.
+	///
+	/// This is the set difference `self & !rhs`, computed in place. If `rhs`
+	/// is shorter than `self`, the bits of `self` beyond it are left
+	/// untouched.
+	///
+	/// ## Behavior
+	///
+	/// This has the same acceleration as the `&=` operator: when `rhs` has the
+	/// same type parameters as `self` and starts at the same bit within its
+	/// first element, the operation proceeds one memory element at a time.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bits = bits![mut 0, 0, 1, 1, 1];
+	/// bits.andnot_assign(bits![0, 1, 0, 1]);
+	/// assert_eq!(bits, bits![0, 0, 1, 0, 1]);
+	/// ```
+	#[inline]
+	pub fn andnot_assign<T2, O2>(&mut self, rhs: &BitSlice<T2, O2>)
+	where
+		T2: BitStore,
+		O2: BitOrder,
+	{
+		if let Some(that) = rhs.coerce::<T, O>() {
+			if self.bitop_domain(that, BitOp::AndNot) {
+				return;
+			}
+		}
+		if let (Some(this), Some(that)) =
+			(self.coerce_mut::<T, Lsb0>(), rhs.coerce::<T, Lsb0>())
+		{
+			return this.sp_bitop_assign(that, |a, b| a & !b, |a, b| a & !b);
+		}
+		if let (Some(this), Some(that)) =
+			(self.coerce_mut::<T, Msb0>(), rhs.coerce::<T, Msb0>())
+		{
+			return this.sp_bitop_assign(that, |a, b| a & !b, |a, b| a & !b);
+		}
+		for (this, that) in self.as_mut_bitptr_range().zip(rhs.as_bitptr_range())
+		{
+			unsafe {
+				this.write(this.read() & !that.read());
+			}
+		}
+	}
 }
 
 /// Crate internals.
@@ -1647,6 +1748,114 @@ where
 		//  Remove the new alias layer.
 		(Self::unalias_mut(head), Self::unalias_mut(tail))
 	}
+
+	/// Merges another bit-slice into `self` one memory element at a time.
+	///
+	/// This only succeeds when `rhs` is at least as long as `self` and begins
+	/// at the same bit within its first element. The two bit-slices then have
+	/// identical domains, so their partial edge elements can be combined under
+	/// their masks and their bodies combined whole, without any realignment.
+	///
+	/// ## Parameters
+	///
+	/// - `&mut self`
+	/// - `rhs`: The bit-slice to merge into `self`. Only its first
+	///   `self.len()` bits are used.
+	/// - `op`: The Boolean operation to apply to each pair of elements.
+	///
+	/// ## Returns
+	///
+	/// Whether the operation was applied. When this is `false`, `self` is
+	/// unmodified and the caller must fall back to a realigning strategy.
+	pub(crate) fn bitop_domain<T2>(
+		&mut self,
+		rhs: &BitSlice<T2, O>,
+		op: BitOp,
+	) -> bool
+	where T2: BitStore<Mem = T::Mem> {
+		let rhs = match rhs.get(.. self.len()) {
+			Some(rhs) => rhs,
+			None => return false,
+		};
+		if self.as_bitspan().head() != rhs.as_bitspan().head() {
+			return false;
+		}
+		match (self.domain_mut(), rhs.domain()) {
+			(Domain::Enclave(mut this), Domain::Enclave(that)) => {
+				this.store_value(op.apply(this.load_value(), that.load_value()));
+			},
+			(
+				Domain::Region {
+					head: this_head,
+					body: this_body,
+					tail: this_tail,
+				},
+				Domain::Region {
+					head: that_head,
+					body: that_body,
+					tail: that_tail,
+				},
+			) => {
+				if let (Some(mut this), Some(that)) = (this_head, that_head) {
+					let val = op.apply(this.load_value(), that.load_value());
+					this.store_value(val);
+				}
+				simd::bitop_assign(this_body, that_body, op);
+				if let (Some(mut this), Some(that)) = (this_tail, that_tail) {
+					let val = op.apply(this.load_value(), that.load_value());
+					this.store_value(val);
+				}
+			},
+			_ => unreachable!("equal spans must have equal domains"),
+		}
+		true
+	}
+
+	/// Tests two bit-slices for equality by comparing their memory directly.
+	///
+	/// This requires that the bit-slices have the same length and begin at the
+	/// same bit within their first elements, as in [`.bitop_domain()`]. Their
+	/// partial edge elements are compared under their masks, and their bodies
+	/// are compared whole.
+	///
+	/// ## Returns
+	///
+	/// Whether the bit-slices are equal, or `None` if their domains differ and
+	/// the caller must fall back to a realigning comparison.
+	///
+	/// [`.bitop_domain()`]: Self::bitop_domain
+	pub(crate) fn eq_domain(&self, rhs: &Self) -> Option<bool> {
+		if self.len() != rhs.len() {
+			return Some(false);
+		}
+		if self.as_bitspan().head() != rhs.as_bitspan().head() {
+			return None;
+		}
+		Some(match (self.domain(), rhs.domain()) {
+			(Domain::Enclave(this), Domain::Enclave(that)) => {
+				this.load_value() == that.load_value()
+			},
+			(
+				Domain::Region {
+					head: this_head,
+					body: this_body,
+					tail: this_tail,
+				},
+				Domain::Region {
+					head: that_head,
+					body: that_body,
+					tail: that_tail,
+				},
+			) => {
+				this_head.map(|elem| elem.load_value())
+					== that_head.map(|elem| elem.load_value())
+					&& this_tail.map(|elem| elem.load_value())
+						== that_tail.map(|elem| elem.load_value())
+					&& simd::eq(this_body, that_body)
+			},
+			_ => unreachable!("equal spans must have equal domains"),
+		})
+	}
 }
 
 /// Methods available only when `T` allows shared mutability.
@@ -1806,6 +2015,35 @@ where
 				bv.set_len(self.len());
 			})
 	}
+
+	/// Computes the set difference of two bit-slices into a new bit-vector.
+	///
+	/// This is the non-mutating form of [`.andnot_assign()`]: the result has
+	/// the length of `self`, and each bit is set only where `self` is set and
+	/// `rhs` is not.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let a = bits![0, 0, 1, 1];
+	/// let b = bits![0, 1, 0, 1];
+	/// assert_eq!(a.and_not(b), bits![0, 0, 1, 0]);
+	/// ```
+	///
+	/// [`.andnot_assign()`]: Self::andnot_assign
+	#[inline]
+	pub fn and_not<T2, O2>(
+		&self,
+		rhs: &BitSlice<T2, O2>,
+	) -> BitVec<T::Unalias, O>
+	where
+		T2: BitStore,
+		O2: BitOrder,
+	{
+		self.to_bitvec().tap_mut(|bv| bv.andnot_assign(rhs))
+	}
 }
 
 #[inline]
@@ -13,6 +13,7 @@ use core::{
 	},
 	marker::PhantomData,
 	mem,
+	ops::Range,
 };
 
 use wyz::comu::{
@@ -2362,6 +2363,112 @@ where
 {
 }
 
+#[doc = include_str!("../../doc/slice/iter/IterRuns.md")]
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct IterRuns<'a, T, O>
+where
+	T: 'a + BitStore,
+	O: BitOrder,
+{
+	/// The remaining bit-slice whose runs are to be found.
+	inner: &'a BitSlice<T, O>,
+	/// The offset from the front of the original bit-slice to the current
+	/// `.inner`.
+	front: usize,
+}
+
+impl<'a, T, O> IterRuns<'a, T, O>
+where
+	T: 'a + BitStore,
+	O: BitOrder,
+{
+	#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+	pub(super) fn new(slice: &'a BitSlice<T, O>) -> Self {
+		Self {
+			inner: slice,
+			front: 0,
+		}
+	}
+}
+
+impl<T, O> Default for IterRuns<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn default() -> Self {
+		Self {
+			inner: Default::default(),
+			front: 0,
+		}
+	}
+}
+
+impl<T, O> Iterator for IterRuns<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	type Item = (bool, Range<usize>);
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let bit = *self.inner.first()?;
+		//  The run ends at the first bit with the opposite value.
+		let len = if bit {
+			self.inner.first_zero()
+		}
+		else {
+			self.inner.first_one()
+		}
+		.unwrap_or_else(|| self.inner.len());
+
+		let (_, rest) = unsafe { self.inner.split_at_unchecked(len) };
+		self.inner = rest;
+		let start = self.front;
+		self.front += len;
+		Some((bit, start .. self.front))
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.inner.len();
+		((len > 0) as usize, Some(len))
+	}
+}
+
+impl<T, O> DoubleEndedIterator for IterRuns<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let bit = *self.inner.last()?;
+		//  The run begins after the last bit with the opposite value.
+		let start = if bit {
+			self.inner.last_zero()
+		}
+		else {
+			self.inner.last_one()
+		}
+		.map_or(0, |idx| idx + 1);
+
+		let (rest, _) = unsafe { self.inner.split_at_unchecked(start) };
+		let end = self.front + self.inner.len();
+		self.inner = rest;
+		Some((bit, self.front + start .. end))
+	}
+}
+
+impl<T, O> FusedIterator for IterRuns<'_, T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+}
+
 /* This macro has some very obnoxious call syntax that is necessary to handle
 the different iteration protocols used above.
 
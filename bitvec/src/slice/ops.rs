@@ -18,10 +18,15 @@ use core::ops::{
 	RangeToInclusive,
 };
 
+#[cfg(feature = "alloc")]
+use tap::Tap;
+
 use super::{
 	BitSlice,
 	BitSliceIndex,
 };
+#[cfg(feature = "alloc")]
+use crate::vec::BitVec;
 use crate::{
 	domain::Domain,
 	order::{
@@ -29,6 +34,7 @@ use crate::{
 		Lsb0,
 		Msb0,
 	},
+	simd::BitOp,
 	store::BitStore,
 };
 
@@ -42,6 +48,11 @@ where
 	#[inline]
 	#[doc = include_str!("../../doc/slice/bitop_assign.md")]
 	fn bitand_assign(&mut self, rhs: &BitSlice<T2, O2>) {
+		if let Some(that) = rhs.coerce::<T1, O1>() {
+			if self.bitop_domain(that, BitOp::And) {
+				return;
+			}
+		}
 		if let (Some(this), Some(that)) =
 			(self.coerce_mut::<T1, Lsb0>(), rhs.coerce::<T1, Lsb0>())
 		{
@@ -74,6 +85,11 @@ where
 	#[inline]
 	#[doc = include_str!("../../doc/slice/bitop_assign.md")]
 	fn bitor_assign(&mut self, rhs: &BitSlice<T2, O2>) {
+		if let Some(that) = rhs.coerce::<T1, O1>() {
+			if self.bitop_domain(that, BitOp::Or) {
+				return;
+			}
+		}
 		if let (Some(this), Some(that)) =
 			(self.coerce_mut::<T1, Lsb0>(), rhs.coerce::<T1, Lsb0>())
 		{
@@ -103,6 +119,11 @@ where
 	#[inline]
 	#[doc = include_str!("../../doc/slice/bitop_assign.md")]
 	fn bitxor_assign(&mut self, rhs: &BitSlice<T2, O2>) {
+		if let Some(that) = rhs.coerce::<T1, O1>() {
+			if self.bitop_domain(that, BitOp::Xor) {
+				return;
+			}
+		}
 		if let (Some(this), Some(that)) =
 			(self.coerce_mut::<T1, Lsb0>(), rhs.coerce::<T1, Lsb0>())
 		{
@@ -122,6 +143,57 @@ where
 	}
 }
 
+#[cfg(feature = "alloc")]
+impl<T1, T2, O1, O2> BitAnd<&BitSlice<T2, O2>> for &BitSlice<T1, O1>
+where
+	T1: BitStore,
+	T2: BitStore,
+	O1: BitOrder,
+	O2: BitOrder,
+{
+	type Output = BitVec<T1::Unalias, O1>;
+
+	#[inline]
+	#[doc = include_str!("../../doc/slice/bitop.md")]
+	fn bitand(self, rhs: &BitSlice<T2, O2>) -> Self::Output {
+		self.to_bitvec().tap_mut(|bv| *bv.as_mut_bitslice() &= rhs)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T1, T2, O1, O2> BitOr<&BitSlice<T2, O2>> for &BitSlice<T1, O1>
+where
+	T1: BitStore,
+	T2: BitStore,
+	O1: BitOrder,
+	O2: BitOrder,
+{
+	type Output = BitVec<T1::Unalias, O1>;
+
+	#[inline]
+	#[doc = include_str!("../../doc/slice/bitop.md")]
+	fn bitor(self, rhs: &BitSlice<T2, O2>) -> Self::Output {
+		self.to_bitvec().tap_mut(|bv| *bv.as_mut_bitslice() |= rhs)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T1, T2, O1, O2> BitXor<&BitSlice<T2, O2>> for &BitSlice<T1, O1>
+where
+	T1: BitStore,
+	T2: BitStore,
+	O1: BitOrder,
+	O2: BitOrder,
+{
+	type Output = BitVec<T1::Unalias, O1>;
+
+	#[inline]
+	#[doc = include_str!("../../doc/slice/bitop.md")]
+	fn bitxor(self, rhs: &BitSlice<T2, O2>) -> Self::Output {
+		self.to_bitvec().tap_mut(|bv| *bv.as_mut_bitslice() ^= rhs)
+	}
+}
+
 impl<T, O> Index<usize> for BitSlice<T, O>
 where
 	T: BitStore,
@@ -237,3 +309,21 @@ where
 		self
 	}
 }
+
+/// Copies the bit-slice into a new bit-vector and inverts each bit of the copy.
+///
+/// The inversion proceeds one memory element at a time, as it does for
+/// `!&mut BitSlice`.
+#[cfg(feature = "alloc")]
+impl<T, O> Not for &BitSlice<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	type Output = BitVec<T::Unalias, O>;
+
+	#[inline]
+	fn not(self) -> Self::Output {
+		!self.to_bitvec()
+	}
+}
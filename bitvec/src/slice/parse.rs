@@ -0,0 +1,558 @@
+#![doc = include_str!("../../doc/slice/parse.md")]
+
+use core::fmt::{
+	self,
+	Display,
+	Formatter,
+};
+
+/// The number of bits in a binary digit.
+const BIN: usize = 1;
+/// The number of bits in an octal digit.
+const OCT: usize = 3;
+/// The number of bits in a hexadecimal digit.
+const HEX: usize = 4;
+
+#[doc = include_str!("../../doc/slice/ParseBitsError.md")]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ParseBitsError {
+	/// The byte offset in the source text at which parsing failed.
+	position: usize,
+	/// The reason that parsing failed.
+	kind:     ParseBitsErrorKind,
+}
+
+/// The reasons that parsing a bit-sequence from text can fail.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ParseBitsErrorKind {
+	/// A number had no digits.
+	Empty,
+	/// A character was not a digit in the radix selected by its number’s
+	/// prefix.
+	InvalidDigit {
+		/// The offending character.
+		digit: char,
+		/// The radix of the number containing it.
+		radix: u32,
+	},
+	/// A character appeared where a list separator or delimiter was expected.
+	Unexpected(char),
+	/// A list was not closed with `]`.
+	Unterminated,
+	/// An octal or hexadecimal list entry could stand for more than one number
+	/// of bits.
+	///
+	/// The numeric formatters narrow the leading digit of a partially-filled
+	/// edge element, without recording how many bits it held.
+	Ambiguous,
+	/// The text held a different number of bits than the destination does.
+	Length {
+		/// The number of bits in the text.
+		actual:   usize,
+		/// The number of bits in the destination.
+		expected: usize,
+	},
+}
+
+impl ParseBitsError {
+	/// Creates a new error.
+	pub(crate) fn new(kind: ParseBitsErrorKind, position: usize) -> Self {
+		Self { position, kind }
+	}
+
+	/// Gets the byte offset in the source text at which parsing failed.
+	///
+	/// For a length mismatch, this is the start of the first digit that did
+	/// not fit, or the end of the text if it held too few bits.
+	#[inline]
+	pub fn position(&self) -> usize {
+		self.position
+	}
+
+	/// Gets the reason that parsing failed.
+	#[inline]
+	pub fn kind(&self) -> &ParseBitsErrorKind {
+		&self.kind
+	}
+}
+
+impl Display for ParseBitsError {
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		match self.kind {
+			ParseBitsErrorKind::Empty => {
+				write!(fmt, "expected digits at byte {}", self.position)
+			},
+			ParseBitsErrorKind::InvalidDigit { digit, radix } => write!(
+				fmt,
+				"invalid digit {:?} for radix {} at byte {}",
+				digit, radix, self.position,
+			),
+			ParseBitsErrorKind::Unexpected(chr) => write!(
+				fmt,
+				"unexpected character {:?} at byte {}",
+				chr, self.position,
+			),
+			ParseBitsErrorKind::Unterminated => {
+				write!(fmt, "unterminated bit list at byte {}", self.position)
+			},
+			ParseBitsErrorKind::Ambiguous => write!(
+				fmt,
+				"cannot tell how many bits the entry at byte {} holds",
+				self.position,
+			),
+			ParseBitsErrorKind::Length { actual, expected } => write!(
+				fmt,
+				"text holds {} bits, but the destination holds {} (at byte {})",
+				actual, expected, self.position,
+			),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseBitsError {}
+
+/// Describes how text is to be read.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Syntax {
+	/// The digit width of the radix given by the caller, if any. Numbers then
+	/// default to it, and may only carry its prefix.
+	pub(crate) radix: Option<usize>,
+	/// The bit width of the destination’s storage elements.
+	pub(crate) elem:  usize,
+	/// Whether every list entry is a whole element, as for a bit-array.
+	pub(crate) whole: bool,
+	/// The number of bits that the text holds, if the caller knows it.
+	pub(crate) len:   Option<usize>,
+}
+
+impl Syntax {
+	/// Describes text with numeric prefixes, for a destination with storage
+	/// elements `elem` bits wide.
+	pub(crate) fn new(elem: usize) -> Self {
+		Self {
+			radix: None,
+			elem,
+			whole: false,
+			len: None,
+		}
+	}
+
+	/// Reads numbers without a prefix in `radix`.
+	///
+	/// ## Panics
+	///
+	/// This panics if `radix` is not 2, 8, or 16.
+	pub(crate) fn radix(mut self, radix: u32) -> Self {
+		self.radix = Some(match radix {
+			2 => BIN,
+			8 => OCT,
+			16 => HEX,
+			_ => panic!("radix must be 2, 8, or 16, not {}", radix),
+		});
+		self
+	}
+
+	/// Reads every list entry as a whole element.
+	pub(crate) fn whole(mut self) -> Self {
+		self.whole = true;
+		self
+	}
+
+	/// Reads text that holds exactly `len` bits.
+	pub(crate) fn len(mut self, len: usize) -> Self {
+		self.len = Some(len);
+		self
+	}
+}
+
+/// Parses a bit-sequence from text, in either the number or the list form.
+///
+/// Surrounding whitespace, and the header that `Debug` writes before a list,
+/// are skipped.
+///
+/// ## Parameters
+///
+/// - `src`: The source text.
+/// - `syntax`: How to read the text.
+/// - `push`: Receives each parsed bit, in order, along with the byte offset of
+///   the digit that produced it.
+///
+/// ## Returns
+///
+/// The number of bits that the text holds.
+pub(crate) fn parse(
+	src: &str,
+	syntax: Syntax,
+	mut push: impl FnMut(bool, usize),
+) -> Result<usize, ParseBitsError> {
+	let bytes = src.as_bytes();
+	let start = skip_space(bytes, 0);
+	let end = bytes
+		.iter()
+		.rposition(|b| !b.is_ascii_whitespace())
+		.map_or(start, |idx| idx + 1);
+	let text = Text { src, end, syntax };
+
+	//  `Debug` renders a `BitType<…> { … }` header before the list.
+	let mut pos = start;
+	let header = bytes[.. end]
+		.iter()
+		.position(|&b| b == b'}')
+		.filter(|_| bytes.get(start).map_or(false, u8::is_ascii_alphabetic));
+	if let Some(idx) = header {
+		pos = skip_space(bytes, idx + 1);
+		match bytes[.. end].get(pos) {
+			Some(b'[') => {},
+			Some(_) => return Err(unexpected(src, pos)),
+			None => {
+				return Err(ParseBitsError::new(
+					ParseBitsErrorKind::Unterminated,
+					pos,
+				));
+			},
+		}
+	}
+
+	let mut count = 0;
+	if bytes[.. end].get(pos) != Some(&b'[') {
+		let num = text.number(start, end)?;
+		let bits = match syntax.len {
+			Some(len) if num.fits(len) => len,
+			_ => num.count * num.width,
+		};
+		num.emit(&text, bits, &mut |bit, pos| {
+			count += 1;
+			push(bit, pos);
+		});
+		return Ok(count);
+	}
+
+	//  The first pass checks the text, and finds the widths of the edge
+	//  entries. The second reads out the bits.
+	let mut edges = None;
+	let entries = text.walk(pos, |_, num| {
+		edges = Some(edges.map_or((num, num), |(first, _)| (first, num)));
+		Ok(())
+	})?;
+	let (first, last) = match edges {
+		Some(edges) => edges,
+		None => return Ok(0),
+	};
+	let (head, tail) = text.edges(first, last, entries)?;
+	text.walk(pos, |idx, num| {
+		let bits = if idx == 0 {
+			head
+		}
+		else if idx + 1 == entries {
+			tail
+		}
+		else {
+			num.resolve(syntax.elem, true)?
+		};
+		num.emit(&text, bits, &mut |bit, pos| {
+			count += 1;
+			push(bit, pos);
+		});
+		Ok(())
+	})?;
+	Ok(count)
+}
+
+/// The source text, trimmed, and the rules for reading it.
+struct Text<'a> {
+	/// The full source text.
+	src:    &'a str,
+	/// The end of the text, before any trailing whitespace.
+	end:    usize,
+	/// How to read the text.
+	syntax: Syntax,
+}
+
+impl Text<'_> {
+	/// Scans the number in `src[start .. end]`.
+	///
+	/// The number may have a `0b`, `0o`, or `0x` prefix, or only the prefix of
+	/// the caller’s radix if it gave one, and may contain `_` separators.
+	fn number(
+		&self,
+		start: usize,
+		end: usize,
+	) -> Result<Number, ParseBitsError> {
+		let text = &self.src.as_bytes()[start .. end];
+		let prefix = match text {
+			[b'0', b'b', ..] => Some(BIN),
+			[b'0', b'o', ..] => Some(OCT),
+			[b'0', b'x', ..] => Some(HEX),
+			_ => None,
+		};
+		let (width, skip) = match (self.syntax.radix, prefix) {
+			(None, Some(width)) => (width, 2),
+			(None, None) => (BIN, 0),
+			(Some(radix), Some(width)) if radix == width => (width, 2),
+			(Some(radix), _) => (radix, 0),
+		};
+		let mut num = Number {
+			entry: start,
+			start: start + skip,
+			end,
+			width,
+			count: 0,
+			lead: 0,
+		};
+
+		for (pos, byte) in num.digits(self.src) {
+			let val = digit(byte, width).ok_or_else(|| {
+				let digit = self.src[pos ..].chars().next().unwrap_or_default();
+				ParseBitsError::new(
+					ParseBitsErrorKind::InvalidDigit {
+						digit,
+						radix: 1 << width,
+					},
+					pos,
+				)
+			})?;
+			if num.count == 0 {
+				num.lead = val;
+			}
+			num.count += 1;
+		}
+		if num.count == 0 {
+			return Err(ParseBitsError::new(ParseBitsErrorKind::Empty, end));
+		}
+		Ok(num)
+	}
+
+	/// Walks the bracketed list that opens at `pos`, handing each entry and
+	/// its index to `each`.
+	///
+	/// ## Returns
+	///
+	/// The number of entries in the list.
+	fn walk(
+		&self,
+		pos: usize,
+		mut each: impl FnMut(usize, Number) -> Result<(), ParseBitsError>,
+	) -> Result<usize, ParseBitsError> {
+		let bytes = &self.src.as_bytes()[.. self.end];
+		let mut pos = skip_space(bytes, pos + 1);
+		let mut idx = 0;
+		if bytes.get(pos) == Some(&b']') {
+			return self.finish(pos + 1).map(|()| idx);
+		}
+		loop {
+			if pos == bytes.len() {
+				return Err(ParseBitsError::new(
+					ParseBitsErrorKind::Unterminated,
+					pos,
+				));
+			}
+			let end = bytes[pos ..]
+				.iter()
+				.position(|&b| {
+					b == b',' || b == b']' || b.is_ascii_whitespace()
+				})
+				.map_or(bytes.len(), |len| pos + len);
+			each(idx, self.number(pos, end)?)?;
+			idx += 1;
+
+			pos = skip_space(bytes, end);
+			match bytes.get(pos) {
+				Some(b',') => {
+					pos = skip_space(bytes, pos + 1);
+					//  Pretty-printed lists have a trailing comma.
+					if bytes.get(pos) == Some(&b']') {
+						return self.finish(pos + 1).map(|()| idx);
+					}
+				},
+				Some(b']') => return self.finish(pos + 1).map(|()| idx),
+				Some(_) => return Err(unexpected(self.src, pos)),
+				None => {
+					return Err(ParseBitsError::new(
+						ParseBitsErrorKind::Unterminated,
+						pos,
+					));
+				},
+			}
+		}
+	}
+
+	/// Finds the widths of the first and last entries of a list.
+	///
+	/// The numeric formatters narrow the leading digit of a partially-filled
+	/// edge element, without recording how many bits it held. When the caller
+	/// knows the length of the text, the edge entries take whatever widths
+	/// make up that length, preferring a whole first element, as a
+	/// bit-sequence that begins at the start of an element has. Otherwise,
+	/// each edge entry takes the only width its digits allow.
+	fn edges(
+		&self,
+		first: Number,
+		last: Number,
+		entries: usize,
+	) -> Result<(usize, usize), ParseBitsError> {
+		let Syntax {
+			elem, whole, len, ..
+		} = self.syntax;
+		let resolve = || {
+			let head = first.resolve(elem, whole)?;
+			let tail = if entries == 1 {
+				head
+			}
+			else {
+				last.resolve(elem, whole)?
+			};
+			Ok((head, tail))
+		};
+		let len = match len {
+			Some(len) if !whole => len,
+			_ => return resolve(),
+		};
+
+		if entries == 1 {
+			return if first.fits(len) { Ok((len, len)) } else { resolve() };
+		}
+		let edges = match len.checked_sub((entries - 2) * elem) {
+			Some(edges) => edges,
+			None => return resolve(),
+		};
+		let split = |head: usize| {
+			edges
+				.checked_sub(head)
+				.filter(|&tail| first.fits(head) && last.fits(tail))
+				.map(|tail| (head, tail))
+		};
+		if let Some(found) = split(elem) {
+			return Ok(found);
+		}
+		let mut found = (1 ..= elem).filter_map(split);
+		match (found.next(), found.next()) {
+			(Some(found), None) => Ok(found),
+			_ => resolve(),
+		}
+	}
+
+	/// Requires that the closing `]` of a list is the end of the text.
+	fn finish(&self, pos: usize) -> Result<(), ParseBitsError> {
+		let pos = skip_space(&self.src.as_bytes()[.. self.end], pos);
+		if pos == self.end {
+			Ok(())
+		}
+		else {
+			Err(unexpected(self.src, pos))
+		}
+	}
+}
+
+/// A number in the source text.
+#[derive(Clone, Copy, Debug)]
+struct Number {
+	/// The byte offset of the number, including any prefix.
+	entry: usize,
+	/// The byte offset of the first digit, after any prefix.
+	start: usize,
+	/// The byte offset after the last digit.
+	end:   usize,
+	/// The number of bits in each digit.
+	width: usize,
+	/// The number of digits.
+	count: usize,
+	/// The value of the leading digit.
+	lead:  usize,
+}
+
+impl Number {
+	/// Iterates over the digits, with their byte offsets.
+	fn digits<'a>(
+		&self,
+		src: &'a str,
+	) -> impl 'a + Iterator<Item = (usize, u8)> {
+		let start = self.start;
+		src.as_bytes()[start .. self.end]
+			.iter()
+			.enumerate()
+			.filter(|&(_, &b)| b != b'_')
+			.map(move |(idx, &b)| (start + idx, b))
+	}
+
+	/// Tests whether the number can be read as `bits` bits.
+	///
+	/// The leading digit holds at least one bit, and all of its value.
+	fn fits(&self, bits: usize) -> bool {
+		let rest = (self.count - 1) * self.width;
+		(rest + bit_len(self.lead).max(1) ..= rest + self.width).contains(&bits)
+	}
+
+	/// Finds the width of a list entry.
+	///
+	/// An entry that is known to be `whole` takes the element width if it can.
+	/// Otherwise, it takes the only width that its digits allow. Entries with
+	/// more digits than an element keep every digit.
+	fn resolve(
+		&self,
+		elem: usize,
+		whole: bool,
+	) -> Result<usize, ParseBitsError> {
+		let rest = (self.count - 1) * self.width;
+		let min = rest + bit_len(self.lead).max(1);
+		let mut max = self.count * self.width;
+		if min <= elem {
+			max = max.min(elem);
+		}
+		if whole && self.fits(elem) {
+			Ok(elem)
+		}
+		else if min == max {
+			Ok(min)
+		}
+		else {
+			Err(ParseBitsError::new(
+				ParseBitsErrorKind::Ambiguous,
+				self.entry,
+			))
+		}
+	}
+
+	/// Produces the low `bits` bits of the number, most significant first.
+	fn emit(
+		&self,
+		text: &Text,
+		bits: usize,
+		push: &mut impl FnMut(bool, usize),
+	) {
+		let lead = bits - (self.count - 1) * self.width;
+		for (idx, (pos, byte)) in self.digits(text.src).enumerate() {
+			let val = digit(byte, self.width).unwrap_or_default();
+			let width = if idx == 0 { lead } else { self.width };
+			for shamt in (0 .. width).rev() {
+				push(val >> shamt & 1 == 1, pos);
+			}
+		}
+	}
+}
+
+/// Decodes an ASCII digit whose value must fit in `width` bits.
+fn digit(byte: u8, width: usize) -> Option<usize> {
+	(byte as char).to_digit(1 << width).map(|val| val as usize)
+}
+
+/// Counts the significant bits in a digit value.
+fn bit_len(val: usize) -> usize {
+	(usize::BITS - val.leading_zeros()) as usize
+}
+
+/// Advances past any ASCII whitespace.
+fn skip_space(bytes: &[u8], pos: usize) -> usize {
+	bytes[pos ..]
+		.iter()
+		.position(|b| !b.is_ascii_whitespace())
+		.map_or(bytes.len(), |len| pos + len)
+}
+
+/// Reports the character at `pos` as out of place.
+fn unexpected(src: &str, pos: usize) -> ParseBitsError {
+	let chr = src[pos ..].chars().next().unwrap_or_default();
+	ParseBitsError::new(ParseBitsErrorKind::Unexpected(chr), pos)
+}
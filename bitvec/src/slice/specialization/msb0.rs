@@ -18,6 +18,7 @@ use crate::{
 	field::BitField,
 	mem::bits_of,
 	order::Msb0,
+	simd,
 	slice::BitSlice,
 	store::BitStore,
 };
@@ -136,7 +137,9 @@ where T: BitStore
 					}
 				}
 
-				for val in body.iter().map(BitStore::load_value) {
+				let skip = simd::leading_fill(body, false);
+				accum += skip * bits_of::<T::Mem>();
+				for val in body[skip ..].iter().map(BitStore::load_value) {
 					accum += val.leading_zeros() as usize;
 					if has_one(val, !<T::Mem as Integral>::ZERO) {
 						return Some(accum);
@@ -181,6 +184,9 @@ where T: BitStore
 					}
 				}
 
+				let skip = simd::trailing_fill(body, false);
+				out -= skip * bits_of::<T::Mem>();
+				let body = &body[.. body.len() - skip];
 				for val in body.iter().map(BitStore::load_value).rev() {
 					out -= val.trailing_zeros() as usize;
 					if has_one(val, !<T::Mem as Integral>::ZERO) {
@@ -225,7 +231,9 @@ where T: BitStore
 					}
 				}
 
-				for val in body.iter().map(BitStore::load_value) {
+				let skip = simd::leading_fill(body, true);
+				accum += skip * bits_of::<T::Mem>();
+				for val in body[skip ..].iter().map(BitStore::load_value) {
 					accum += val.leading_ones() as usize;
 					if has_zero(val, !<T::Mem as Integral>::ZERO) {
 						return Some(accum);
@@ -270,6 +278,9 @@ where T: BitStore
 					}
 				}
 
+				let skip = simd::trailing_fill(body, true);
+				out -= skip * bits_of::<T::Mem>();
+				let body = &body[.. body.len() - skip];
 				for val in body.iter().map(BitStore::load_value).rev() {
 					out -= val.trailing_ones() as usize;
 					if has_zero(val, !<T::Mem as Integral>::ZERO) {
@@ -302,4 +313,69 @@ where T: BitStore
 			that.store_be(a);
 		}
 	}
+
+	/// Accelerates shifting towards the start of the bit-slice.
+	///
+	/// The bit-slice is treated as a big-endian integer built from `usize`
+	/// chunks, so moving bits towards index zero is a left shift. Each output
+	/// word is assembled from the two source words that straddle it, and is
+	/// only written after both have been read, so the shift can proceed in
+	/// place from the front.
+	pub(crate) fn sp_shift_start(&mut self, by: usize) {
+		let words = (self.len() + WORD_BITS - 1) / WORD_BITS;
+		let (skip, shamt) = (by / WORD_BITS, by % WORD_BITS);
+		for idx in 0 .. words {
+			let mut word = self.sp_load_word(idx + skip) << shamt;
+			if shamt != 0 {
+				word |= self.sp_load_word(idx + skip + 1) >> (WORD_BITS - shamt);
+			}
+			self.sp_store_word(idx, word);
+		}
+	}
+
+	/// Accelerates shifting towards the end of the bit-slice.
+	///
+	/// This is the mirror of [`.sp_shift_start()`]: it is a right shift of the
+	/// same integer, and proceeds from the back so that no source word is
+	/// overwritten before it is read.
+	///
+	/// [`.sp_shift_start()`]: Self::sp_shift_start
+	pub(crate) fn sp_shift_end(&mut self, by: usize) {
+		let words = (self.len() + WORD_BITS - 1) / WORD_BITS;
+		let (skip, shamt) = (by / WORD_BITS, by % WORD_BITS);
+		for idx in (0 .. words).rev() {
+			let mut word = 0;
+			if let Some(src) = idx.checked_sub(skip) {
+				word = self.sp_load_word(src) >> shamt;
+				if shamt != 0 && src > 0 {
+					word |= self.sp_load_word(src - 1) << (WORD_BITS - shamt);
+				}
+			}
+			self.sp_store_word(idx, word);
+		}
+	}
+
+	/// Loads the `idx`th `usize` chunk of the bit-slice.
+	///
+	/// A partial final chunk is placed in the high bits of the word, with its
+	/// low bits zeroed, and chunks past the end of the bit-slice read as zero.
+	fn sp_load_word(&self, idx: usize) -> usize {
+		let start = idx * WORD_BITS;
+		if start >= self.len() {
+			return 0;
+		}
+		let end = (start + WORD_BITS).min(self.len());
+		unsafe { self.get_unchecked(start .. end) }.load_be::<usize>()
+			<< (WORD_BITS - (end - start))
+	}
+
+	/// Stores into the `idx`th `usize` chunk of the bit-slice.
+	///
+	/// A partial final chunk keeps only the high bits of `word`.
+	fn sp_store_word(&mut self, idx: usize, word: usize) {
+		let start = idx * WORD_BITS;
+		let end = (start + WORD_BITS).min(self.len());
+		unsafe { self.get_unchecked_mut(start .. end) }
+			.store_be(word >> (WORD_BITS - (end - start)));
+	}
 }
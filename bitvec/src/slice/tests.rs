@@ -14,6 +14,7 @@ use crate::{
 mod api;
 mod iter;
 mod ops;
+mod parse;
 mod traits;
 
 #[test]
@@ -227,6 +228,106 @@ fn shunting() {
 	assert_eq!(bits, bits![0, 1, 1, 1, 0]);
 }
 
+#[test]
+#[cfg(feature = "alloc")]
+fn shunting_words() {
+	fn check<T, O>(bits: &BitSlice<T, O>)
+	where
+		T: BitStore,
+		O: BitOrder,
+	{
+		let len = bits.len();
+		let orig = bits.to_bitvec();
+		for by in (0 ..= len).step_by(7).chain([1, 63, 64, 65, len]) {
+			if by > len {
+				continue;
+			}
+			let mut start = bits.to_bitvec();
+			start.shift_start(by);
+			let mut end = bits.to_bitvec();
+			end.shift_end(by);
+			for idx in 0 .. len {
+				let old = idx.checked_add(by).filter(|&i| i < len);
+				assert_eq!(start[idx], old.map_or(false, |i| orig[i]));
+				let old = idx.checked_sub(by);
+				assert_eq!(end[idx], old.map_or(false, |i| orig[i]));
+			}
+		}
+	}
+
+	let data = random::<[u8; 32]>();
+	check(&data.view_bits::<Lsb0>()[3 .. 251]);
+	check(&data.view_bits::<Msb0>()[5 .. 250]);
+	check(&data.view_bits::<HiLo>()[1 .. 200]);
+	let data = random::<[u16; 20]>();
+	check(&data.view_bits::<Lsb0>()[11 ..]);
+	check(&data.view_bits::<Msb0>()[.. 317]);
+	let data = random::<[u32; 10]>();
+	check(data.view_bits::<Lsb0>());
+	check(&data.view_bits::<Msb0>()[31 .. 289]);
+	let data = random::<[u64; 5]>();
+	check(&data.view_bits::<Lsb0>()[.. 130]);
+	check(&data.view_bits::<Msb0>()[64 ..]);
+
+	let mut bits = bitarr![u8, Msb0; 1; 20];
+	bits[.. 19].shift_start(18);
+	assert!(bits[0] && bits[19]);
+	assert!(bits[1 .. 19].not_any());
+}
+
+#[test]
+fn bulk_domains() {
+	fn check<T, O>(bits: &BitSlice<T, O>)
+	where
+		T: BitStore,
+		O: BitOrder,
+	{
+		let ones = bits.iter().by_vals().filter(|&b| b).count();
+		assert_eq!(bits.count_ones(), ones);
+		assert_eq!(bits.count_zeros(), bits.len() - ones);
+		assert_eq!(bits.first_one(), bits.iter().by_vals().position(|b| b));
+		assert_eq!(bits.last_one(), bits.iter().by_vals().rposition(|b| b));
+		assert_eq!(bits.first_zero(), bits.iter().by_vals().position(|b| !b));
+		assert_eq!(bits.last_zero(), bits.iter().by_vals().rposition(|b| !b));
+	}
+
+	//  Long runs of filled elements, with a single bit of interest near each
+	//  end, make the searches skip over most of the body.
+	let mut zeros = [0u16; 48];
+	zeros[29] |= 1 << 3;
+	zeros[7] |= 1 << 12;
+	let mut ones = [!0u64; 12];
+	ones[2] &= !(1 << 40);
+	ones[10] &= !1;
+	for (start, end) in [(0, 0), (5, 700), (16, 768), (130, 131)] {
+		check(&zeros.view_bits::<Lsb0>()[start .. end]);
+		check(&zeros.view_bits::<Msb0>()[start .. end]);
+		check(&ones.view_bits::<Lsb0>()[start .. end]);
+		check(&ones.view_bits::<Msb0>()[start .. end]);
+	}
+	let data = random::<[u32; 24]>();
+	check(&data.view_bits::<Lsb0>()[9 .. 700]);
+	check(&data.view_bits::<Msb0>()[32 ..]);
+
+	//  Equality compares whole bodies when both sides are aligned alike.
+	let mut other = data;
+	let (a, b) = (data.view_bits::<Lsb0>(), other.view_bits::<Lsb0>());
+	assert_eq!(a[3 .. 700], b[3 .. 700]);
+	assert_ne!(a[3 .. 700], b[3 .. 701]);
+	other[12] ^= 1 << 17;
+	let b = other.view_bits::<Lsb0>();
+	assert_ne!(a[3 .. 700], b[3 .. 700]);
+	assert_eq!(a[3 .. 401], b[3 .. 401]);
+	assert_eq!(a[402 .. 700], b[402 .. 700]);
+
+	//  Boolean assignment combines whole bodies the same way.
+	let mut dst = data;
+	dst.view_bits_mut::<Lsb0>()[5 .. 705] ^= &a[5 .. 705];
+	assert!(dst.view_bits::<Lsb0>()[5 .. 705].not_any());
+	assert_eq!(dst.view_bits::<Lsb0>()[.. 5], a[.. 5]);
+	assert_eq!(dst.view_bits::<Lsb0>()[705 ..], a[705 ..]);
+}
+
 #[test]
 fn aliasing() {
 	let bits = bits![Cell<u32>, Lsb0; 0];
@@ -677,6 +677,40 @@ fn iter_zeros() {
 	assert!(zeros.next().is_none());
 }
 
+#[test]
+fn iter_runs() {
+	use crate::order::HiLo;
+
+	let bits = 0b1001_1100u8.view_bits::<HiLo>();
+	// ordering: 3210 7654
+	let mut runs = bits.iter_runs();
+	assert_eq!(runs.next(), Some((true, 0 .. 1)));
+	assert_eq!(runs.next_back(), Some((true, 6 .. 8)));
+	assert_eq!(runs.next(), Some((false, 1 .. 3)));
+	assert_eq!(runs.next_back(), Some((false, 4 .. 6)));
+	assert_eq!(runs.next(), Some((true, 3 .. 4)));
+	assert!(runs.next().is_none());
+	assert!(runs.next_back().is_none());
+
+	let mut bits = bitvec![u32, Msb0; 0; 200];
+	bits[30 .. 170].fill(true);
+	bits.set(100, false);
+	let runs = bits[10 ..].iter_runs().collect::<Vec<_>>();
+	assert_eq!(runs, [
+		(false, 0 .. 20),
+		(true, 20 .. 90),
+		(false, 90 .. 91),
+		(true, 91 .. 160),
+		(false, 160 .. 190),
+	]);
+	let mut rev = bits[10 ..].iter_runs().rev().collect::<Vec<_>>();
+	rev.reverse();
+	assert_eq!(rev, runs);
+
+	assert!(bits![].iter_runs().next().is_none());
+	assert_eq!(bits![1; 5].iter_runs().collect::<Vec<_>>(), [(true, 0 .. 5)]);
+}
+
 #[test]
 fn trait_impls() {
 	use core::iter::FusedIterator;
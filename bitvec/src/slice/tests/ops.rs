@@ -1,6 +1,7 @@
 use rand::random;
 
 use crate::{
+	order::HiLo,
 	prelude::*,
 	slice::BitSliceIndex,
 };
@@ -108,6 +109,52 @@ fn bit_not() {
 	assert_eq!(c, [0xFF_FF_00_00, 0xFF_00_00_FF, 0x00_00_FF_FF]);
 }
 
+#[test]
+#[cfg(feature = "alloc")]
+fn borrowed_bitops() {
+	fn check<T1, T2, O1, O2>(a: &BitSlice<T1, O1>, b: &BitSlice<T2, O2>)
+	where
+		T1: BitStore,
+		T2: BitStore,
+		O1: BitOrder,
+		O2: BitOrder,
+	{
+		let (and, or, xor, diff) = (a & b, a | b, a ^ b, a.and_not(b));
+		let not = !a;
+		for (idx, x) in a.iter().by_vals().enumerate() {
+			let y = b.get(idx).map_or(false, |bit| *bit);
+			assert_eq!(and[idx], x & y);
+			assert_eq!(or[idx], x | y);
+			assert_eq!(xor[idx], x ^ y);
+			assert_eq!(diff[idx], x & !y);
+			assert_eq!(not[idx], !x);
+		}
+		for out in [&and, &or, &xor, &diff, &not] {
+			assert_eq!(out.len(), a.len());
+		}
+
+		let mut c = a.to_bitvec();
+		c.andnot_assign(b);
+		assert_eq!(c, diff);
+	}
+
+	let a = random::<[u16; 8]>();
+	let b = random::<[u16; 8]>();
+	let c = random::<[u8; 16]>();
+
+	//  Same layout, whole elements and partial edges.
+	check(a.view_bits::<Lsb0>(), b.view_bits::<Lsb0>());
+	check(&a.view_bits::<Msb0>()[3 .. 101], &b.view_bits::<Msb0>()[3 ..]);
+	check(&a.view_bits::<HiLo>()[5 .. 12], &b.view_bits::<HiLo>()[5 .. 12]);
+	//  Shorter or misaligned right-hand sides.
+	check(&a.view_bits::<Lsb0>()[2 ..], &b.view_bits::<Lsb0>()[2 .. 50]);
+	check(&a.view_bits::<Msb0>()[1 ..], &b.view_bits::<Msb0>()[4 ..]);
+	check(&a.view_bits::<HiLo>()[7 ..], &b.view_bits::<HiLo>()[..]);
+	//  Different type parameters.
+	check(a.view_bits::<Lsb0>(), c.view_bits::<Msb0>());
+	check(&c.view_bits::<Msb0>()[9 ..], &a.view_bits::<Lsb0>()[9 .. 90]);
+}
+
 #[test]
 fn indexing() {
 	let bits = bits![mut 0, 1, 0, 0, 1];
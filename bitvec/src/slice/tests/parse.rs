@@ -0,0 +1,357 @@
+#![cfg(feature = "alloc")]
+
+use core::convert::TryFrom;
+
+use rand::random;
+
+use crate::{
+	mem,
+	prelude::*,
+	slice::{
+		ParseBitsError,
+		ParseBitsErrorKind,
+	},
+};
+
+fn err(src: &str) -> (usize, ParseBitsErrorKind) {
+	let err = src.parse::<BitVec>().unwrap_err();
+	(err.position(), *err.kind())
+}
+
+#[test]
+fn numbers() {
+	let bv = "0b1011_0010".parse::<BitVec<u8, Msb0>>().unwrap();
+	assert_eq!(bv, bits![1, 0, 1, 1, 0, 0, 1, 0]);
+	assert_eq!("1011_0010".parse::<BitVec>().unwrap(), bv);
+
+	let bv = "0o7_1".parse::<BitVec>().unwrap();
+	assert_eq!(bv, bits![1, 1, 1, 0, 0, 1]);
+
+	let bv = "0xdead_BEEF".parse::<BitVec<u16, Msb0>>().unwrap();
+	assert_eq!(bv.len(), 32);
+	assert_eq!(bv.load_be::<u32>(), 0xDEAD_BEEF);
+
+	let bb = "0x0F".parse::<BitBox<u8, Msb0>>().unwrap();
+	assert_eq!(bb, bits![0, 0, 0, 0, 1, 1, 1, 1]);
+}
+
+#[test]
+fn lists() {
+	let bv = "[0, 1, 1]".parse::<BitVec>().unwrap();
+	assert_eq!(bv, bits![0, 1, 1]);
+	assert!("[]".parse::<BitVec>().unwrap().is_empty());
+	assert!("[ ]".parse::<BitVec>().unwrap().is_empty());
+
+	let bv = "[\n    0xc,\n    0b1_01,\n]".parse::<BitVec<u8>>().unwrap();
+	assert_eq!(bv, bits![1, 1, 0, 0, 1, 0, 1]);
+
+	//  Entries are narrowed to the only width that their digits allow.
+	let bv = "[0o377, 0o57]".parse::<BitVec<u8>>().unwrap();
+	assert_eq!(bv[.. 8], bits![1; 8]);
+	assert_eq!(bv[8 ..], bits![1, 0, 1, 1, 1, 1]);
+	//  Interior entries are whole elements.
+	let bv = "[0b1, 0o017, 0b1]".parse::<BitVec<u8>>().unwrap();
+	assert_eq!(bv, bits![1, 0, 0, 0, 0, 1, 1, 1, 1, 1]);
+	//  Entries wider than an element keep every digit.
+	assert_eq!("[0o777]".parse::<BitVec<u8>>().unwrap().len(), 9);
+
+	//  Edge entries whose width cannot be told are rejected.
+	let err_u8 = "[0o377, 0o17]".parse::<BitVec<u8>>().unwrap_err();
+	assert_eq!(err_u8.position(), 8);
+	assert_eq!(err_u8.kind(), &ParseBitsErrorKind::Ambiguous);
+	assert_eq!(err("[0x3]"), (1, ParseBitsErrorKind::Ambiguous));
+}
+
+#[test]
+fn debug_header() {
+	let bv = "BitVec<u8, Msb0> { addr: 0x1, head: 000, bits: 2 } [1, 0]"
+		.parse::<BitVec<u8, Msb0>>()
+		.unwrap();
+	assert_eq!(bv, bits![1, 0]);
+
+	assert_eq!(err("Bit { bits: 2 } 10"), (16, ParseBitsErrorKind::Unexpected(
+		'1'
+	)));
+	assert_eq!(err("Bit { bits: 2 }"), (15, ParseBitsErrorKind::Unterminated));
+	assert_eq!(
+		err("Bit"),
+		(0, ParseBitsErrorKind::InvalidDigit {
+			digit: 'B',
+			radix: 2,
+		}),
+	);
+}
+
+#[test]
+fn round_trips() {
+	fn check<T, O>(bits: &BitSlice<T, O>)
+	where
+		T: BitStore,
+		O: BitOrder,
+	{
+		let bits = bits.to_bitvec();
+		let texts = [
+			format!("{}", bits),
+			format!("{:#}", bits),
+			format!("{:?}", bits),
+			format!("{:#?}", bits),
+			format!("{:b}", bits),
+			format!("{:#b}", bits),
+		];
+		for text in texts.iter() {
+			assert_eq!(text.parse::<BitVec<T::Unalias, O>>().unwrap(), bits);
+			assert_eq!(text.parse::<BitBox<T::Unalias, O>>().unwrap(), bits);
+		}
+		//  Octal and hexadecimal text reads back exactly, or not at all.
+		let texts = [
+			format!("{:#o}", bits),
+			format!("{:#x}", bits),
+			format!("{:#X}", bits),
+		];
+		for text in texts.iter() {
+			match text.parse::<BitVec<T::Unalias, O>>() {
+				Ok(parsed) => assert_eq!(parsed, bits),
+				Err(err) => {
+					assert_eq!(err.kind(), &ParseBitsErrorKind::Ambiguous)
+				},
+			}
+		}
+	}
+
+	let data = random::<[u8; 16]>();
+	check(data.view_bits::<Lsb0>());
+	check(data.view_bits::<Msb0>());
+	check(&data.view_bits::<Lsb0>()[3 .. 101]);
+	let data = random::<[u16; 8]>();
+	check(data.view_bits::<Lsb0>());
+	check(&data.view_bits::<Msb0>()[5 ..]);
+	let data = random::<[u32; 4]>();
+	check(data.view_bits::<Msb0>());
+	check(&data.view_bits::<Lsb0>()[.. 77]);
+	let data = random::<[u64; 2]>();
+	check(data.view_bits::<Lsb0>());
+	check(&data.view_bits::<Msb0>()[1 .. 127]);
+
+	let data = random::<[u32; 4]>();
+	let arr = BitArray::<_, Lsb0>::new(data);
+	for text in [
+		format!("{:#o}", arr),
+		format!("{:#x}", arr),
+		format!("{:#X}", arr),
+	] {
+		assert_eq!(BitArray::try_from(text.as_str()), Ok(arr));
+	}
+}
+
+#[test]
+fn every_formatter() {
+	fn check<T, O>()
+	where
+		T: BitStore,
+		O: BitOrder,
+	{
+		let elem = mem::bits_of::<T::Mem>();
+		for len in 1 ..= 3 * elem {
+			let bv = (0 .. len)
+				.map(|_| random::<bool>())
+				.collect::<BitVec<T, O>>();
+			for text in [
+				format!("{}", bv),
+				format!("{:#}", bv),
+				format!("{:?}", bv),
+				format!("{:#?}", bv),
+				format!("{:b}", bv),
+				format!("{:#b}", bv),
+			] {
+				let parsed = text.parse::<BitVec<T, O>>();
+				assert_eq!(parsed.unwrap(), bv, "{}", text);
+				let parsed = BitBox::<T, O>::from_str_radix_len(&text, 2, len);
+				assert_eq!(parsed.unwrap(), bv, "{}", text);
+			}
+			for (radix, text) in [
+				(8, format!("{:o}", bv)),
+				(8, format!("{:#o}", bv)),
+				(16, format!("{:x}", bv)),
+				(16, format!("{:X}", bv)),
+				(16, format!("{:#x}", bv)),
+				(16, format!("{:#X}", bv)),
+			] {
+				let parsed =
+					BitVec::<T, O>::from_str_radix_len(&text, radix, len);
+				assert_eq!(parsed.unwrap(), bv, "{}", text);
+			}
+		}
+	}
+
+	check::<u8, Lsb0>();
+	check::<u8, Msb0>();
+	check::<u16, Lsb0>();
+	check::<u16, Msb0>();
+	check::<u32, Lsb0>();
+	check::<u32, Msb0>();
+}
+
+#[test]
+fn radix() {
+	let bv = BitVec::<u8, Msb0>::from_str_radix("[b2, f]", 16).unwrap();
+	assert_eq!(bv, bits![1, 0, 1, 1, 0, 0, 1, 0, 1, 1, 1, 1]);
+	let bv = BitVec::<u8, Msb0>::from_str_radix_len("[262, 17]", 8, 12)
+		.unwrap();
+	assert_eq!(bv, bits![1, 0, 1, 1, 0, 0, 1, 0, 1, 1, 1, 1]);
+	//  Only the prefix of the given radix is skipped.
+	let bv = BitVec::<u16, Lsb0>::from_str_radix("0b1", 16).unwrap();
+	assert_eq!(bv.len(), 12);
+	assert_eq!(bv[4 .. 8], bits![1, 0, 1, 1]);
+	assert_eq!(BitVec::<u8>::from_str_radix("0x0f", 16).unwrap().len(), 8);
+
+	let err = BitVec::<u8>::from_str_radix("0o7", 16).unwrap_err();
+	assert_eq!(err.position(), 1);
+	assert_eq!(err.kind(), &ParseBitsErrorKind::InvalidDigit {
+		digit: 'o',
+		radix: 16,
+	});
+
+	let arr = bitarr![u16, Msb0; 1, 0, 1, 1, 0, 0, 1, 0];
+	let text = format!("{:o}", arr);
+	assert_eq!(BitArray::from_str_radix(&text, 8), Ok(arr));
+}
+
+#[test]
+#[should_panic]
+fn bad_radix() {
+	BitVec::<u8>::from_str_radix("0", 10).ok();
+}
+
+#[test]
+fn whitespace() {
+	let bv = " [10110010, 1111] ".parse::<BitVec<u8, Msb0>>().unwrap();
+	assert_eq!(bv, bits![1, 0, 1, 1, 0, 0, 1, 0, 1, 1, 1, 1]);
+	let bv = "\n\t0x3c\n".parse::<BitVec<u8, Msb0>>().unwrap();
+	assert_eq!(bv, bits![0, 0, 1, 1, 1, 1, 0, 0]);
+	assert!("  ".parse::<BitVec>().is_err());
+}
+
+#[test]
+fn partial_elements() {
+	let bv = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 1, 1, 0, 1, 0, 1];
+	for text in [
+		format!("{:b}", bv),
+		format!("{:?}", bv),
+		format!("{:#?}", bv),
+	] {
+		assert_eq!(text.parse::<BitVec<u8, Msb0>>().unwrap(), bv);
+	}
+
+	//  The tail `1_0101` prints the same as the whole element `0001_0101`.
+	let text = format!("{:#x}", bv);
+	assert_eq!(text, "[\n    0xb3,\n    0x15,\n]");
+	let err = text.parse::<BitVec<u8, Msb0>>().unwrap_err();
+	assert_eq!(err.position(), 16);
+	assert_eq!(err.kind(), &ParseBitsErrorKind::Ambiguous);
+	let text = format!("{:#o}", bv);
+	assert_eq!(text, "[\n    0o263,\n    0o25,\n]");
+	let err = text.parse::<BitVec<u8, Msb0>>().unwrap_err();
+	assert_eq!(err.position(), 17);
+	assert_eq!(err.kind(), &ParseBitsErrorKind::Ambiguous);
+	//  Knowing the length settles the width of the tail.
+	let parsed = BitVec::<u8, Msb0>::from_str_radix_len(&text, 8, 13);
+	assert_eq!(parsed.unwrap(), bv);
+	let parsed = BitBox::<u8, Msb0>::from_str_radix_len("[b3, 15]", 16, 13);
+	assert_eq!(parsed.unwrap(), bv);
+	let err = BitVec::<u8, Msb0>::from_str_radix_len("0xb3", 16, 9)
+		.unwrap_err();
+	assert_eq!(err.kind(), &ParseBitsErrorKind::Length {
+		actual:   8,
+		expected: 9,
+	});
+
+	//  Tails whose leading digit is full read back exactly.
+	let bv = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 1, 1, 0, 1, 0];
+	let text = format!("{:#x}", bv);
+	assert_eq!(text, "[\n    0xb3,\n    0xa,\n]");
+	assert_eq!(text.parse::<BitVec<u8, Msb0>>().unwrap(), bv);
+	let bv = bitvec![u16, Lsb0; 1, 1, 0, 1, 0, 0, 1, 0, 1, 1, 0, 1];
+	let text = format!("{:#o}", bv);
+	assert_eq!(text.parse::<BitVec<u16, Lsb0>>().unwrap(), bv);
+
+	//  A bit-array reads every entry as a whole element.
+	let arr = bitarr![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 1, 1, 0, 1, 0, 1];
+	let text = format!("{:#x}", arr);
+	assert_eq!(text, "[\n    0xb3,\n    0xa8,\n]");
+	assert_eq!(BitArray::try_from(text.as_str()), Ok(arr));
+}
+
+#[test]
+fn arrays() {
+	let arr = BitArray::<[u8; 2], Msb0>::try_from("0xa5_0f").unwrap();
+	assert_eq!(arr.into_inner(), [0xA5, 0x0F]);
+
+	let data = bitarr![u16, Lsb0; 1, 0, 1, 1, 0, 0, 1];
+	let text = format!("{:#o}", data);
+	assert_eq!(BitArray::try_from(text.as_str()), Ok(data));
+
+	let err = BitArray::<[u8; 1], Msb0>::try_from("0x1").unwrap_err();
+	assert_eq!(err.position(), 3);
+	assert_eq!(err.kind(), &ParseBitsErrorKind::Length {
+		actual:   4,
+		expected: 8,
+	});
+	let err = BitArray::<[u8; 1], Msb0>::try_from("[0x12, 0b1]").unwrap_err();
+	assert_eq!(err.position(), 9);
+	assert_eq!(err.kind(), &ParseBitsErrorKind::Length {
+		actual:   9,
+		expected: 8,
+	});
+}
+
+#[test]
+fn errors() {
+	assert_eq!(err(""), (0, ParseBitsErrorKind::Empty));
+	assert_eq!(err("0x"), (2, ParseBitsErrorKind::Empty));
+	assert_eq!(err("0b__"), (4, ParseBitsErrorKind::Empty));
+	assert_eq!(
+		err("0b102"),
+		(4, ParseBitsErrorKind::InvalidDigit {
+			digit: '2',
+			radix: 2,
+		}),
+	);
+	assert_eq!(
+		err("0o18"),
+		(3, ParseBitsErrorKind::InvalidDigit {
+			digit: '8',
+			radix: 8,
+		}),
+	);
+	assert_eq!(
+		err("0xfé"),
+		(3, ParseBitsErrorKind::InvalidDigit {
+			digit: 'é',
+			radix: 16,
+		}),
+	);
+	assert_eq!(
+		err("1 0"),
+		(1, ParseBitsErrorKind::InvalidDigit {
+			digit: ' ',
+			radix: 2,
+		}),
+	);
+	assert_eq!(err("[0, , 1]"), (4, ParseBitsErrorKind::Empty));
+	assert_eq!(err("[0 1]"), (3, ParseBitsErrorKind::Unexpected('1')));
+	assert_eq!(err("[0, 1] x"), (7, ParseBitsErrorKind::Unexpected('x')));
+	assert_eq!(err("[0, 1"), (5, ParseBitsErrorKind::Unterminated));
+	assert_eq!(err("[0,"), (3, ParseBitsErrorKind::Unterminated));
+	assert_eq!(err("["), (1, ParseBitsErrorKind::Unterminated));
+
+	let err: ParseBitsError = "0b2".parse::<BitVec>().unwrap_err();
+	assert_eq!(
+		format!("{}", err),
+		"invalid digit '2' for radix 2 at byte 2",
+	);
+	let err: ParseBitsError = "[0x1]".parse::<BitVec>().unwrap_err();
+	assert_eq!(
+		format!("{}", err),
+		"cannot tell how many bits the entry at byte 1 holds",
+	);
+}
@@ -103,7 +103,13 @@ where
 {
 	#[inline]
 	fn eq(&self, rhs: &BitSlice<T2, O2>) -> bool {
-		if let (Some(this), Some(that)) =
+		if let Some(eq) = rhs
+			.coerce::<T1, O1>()
+			.and_then(|that| self.eq_domain(that))
+		{
+			eq
+		}
+		else if let (Some(this), Some(that)) =
 			(self.coerce::<T1, Lsb0>(), rhs.coerce::<T1, Lsb0>())
 		{
 			this.sp_eq(that)
@@ -196,6 +196,9 @@ store!(u64 => BitSafeU64);
 
 store!(usize => BitSafeUsize);
 
+#[cfg(feature = "u128")]
+store!(u128 => BitSafeU128);
+
 /// Generates `BitStore` implementations for atomic types.
 macro_rules! atomic {
 	($($size:tt, $base:ty => $atom:ident);+ $(;)?) => { $(
@@ -203,6 +206,10 @@ macro_rules! atomic {
 		use core::sync::atomic::$atom;
 
 		#[cfg(target_has_atomic = $size)]
+		atomic!(impl $base => $atom);
+	)+ };
+
+	(impl $base:ty => $atom:ty) => {
 		impl BitStore for $atom {
 			type Mem = $base;
 			type Access = Self;
@@ -229,7 +236,7 @@ macro_rules! atomic {
 
 			const ALIAS_WIDTH: [(); 1] = [()];
 		}
-	)+ };
+	};
 }
 
 atomic! {
@@ -243,6 +250,15 @@ atomic!("64", u64 => AtomicU64);
 
 atomic!("ptr", usize => AtomicUsize);
 
+//  `core` has no stable 128-bit atomic, but `radium` provides one on targets
+//  with a double-width compare-exchange.
+#[cfg(all(
+	feature = "u128",
+	target_arch = "x86_64",
+	target_feature = "cmpxchg16b",
+))]
+atomic!(impl u128 => radium::types::AtomicU128);
+
 #[cfg(test)]
 mod tests {
 	use static_assertions::*;
@@ -283,6 +299,9 @@ mod tests {
 
 		#[cfg(target_pointer_width = "64")]
 		assert_impl_all!(BitSlice<u64, LocalBits>: Send, Sync);
+
+		#[cfg(feature = "u128")]
+		assert_impl_all!(BitSlice<u128, LocalBits>: Send, Sync);
 	}
 
 	#[test]
@@ -294,6 +313,9 @@ mod tests {
 
 		#[cfg(target_pointer_width = "64")]
 		assert_not_impl_any!(BitSlice<Cell<u64>, LocalBits>: Send, Sync);
+
+		#[cfg(feature = "u128")]
+		assert_not_impl_any!(BitSlice<Cell<u128>, LocalBits>: Send, Sync);
 	}
 
 	/// In non-atomic builds, aliased `BitSlice`s become universally
@@ -313,6 +335,9 @@ mod tests {
 
 		#[cfg(target_pointer_width = "64")]
 		assert_not_impl_any!(BitSlice<BitSafeU64, LocalBits>: Send, Sync);
+
+		#[cfg(feature = "u128")]
+		assert_not_impl_any!(BitSlice<BitSafeU128, LocalBits>: Send, Sync);
 	}
 
 	#[test]
@@ -325,5 +350,14 @@ mod tests {
 
 		#[cfg(target_pointer_width = "64")]
 		assert_impl_all!(BitSlice<AtomicU64, LocalBits>: Send, Sync);
+
+		#[cfg(all(
+			feature = "u128",
+			target_arch = "x86_64",
+			target_feature = "cmpxchg16b",
+		))]
+		assert_impl_all!(
+			BitSlice<radium::types::AtomicU128, LocalBits>: Send, Sync
+		);
 	}
 }
@@ -38,7 +38,11 @@ use crate::{
 		BitSpan,
 		BitSpanError,
 	},
-	slice::BitSlice,
+	slice::{
+		parse::Syntax,
+		BitSlice,
+		ParseBitsError,
+	},
 	store::BitStore,
 	view::BitView,
 };
@@ -250,6 +254,71 @@ where
 			.map_err(|_| ManuallyDrop::into_inner(vec))
 	}
 
+	/// Parses the text that a numeric formatter writes, in a given radix.
+	///
+	/// This accepts the same text as [`FromStr`], except that numbers without
+	/// a prefix are read in `radix`, as `{:o}` and `{:x}` write them, rather
+	/// than in binary. Numbers may still carry the prefix of `radix`.
+	///
+	/// ## Panics
+	///
+	/// This panics if `radix` is not 2, 8, or 16.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bv = BitVec::<u8, Msb0>::from_str_radix("[b2, f]", 16).unwrap();
+	/// assert_eq!(bv, bits![1, 0, 1, 1, 0, 0, 1, 0, 1, 1, 1, 1]);
+	/// ```
+	///
+	/// [`FromStr`]: core::str::FromStr
+	#[inline]
+	pub fn from_str_radix(
+		src: &str,
+		radix: u32,
+	) -> Result<Self, ParseBitsError> {
+		Self::parse_text(src, Syntax::new(bits_of::<T::Mem>()).radix(radix))
+	}
+
+	/// Parses the text that a numeric formatter writes for a bit-vector of
+	/// known length.
+	///
+	/// The formatters do not record how many bits a partially-filled edge
+	/// element holds, so [`from_str_radix`] cannot always tell. Knowing the
+	/// length, the edge entries are read as however many low bits make it up.
+	///
+	/// ## Panics
+	///
+	/// This panics if `radix` is not 2, 8, or 16.
+	///
+	/// ## Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bv = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 1, 1, 0, 1, 0, 1];
+	/// let text = format!("{:#x}", bv);
+	/// assert!(BitVec::<u8, Msb0>::from_str_radix(&text, 16).is_err());
+	///
+	/// let parsed = BitVec::<u8, Msb0>::from_str_radix_len(&text, 16, 13);
+	/// assert_eq!(parsed.unwrap(), bv);
+	/// ```
+	///
+	/// [`from_str_radix`]: Self::from_str_radix
+	#[inline]
+	pub fn from_str_radix_len(
+		src: &str,
+		radix: u32,
+		len: usize,
+	) -> Result<Self, ParseBitsError> {
+		Self::parse_text(
+			src,
+			Syntax::new(bits_of::<T::Mem>()).radix(radix).len(len),
+		)
+	}
+
 	/// Appends the contents of a bit-slice to a bit-vector.
 	///
 	/// This can extend from a bit-slice of any type parameters; it is not
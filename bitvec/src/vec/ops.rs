@@ -14,6 +14,10 @@ use core::{
 		Index,
 		IndexMut,
 		Not,
+		Shl,
+		ShlAssign,
+		Shr,
+		ShrAssign,
 	},
 };
 
@@ -270,3 +274,65 @@ where
 		self
 	}
 }
+
+#[cfg(not(tarpaulin_include))]
+impl<T, O> Shl<usize> for BitVec<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	type Output = Self;
+
+	#[inline]
+	fn shl(mut self, rhs: usize) -> Self::Output {
+		self <<= rhs;
+		self
+	}
+}
+
+/// Shifts the contents of the bit-vector towards the zero index.
+///
+/// This is [`BitSlice::shift_start`], except that shifting by the length of
+/// the bit-vector or more clears it rather than panicking.
+impl<T, O> ShlAssign<usize> for BitVec<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn shl_assign(&mut self, rhs: usize) {
+		let bits = self.as_mut_bitslice();
+		bits.shift_start(rhs.min(bits.len()));
+	}
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<T, O> Shr<usize> for BitVec<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	type Output = Self;
+
+	#[inline]
+	fn shr(mut self, rhs: usize) -> Self::Output {
+		self >>= rhs;
+		self
+	}
+}
+
+/// Shifts the contents of the bit-vector away from the zero index.
+///
+/// This is [`BitSlice::shift_end`], except that shifting by the length of
+/// the bit-vector or more clears it rather than panicking.
+impl<T, O> ShrAssign<usize> for BitVec<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	#[inline]
+	fn shr_assign(&mut self, rhs: usize) {
+		let bits = self.as_mut_bitslice();
+		bits.shift_end(rhs.min(bits.len()));
+	}
+}
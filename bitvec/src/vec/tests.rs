@@ -78,4 +78,13 @@ fn misc() {
 	bv.force_align();
 
 	assert_eq!(!bitvec![0, 1], bits![1, 0]);
+
+	let mut bv = bitvec![u16, Lsb0; 1; 40];
+	bv <<= 33;
+	assert_eq!(bv.count_ones(), 7);
+	assert!(bv[.. 7].all());
+	bv >>= 35;
+	assert_eq!(bv.count_ones(), 5);
+	assert!(bv[35 ..].all());
+	assert!((bv >> 40).not_any());
 }
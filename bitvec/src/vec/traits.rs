@@ -25,14 +25,24 @@ use core::{
 		Hasher,
 	},
 	marker::Unpin,
+	str::FromStr,
 };
 
 use super::BitVec;
 use crate::{
 	array::BitArray,
 	boxed::BitBox,
+	mem,
 	order::BitOrder,
-	slice::BitSlice,
+	slice::{
+		parse::{
+			self,
+			Syntax,
+		},
+		BitSlice,
+		ParseBitsError,
+		ParseBitsErrorKind,
+	},
 	store::BitStore,
 	view::BitViewSized,
 };
@@ -336,6 +346,42 @@ where
 	}
 }
 
+#[doc = include_str!("../../doc/slice/from_str.md")]
+impl<T, O> FromStr for BitVec<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	type Err = ParseBitsError;
+
+	#[inline]
+	fn from_str(src: &str) -> Result<Self, Self::Err> {
+		Self::parse_text(src, Syntax::new(mem::bits_of::<T::Mem>()))
+	}
+}
+
+impl<T, O> BitVec<T, O>
+where
+	T: BitStore,
+	O: BitOrder,
+{
+	/// Parses text, requiring that it holds the length that `syntax` names.
+	pub(crate) fn parse_text(
+		src: &str,
+		syntax: Syntax,
+	) -> Result<Self, ParseBitsError> {
+		let mut out = Self::new();
+		let actual = parse::parse(src, syntax, |bit, _| out.push(bit))?;
+		match syntax.len {
+			Some(expected) if actual != expected => Err(ParseBitsError::new(
+				ParseBitsErrorKind::Length { actual, expected },
+				src.len(),
+			)),
+			_ => Ok(out),
+		}
+	}
+}
+
 #[cfg(not(tarpaulin_include))]
 impl<T, O> Default for BitVec<T, O>
 where
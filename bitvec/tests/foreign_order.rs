@@ -64,3 +64,16 @@ fn verify_u64() {
 fn verify_usize() {
 	verify_for_type::<usize, Swizzle>(cfg!(feature = "verbose"));
 }
+
+#[test]
+fn images() {
+	let bits = (0 .. 20)
+		.map(|n| n % 3 == 0)
+		.collect::<BitVec<u16, Swizzle>>();
+	let mut buf = vec![0; bits.encoded_len()];
+	bits.encode_into(&mut buf).unwrap();
+
+	//  Foreign orderings are re-laid out as `Lsb0` elements.
+	assert_eq!(BitVec::<u16, Lsb0>::decode(&buf).unwrap(), bits);
+	assert_eq!(BitVec::<u8, Swizzle>::decode(&buf).unwrap(), bits);
+}
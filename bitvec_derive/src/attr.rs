@@ -0,0 +1,202 @@
+//! Parsing for the `#[bits(…)]` helper attribute.
+
+use proc_macro2::Span;
+use syn::{
+	parse::ParseStream,
+	Attribute,
+	Ident,
+	LitInt,
+	Path,
+	Token,
+	Type,
+};
+
+/// The element ordering used when a bit-slice spans more than one element.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Endian {
+	/// Use `load_le`/`store_le`.
+	Little,
+	/// Use `load_be`/`store_be`.
+	Big,
+}
+
+impl Endian {
+	/// Parses `le` or `be` from the value side of an `endian = …` pair.
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let ident = input.parse::<Ident>()?;
+		match ident.to_string().as_str() {
+			"le" => Ok(Self::Little),
+			"be" => Ok(Self::Big),
+			_ => Err(syn::Error::new(
+				ident.span(),
+				"expected `le` or `be` for the element endianness",
+			)),
+		}
+	}
+}
+
+/// Options set on the struct itself with `#[bits(…)]`.
+#[derive(Default)]
+pub struct Container {
+	/// The `BitOrder` type parameter of the generated bit-array.
+	pub order:  Option<Path>,
+	/// The `BitStore` type parameter of the generated bit-array.
+	pub store:  Option<Type>,
+	/// The default endianness for fields that do not set their own.
+	pub endian: Option<Endian>,
+	/// The total bit-length of the generated bit-array.
+	pub len:    Option<(usize, Span)>,
+	/// The name of the generated type.
+	pub name:   Option<Ident>,
+}
+
+impl Container {
+	/// Collects every `#[bits(…)]` attribute on an item.
+	pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+		let mut this = Self::default();
+		for attr in attrs.iter().filter(|attr| attr.path().is_ident("bits")) {
+			attr.parse_args_with(|input: ParseStream| {
+				while !input.is_empty() {
+					let key = input.parse::<Ident>()?;
+					input.parse::<Token![=]>()?;
+					match key.to_string().as_str() {
+						"order" => this.order = Some(input.parse()?),
+						"store" => this.store = Some(input.parse()?),
+						"endian" => this.endian = Some(Endian::parse(input)?),
+						"len" => {
+							let lit = input.parse::<LitInt>()?;
+							this.len = Some((lit.base10_parse()?, lit.span()));
+						},
+						"name" => this.name = Some(input.parse()?),
+						_ => {
+							return Err(syn::Error::new(
+								key.span(),
+								"expected one of `order`, `store`, `endian`, \
+								 `len`, or `name`",
+							));
+						},
+					}
+					if !input.is_empty() {
+						input.parse::<Token![,]>()?;
+					}
+				}
+				Ok(())
+			})?;
+		}
+		Ok(this)
+	}
+}
+
+/// Options set on a struct field with `#[bits(…)]`.
+pub struct Field {
+	/// The first bit index of the field.
+	pub start:  usize,
+	/// The index one past the last bit of the field.
+	pub end:    usize,
+	/// The span of the range, used to report range errors.
+	pub span:   Span,
+	/// The endianness of this field, if it overrides the container.
+	pub endian: Option<Endian>,
+	/// The bit-ordering in which this field’s range is counted, if it
+	/// differs from the container.
+	pub order:  Option<Path>,
+}
+
+impl Field {
+	/// Finds and parses the `#[bits(…)]` attribute on a struct field.
+	///
+	/// The attribute begins with a bit range, written as `start .. end`,
+	/// `start ..= last`, or a single index `bit`, and may be followed by
+	/// `endian = le` or `endian = be`, and by `order = Lsb0` or `order = Msb0`.
+	pub fn parse(attrs: &[Attribute], span: Span) -> syn::Result<Self> {
+		let mut attrs = attrs.iter().filter(|attr| attr.path().is_ident("bits"));
+		let attr = attrs.next().ok_or_else(|| {
+			syn::Error::new(
+				span,
+				"every field must have a `#[bits(…)]` attribute naming its \
+				 bit range",
+			)
+		})?;
+		if let Some(extra) = attrs.next() {
+			return Err(syn::Error::new_spanned(
+				extra,
+				"a field may only have one `#[bits(…)]` attribute",
+			));
+		}
+
+		attr.parse_args_with(|input: ParseStream| {
+			let first = input.parse::<LitInt>()?;
+			let start = first.base10_parse::<usize>()?;
+			let mut span = first.span();
+			let end = if input.peek(Token![..=]) {
+				input.parse::<Token![..=]>()?;
+				let last = input.parse::<LitInt>()?;
+				span = span.join(last.span()).unwrap_or(span);
+				past(&last)?
+			}
+			else if input.peek(Token![..]) {
+				input.parse::<Token![..]>()?;
+				let end = input.parse::<LitInt>()?;
+				span = span.join(end.span()).unwrap_or(span);
+				end.base10_parse::<usize>()?
+			}
+			else {
+				past(&first)?
+			};
+
+			let mut endian = None;
+			let mut order = None;
+			while !input.is_empty() {
+				input.parse::<Token![,]>()?;
+				if input.is_empty() {
+					break;
+				}
+				let key = input.parse::<Ident>()?;
+				input.parse::<Token![=]>()?;
+				match key.to_string().as_str() {
+					"endian" => endian = Some(Endian::parse(input)?),
+					"order" => order = Some(input.parse()?),
+					"store" | "len" | "name" => {
+						return Err(syn::Error::new(
+							key.span(),
+							format!(
+								"`{}` applies to the whole bit-array, and must \
+								 be set on the struct",
+								key,
+							),
+						));
+					},
+					_ => {
+						return Err(syn::Error::new(
+							key.span(),
+							"expected `endian` or `order`",
+						));
+					},
+				}
+			}
+
+			Ok(Self {
+				start,
+				end,
+				span,
+				endian,
+				order,
+			})
+		})
+	}
+
+	/// The number of bits in the field.
+	pub fn width(&self) -> usize {
+		self.end.saturating_sub(self.start)
+	}
+}
+
+/// Computes the index one past the bit named by `last`.
+fn past(last: &LitInt) -> syn::Result<usize> {
+	last.base10_parse::<usize>()?.checked_add(1).ok_or_else(|| {
+		syn::Error::new(
+			last.span(),
+			"the bit range must end before `usize::MAX`",
+		)
+	})
+}
@@ -0,0 +1,644 @@
+//! Code generation for `#[derive(BitFields)]`.
+
+use core::ops::Range;
+
+use proc_macro2::TokenStream;
+use quote::{
+	format_ident,
+	quote,
+	quote_spanned,
+};
+use syn::{
+	ext::IdentExt,
+	parenthesized,
+	spanned::Spanned,
+	token::Paren,
+	Data,
+	DataEnum,
+	DataStruct,
+	DeriveInput,
+	Fields,
+	Ident,
+	Path,
+	Type,
+};
+
+use crate::attr::{
+	Container,
+	Endian,
+	Field,
+};
+
+/// Dispatches on the shape of the annotated item.
+pub fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+	if !input.generics.params.is_empty() {
+		return Err(syn::Error::new_spanned(
+			&input.generics,
+			"`BitFields` cannot be derived for generic types",
+		));
+	}
+	match &input.data {
+		Data::Struct(data) => derive_struct(input, data),
+		Data::Enum(data) => derive_enum(input, data),
+		Data::Union(data) => Err(syn::Error::new(
+			data.union_token.span,
+			"`BitFields` cannot be derived for unions",
+		)),
+	}
+}
+
+/// How a field’s value is moved in and out of the bit-array.
+enum Kind {
+	/// A single bit, accessed by index.
+	Bool,
+	/// A primitive integer, transferred directly through `BitField`. The
+	/// width is `None` for the pointer-sized integers.
+	Int(Option<usize>),
+	/// Any other type, transferred through its `FieldValue` representation.
+	Value,
+}
+
+impl Kind {
+	/// Classifies a field by the name of its type.
+	fn of(ty: &Type) -> Self {
+		let ident = match ty {
+			Type::Path(path) if path.qself.is_none() => {
+				match path.path.get_ident() {
+					Some(ident) => ident.to_string(),
+					None => return Self::Value,
+				}
+			},
+			_ => return Self::Value,
+		};
+		match ident.as_str() {
+			"bool" => Self::Bool,
+			"u8" | "i8" => Self::Int(Some(8)),
+			"u16" | "i16" => Self::Int(Some(16)),
+			"u32" | "i32" => Self::Int(Some(32)),
+			"u64" | "i64" => Self::Int(Some(64)),
+			"u128" | "i128" => Self::Int(Some(128)),
+			"usize" | "isize" => Self::Int(None),
+			_ => Self::Value,
+		}
+	}
+}
+
+/// A struct field paired with its parsed attribute.
+struct Member<'a> {
+	/// The field’s name.
+	ident: &'a Ident,
+	/// The field’s type.
+	ty:    &'a Type,
+	/// The field’s visibility, which the generated accessors inherit.
+	vis:   &'a syn::Visibility,
+	/// The parsed `#[bits(…)]` attribute.
+	bits:  Field,
+	/// How the field is transferred.
+	kind:  Kind,
+	/// The bits that the field occupies, counted in the container’s ordering.
+	place: Vec<Range<usize>>,
+	/// The field’s own bit-ordering, when it differs from the container’s.
+	order: Option<TokenStream>,
+}
+
+/// Generates the packed type, its accessors, and its conversions.
+fn derive_struct(
+	input: &DeriveInput,
+	data: &DataStruct,
+) -> syn::Result<TokenStream> {
+	let fields = match &data.fields {
+		Fields::Named(fields) => &fields.named,
+		_ => {
+			return Err(syn::Error::new(
+				data.struct_token.span,
+				"`BitFields` can only be derived for structs with named fields",
+			));
+		},
+	};
+
+	let container = Container::parse(&input.attrs)?;
+	let mut errors = Vec::<syn::Error>::new();
+	let mut members = Vec::with_capacity(fields.len());
+	for field in fields {
+		let ident = field.ident.as_ref().expect("named fields have names");
+		let bits = match Field::parse(&field.attrs, ident.span()) {
+			Ok(bits) => bits,
+			Err(err) => {
+				errors.push(err);
+				continue;
+			},
+		};
+		let (place, order) = match placement(&bits, &container) {
+			Ok(placed) => placed,
+			Err(err) => {
+				errors.push(err);
+				continue;
+			},
+		};
+		members.push(Member {
+			ident,
+			ty: &field.ty,
+			vis: &field.vis,
+			bits,
+			kind: Kind::of(&field.ty),
+			place,
+			order,
+		});
+	}
+
+	let len = container
+		.len
+		.map(|(len, _)| len)
+		.or_else(|| members.iter().flat_map(|m| &m.place).map(|r| r.end).max())
+		.unwrap_or(0);
+	for (idx, member) in members.iter().enumerate() {
+		check(member, len, container.len.is_some(), &mut errors);
+		for prev in &members[.. idx] {
+			let (a, b) = (&prev.bits, &member.bits);
+			let overlaps = prev.place.iter().any(|x| {
+				member.place.iter().any(|y| x.start < y.end && y.start < x.end)
+			});
+			if overlaps {
+				errors.push(syn::Error::new(
+					b.span,
+					format!(
+						"bits {} .. {} of `{}` overlap bits {} .. {} of `{}`",
+						b.start, b.end, member.ident, a.start, a.end, prev.ident,
+					),
+				));
+			}
+		}
+	}
+	if let Some(err) = errors.into_iter().reduce(|mut acc, err| {
+		acc.combine(err);
+		acc
+	}) {
+		return Err(err);
+	}
+
+	let ident = &input.ident;
+	let vis = &input.vis;
+	let name = container
+		.name
+		.clone()
+		.unwrap_or_else(|| format_ident!("{}Bits", ident));
+	let store = container
+		.store
+		.as_ref()
+		.map(|store| quote!(#store))
+		.unwrap_or_else(|| quote!(u8));
+	let order = order_path(container.order.as_ref());
+	let endian = container.endian.unwrap_or(Endian::Big);
+	let array = quote! {
+		::bitvec::array::BitArray<
+			[#store; ::bitvec::mem::elts::<#store>(#len)],
+			#order,
+		>
+	};
+
+	let accessors = members.iter().map(|m| accessors(m, endian));
+	let asserts = members.iter().filter_map(width_assert);
+	let packs = members.iter().map(|m| {
+		let setter = setter_name(m.ident);
+		let field = m.ident;
+		quote!(this.#setter(value.#field);)
+	});
+	let fallible = members.iter().any(|m| matches!(m.kind, Kind::Value));
+	let unpack = if fallible {
+		let inits = members.iter().map(|m| {
+			let field = m.ident;
+			match m.kind {
+				Kind::Value => quote! {
+					#field: match bits.#field() {
+						::core::option::Option::Some(value) => value,
+						::core::option::Option::None => {
+							return ::core::result::Result::Err(bits);
+						},
+					},
+				},
+				_ => quote!(#field: bits.#field(),),
+			}
+		});
+		quote! {
+			impl ::core::convert::TryFrom<#name> for #ident {
+				type Error = #name;
+
+				#[inline]
+				fn try_from(
+					bits: #name,
+				) -> ::core::result::Result<Self, Self::Error> {
+					::core::result::Result::Ok(Self { #(#inits)* })
+				}
+			}
+		}
+	}
+	else {
+		let inits = members.iter().map(|m| {
+			let field = m.ident;
+			quote!(#field: bits.#field(),)
+		});
+		quote! {
+			impl ::core::convert::From<#name> for #ident {
+				#[inline]
+				fn from(bits: #name) -> Self {
+					Self { #(#inits)* }
+				}
+			}
+		}
+	};
+
+	let doc = format!("The packed bit-field representation of [`{}`].", ident);
+	Ok(quote! {
+		#[doc = #doc]
+		#[repr(transparent)]
+		#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+		#vis struct #name {
+			bits: #array,
+		}
+
+		#(#asserts)*
+
+		impl #name {
+			/// The number of bits in the packed representation.
+			pub const LEN: usize = #len;
+
+			/// A packed value with every bit cleared.
+			pub const ZERO: Self = Self {
+				bits: ::bitvec::array::BitArray::ZERO,
+			};
+
+			/// Wraps a bit-array as a packed value.
+			#[inline]
+			pub const fn from_bitarray(bits: #array) -> Self {
+				Self { bits }
+			}
+
+			/// Unwraps the packed value into its bit-array.
+			#[inline]
+			pub fn into_bitarray(self) -> #array {
+				self.bits
+			}
+
+			/// Views the packed value as its bit-array.
+			#[inline]
+			pub fn as_bitarray(&self) -> &#array {
+				&self.bits
+			}
+
+			/// Views the packed value as a mutable bit-array.
+			#[inline]
+			pub fn as_mut_bitarray(&mut self) -> &mut #array {
+				&mut self.bits
+			}
+
+			#(#accessors)*
+		}
+
+		impl ::core::convert::From<#ident> for #name {
+			#[inline]
+			fn from(value: #ident) -> Self {
+				let mut this = Self::ZERO;
+				#(#packs)*
+				this
+			}
+		}
+
+		#unpack
+	})
+}
+
+/// Checks the parts of a field that can be known before type-checking.
+fn check(member: &Member, len: usize, fixed: bool, errors: &mut Vec<syn::Error>) {
+	let bits = &member.bits;
+	let width = bits.width();
+	if width == 0 {
+		errors.push(syn::Error::new(
+			bits.span,
+			format!("the bit range of `{}` is empty", member.ident),
+		));
+		return;
+	}
+	if fixed && member.place.iter().any(|range| range.end > len) {
+		errors.push(syn::Error::new(
+			bits.span,
+			format!(
+				"bits {} .. {} of `{}` are outside the {}-bit container",
+				bits.start, bits.end, member.ident, len,
+			),
+		));
+	}
+	match member.kind {
+		Kind::Bool if width != 1 => errors.push(syn::Error::new(
+			bits.span,
+			format!(
+				"`bool` fields must be exactly one bit wide, but `{}` is {}",
+				member.ident, width,
+			),
+		)),
+		Kind::Int(Some(max)) if width > max => {
+			let ty = member.ty;
+			errors.push(syn::Error::new(
+				bits.span,
+				format!(
+					"`{}` is {} bits wide, which does not fit in `{}`",
+					member.ident,
+					width,
+					quote!(#ty),
+				),
+			));
+		},
+		_ => {},
+	}
+}
+
+/// Emits a `const` assertion for field widths that depend on the target or on
+/// a `FieldValue` implementation.
+fn width_assert(member: &Member) -> Option<TokenStream> {
+	let ty = member.ty;
+	let repr = match member.kind {
+		Kind::Int(None) => quote!(#ty),
+		Kind::Value => quote!(<#ty as ::bitvec::field::FieldValue>::Repr),
+		_ => return None,
+	};
+	let width = member.bits.width();
+	let msg = format!(
+		"`{}` is {} bits wide, which does not fit in its representation",
+		member.ident, width,
+	);
+	Some(quote_spanned! {member.bits.span=>
+		const _: () = ::core::assert!(
+			#width <= ::bitvec::mem::bits_of::<#repr>(),
+			#msg,
+		);
+	})
+}
+
+/// Generates the getter and setter for one field.
+fn accessors(member: &Member, default: Endian) -> TokenStream {
+	let Member {
+		ident, ty, vis, ..
+	} = member;
+	let Field {
+		start, end, ..
+	} = member.bits;
+	let setter = setter_name(ident);
+	let range = if end - start == 1 {
+		format!("bit {}", start)
+	}
+	else {
+		format!("bits {} .. {}", start, end)
+	};
+	let get_doc = format!("Loads the `{}` field from {}.", ident.unraw(), range);
+	let set_doc = format!("Stores the `{}` field into {}.", ident.unraw(), range);
+	let width = (end - start) as u32;
+	let (load, store) = match member.bits.endian.unwrap_or(default) {
+		Endian::Little => (quote!(load_le), quote!(store_le)),
+		Endian::Big => (quote!(load_be), quote!(store_be)),
+	};
+	//  A field in its own ordering is reached through a view of the storage
+	//  in that ordering, rather than through the bit-array.
+	let (bits, bits_mut) = match &member.order {
+		None => (quote!(self.bits), quote!(self.bits)),
+		Some(order) => (
+			quote! {
+				::bitvec::view::BitView::view_bits::<#order>(
+					self.bits.as_raw_slice(),
+				)
+			},
+			quote! {
+				::bitvec::view::BitView::view_bits_mut::<#order>(
+					self.bits.as_raw_mut_slice(),
+				)
+			},
+		),
+	};
+
+	match member.kind {
+		Kind::Bool => quote! {
+			#[doc = #get_doc]
+			#[inline]
+			#vis fn #ident(&self) -> bool {
+				#bits[#start]
+			}
+
+			#[doc = #set_doc]
+			#[inline]
+			#vis fn #setter(&mut self, value: bool) {
+				#bits_mut.set(#start, value);
+			}
+		},
+		Kind::Int(_) => quote! {
+			#[doc = #get_doc]
+			#[inline]
+			#vis fn #ident(&self) -> #ty {
+				::bitvec::field::BitField::#load::<#ty>(
+					&#bits[#start .. #end],
+				)
+			}
+
+			#[doc = #set_doc]
+			#[inline]
+			#vis fn #setter(&mut self, value: #ty) {
+				::bitvec::field::BitField::#store::<#ty>(
+					&mut #bits_mut[#start .. #end],
+					value,
+				);
+			}
+		},
+		Kind::Value => quote! {
+			#[doc = #get_doc]
+			///
+			/// This returns `None` if the stored bits do not correspond to
+			/// any value of the field’s type.
+			#[inline]
+			#vis fn #ident(&self) -> ::core::option::Option<#ty> {
+				let raw = ::bitvec::field::BitField::#load::<
+					<#ty as ::bitvec::field::FieldValue>::Repr,
+				>(&#bits[#start .. #end]);
+				//  Signed representations are sign-extended, but a value may
+				//  also use the top bit of the field as a magnitude bit.
+				//  `!raw ^ raw` is all ones in the representation type.
+				let zext = (!raw ^ raw)
+					.checked_shl(#width)
+					.map_or(raw, |high| raw & !high);
+				<#ty as ::bitvec::field::FieldValue>::from_repr(raw)
+					.or_else(|| {
+						<#ty as ::bitvec::field::FieldValue>::from_repr(zext)
+					})
+			}
+
+			#[doc = #set_doc]
+			#[inline]
+			#vis fn #setter(&mut self, value: #ty) {
+				::bitvec::field::BitField::#store(
+					&mut #bits_mut[#start .. #end],
+					<#ty as ::bitvec::field::FieldValue>::into_repr(value),
+				);
+			}
+		},
+	}
+}
+
+/// Finds the bits that a field occupies, counted in the container’s ordering.
+///
+/// A field may count its range in the other of `Lsb0` and `Msb0` from the
+/// container. Its range then covers the mirror image, within each storage
+/// element, of the same range in the container’s ordering, so the container
+/// must use an integer store of known width.
+///
+/// ## Returns
+///
+/// The ranges of container bits that the field occupies, and the field’s own
+/// ordering if it differs from the container’s.
+fn placement(
+	bits: &Field,
+	container: &Container,
+) -> syn::Result<(Vec<Range<usize>>, Option<TokenStream>)> {
+	let same = vec![Range {
+		start: bits.start,
+		end:   bits.end,
+	}];
+	let order = match &bits.order {
+		Some(order) => order,
+		None => return Ok((same, None)),
+	};
+	let outer = container
+		.order
+		.as_ref()
+		.map_or(Some("Msb0".to_owned()), |path| {
+			path.get_ident().map(ToString::to_string)
+		});
+	let inner = order.get_ident().map(ToString::to_string);
+	if inner == outer {
+		return Ok((same, None));
+	}
+	let elem = match &container.store {
+		None => Some(8),
+		Some(Type::Path(path)) => path.path.get_ident().and_then(|ident| {
+			match ident.to_string().as_str() {
+				"u8" => Some(8),
+				"u16" => Some(16),
+				"u32" => Some(32),
+				"u64" => Some(64),
+				_ => None,
+			}
+		}),
+		Some(_) => None,
+	};
+	let known = |name: &Option<String>| {
+		matches!(name.as_deref(), Some("Lsb0") | Some("Msb0"))
+	};
+	let elem = match elem {
+		Some(elem) if known(&inner) && known(&outer) => elem,
+		_ => {
+			return Err(syn::Error::new_spanned(
+				order,
+				"a field may only set `order` to `Lsb0` or `Msb0` when the \
+				 struct uses the other, with a `store` of `u8`, `u16`, `u32`, \
+				 or `u64`",
+			));
+		},
+	};
+
+	let mut place = Vec::new();
+	let mut idx = bits.start;
+	while idx < bits.end {
+		let base = idx - idx % elem;
+		let stop = bits.end.min(base + elem);
+		place.push(base + elem - (stop - base) .. base + elem - (idx - base));
+		idx = stop;
+	}
+	Ok((place, Some(order_path(Some(order)))))
+}
+
+/// Produces `set_name` for a field `name`, including for raw identifiers.
+fn setter_name(ident: &Ident) -> Ident {
+	format_ident!("set_{}", ident.unraw())
+}
+
+/// Resolves the bit-ordering, routing the built-in names through `bitvec` so
+/// that users do not need to import them.
+fn order_path(order: Option<&Path>) -> TokenStream {
+	match order {
+		None => quote!(::bitvec::order::Msb0),
+		Some(path) => match path.get_ident().map(ToString::to_string) {
+			Some(name)
+				if name == "Lsb0" || name == "Msb0" || name == "LocalBits" =>
+			{
+				let ident = Ident::new(&name, path.span());
+				quote!(::bitvec::order::#ident)
+			},
+			_ => quote!(#path),
+		},
+	}
+}
+
+/// Implements `FieldValue` for a fieldless enum with an integer `#[repr]`.
+fn derive_enum(input: &DeriveInput, data: &DataEnum) -> syn::Result<TokenStream> {
+	if let Some(variant) = data
+		.variants
+		.iter()
+		.find(|variant| !matches!(variant.fields, Fields::Unit))
+	{
+		return Err(syn::Error::new_spanned(
+			&variant.fields,
+			"`BitFields` can only be derived for enums without fields",
+		));
+	}
+
+	let repr = enum_repr(input)?;
+	let ident = &input.ident;
+	let variants = data.variants.iter().map(|variant| &variant.ident);
+	Ok(quote! {
+		impl ::bitvec::field::FieldValue for #ident {
+			type Repr = #repr;
+
+			#[inline]
+			fn into_repr(self) -> #repr {
+				self as #repr
+			}
+
+			#[inline]
+			fn from_repr(repr: #repr) -> ::core::option::Option<Self> {
+				#(
+					if repr == Self::#variants as #repr {
+						return ::core::option::Option::Some(Self::#variants);
+					}
+				)*
+				::core::option::Option::None
+			}
+		}
+	})
+}
+
+/// Finds the integer named in an enum’s `#[repr(…)]` attribute.
+fn enum_repr(input: &DeriveInput) -> syn::Result<Ident> {
+	const INTS: &[&str] = &[
+		"u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64", "u128", "i128",
+		"usize", "isize",
+	];
+	let mut repr = None;
+	for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+		attr.parse_nested_meta(|meta| {
+			if let Some(ident) = meta.path.get_ident() {
+				if INTS.contains(&&*ident.to_string()) {
+					repr = Some(ident.clone());
+				}
+			}
+			//  Skip the arguments of `align(…)` and `packed(…)`.
+			if meta.input.peek(Paren) {
+				let content;
+				parenthesized!(content in meta.input);
+				content.parse::<TokenStream>()?;
+			}
+			Ok(())
+		})?;
+	}
+	repr.ok_or_else(|| {
+		syn::Error::new(
+			input.ident.span(),
+			"`BitFields` enums must declare their integer type with \
+			 `#[repr(…)]`",
+		)
+	})
+}
@@ -0,0 +1,29 @@
+//! Derive macros for `bitvec`.
+//!
+//! This crate is an implementation detail of `bitvec`’s `derive` feature. Its
+//! macros are documented where `bitvec` re-exports them, and it is not intended
+//! to be used directly.
+
+#![deny(unconditional_recursion)]
+
+mod attr;
+mod expand;
+mod tests;
+
+use proc_macro::TokenStream;
+use syn::{
+	parse_macro_input,
+	DeriveInput,
+};
+
+/// Generates a packed bit-field type for a struct, or a `FieldValue`
+/// implementation for a fieldless enum.
+///
+/// See `bitvec::field::BitFields` for the full documentation.
+#[proc_macro_derive(BitFields, attributes(bits))]
+pub fn derive_bit_fields(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	expand::derive(&input)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
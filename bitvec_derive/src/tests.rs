@@ -0,0 +1,191 @@
+#![cfg(test)]
+
+use syn::{
+	parse_quote,
+	DeriveInput,
+};
+
+use crate::expand;
+
+/// Expands an item and returns the error message it produced.
+fn error(input: DeriveInput) -> String {
+	expand::derive(&input)
+		.expect_err("expansion should have failed")
+		.into_compile_error()
+		.to_string()
+}
+
+#[test]
+fn accepts_layouts() {
+	let input: DeriveInput = parse_quote! {
+		#[bits(order = Lsb0, store = u16, endian = le, len = 32, name = P)]
+		struct S {
+			#[bits(0 .. 4)]
+			a: u8,
+			#[bits(4 ..= 7, endian = be)]
+			b: i8,
+			#[bits(8)]
+			c: bool,
+			#[bits(9 .. 31)]
+			d: Custom,
+		}
+	};
+	let out = expand::derive(&input).unwrap().to_string();
+	assert!(out.contains("struct P"));
+	assert!(out.contains("fn set_d"));
+	assert!(out.contains("TryFrom < P > for S"));
+
+	let input: DeriveInput = parse_quote! {
+		#[repr(C, u16, align(4))]
+		enum E {
+			A,
+			B = 7,
+		}
+	};
+	let out = expand::derive(&input).unwrap().to_string();
+	assert!(out.contains("type Repr = u16"));
+}
+
+#[test]
+fn rejects_overlap() {
+	let msg = error(parse_quote! {
+		struct S {
+			#[bits(0 .. 4)]
+			a: u8,
+			#[bits(3 .. 6)]
+			b: u8,
+		}
+	});
+	assert!(msg.contains("bits 3 .. 6 of `b` overlap bits 0 .. 4 of `a`"));
+}
+
+#[test]
+fn rejects_out_of_range() {
+	let msg = error(parse_quote! {
+		#[bits(len = 8)]
+		struct S {
+			#[bits(4 .. 12)]
+			a: u8,
+		}
+	});
+	assert!(msg.contains("outside the 8-bit container"));
+
+	let msg = error(parse_quote! {
+		struct S {
+			#[bits(0 .. 9)]
+			a: u8,
+			#[bits(9 .. 11)]
+			b: bool,
+			#[bits(12 .. 12)]
+			c: u16,
+		}
+	});
+	assert!(msg.contains("`a` is 9 bits wide, which does not fit in `u8`"));
+	assert!(msg.contains("`bool` fields must be exactly one bit wide"));
+	assert!(msg.contains("the bit range of `c` is empty"));
+}
+
+#[test]
+fn rejects_shapes() {
+	assert!(error(parse_quote! {
+		struct S {
+			a: u8,
+		}
+	})
+	.contains("every field must have a `#[bits(…)]` attribute"));
+	assert!(error(parse_quote! {
+		struct S(#[bits(0)] bool);
+	})
+	.contains("structs with named fields"));
+	assert!(error(parse_quote! {
+		struct S<T> {
+			#[bits(0)]
+			a: T,
+		}
+	})
+	.contains("generic types"));
+	assert!(error(parse_quote! {
+		enum E {
+			A(u8),
+		}
+	})
+	.contains("enums without fields"));
+	assert!(error(parse_quote! {
+		enum E {
+			A,
+		}
+	})
+	.contains("#[repr(…)]"));
+	assert!(error(parse_quote! {
+		struct S {
+			#[bits(0, store = u16)]
+			a: bool,
+		}
+	})
+	.contains("must be set on the struct"));
+	assert!(error(parse_quote! {
+		struct S {
+			#[bits(0 ..= 18446744073709551615)]
+			a: bool,
+		}
+	})
+	.contains("must end before `usize::MAX`"));
+}
+
+#[test]
+fn field_order() {
+	let input: DeriveInput = parse_quote! {
+		#[bits(store = u16)]
+		struct S {
+			#[bits(0 .. 4, order = Lsb0)]
+			a: u8,
+			#[bits(0 .. 12)]
+			b: u16,
+			#[bits(16, order = Msb0)]
+			c: bool,
+		}
+	};
+	let out = expand::derive(&input).unwrap().to_string();
+	assert!(out.contains("view_bits :: < :: bitvec :: order :: Lsb0 >"));
+	assert!(out.contains("pub const LEN : usize = 17"));
+
+	//  Bits `0 .. 4` in `Lsb0` are bits `12 .. 16` in `Msb0`.
+	let msg = error(parse_quote! {
+		#[bits(order = Msb0, store = u16)]
+		struct S {
+			#[bits(0 .. 4, order = Lsb0)]
+			a: u8,
+			#[bits(10 .. 14)]
+			b: u8,
+		}
+	});
+	assert!(msg.contains("bits 10 .. 14 of `b` overlap bits 0 .. 4 of `a`"));
+
+	let msg = error(parse_quote! {
+		#[bits(order = Lsb0, len = 8)]
+		struct S {
+			#[bits(8 .. 10, order = Msb0)]
+			a: u8,
+		}
+	});
+	assert!(msg.contains("outside the 8-bit container"));
+
+	for input in [
+		parse_quote! {
+			#[bits(store = usize)]
+			struct S {
+				#[bits(0, order = Lsb0)]
+				a: bool,
+			}
+		},
+		parse_quote! {
+			#[bits(order = LocalBits)]
+			struct S {
+				#[bits(0, order = Lsb0)]
+				a: bool,
+			}
+		},
+	] {
+		assert!(error(input).contains("may only set `order`"));
+	}
+}
@@ -0,0 +1,232 @@
+use std::convert::TryFrom;
+
+use bitvec::{
+	field::{
+		BitFields,
+		FieldValue,
+	},
+	prelude::*,
+};
+
+#[derive(BitFields, Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+enum Opcode {
+	Load = 1,
+	Store = 2,
+	Jump = 6,
+}
+
+#[derive(BitFields, Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(i16)]
+enum Sign {
+	Negative = -1,
+	Zero = 0,
+	Positive = 1,
+}
+
+#[derive(BitFields, Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(i8)]
+enum Mode {
+	A = 0,
+	B = 1,
+	C = 2,
+	D = -1,
+}
+
+#[derive(BitFields, Debug, Eq, PartialEq)]
+struct Reg {
+	#[bits(0 .. 2)]
+	mode: Mode,
+}
+
+/// An IPv4 header prefix, in the network layout.
+#[derive(BitFields, Debug, Eq, PartialEq)]
+struct Ipv4 {
+	#[bits(0 .. 4)]
+	version: u8,
+	#[bits(4 .. 8)]
+	ihl: u8,
+	#[bits(8 .. 14)]
+	dscp: u8,
+	#[bits(14 .. 16)]
+	ecn: u8,
+	#[bits(16 .. 32)]
+	length: u16,
+	#[bits(49)]
+	dont_fragment: bool,
+	#[bits(50)]
+	more_fragments: bool,
+	#[bits(51 .. 64)]
+	offset: u16,
+}
+
+#[derive(BitFields, Debug, Eq, PartialEq)]
+#[bits(order = Lsb0, store = u16, endian = le, len = 40, name = Packed)]
+struct Instruction {
+	#[bits(0 .. 3)]
+	op: Opcode,
+	#[bits(3 ..= 4)]
+	sign: Sign,
+	#[bits(5 .. 17)]
+	imm: i16,
+	#[bits(17 .. 37, endian = be)]
+	addr: u32,
+	#[bits(39)]
+	r#final: bool,
+}
+
+/// A register counted from its least significant bits, with fields that count
+/// from the most significant bit of their byte.
+#[derive(BitFields, Debug, Eq, PartialEq)]
+#[bits(order = Lsb0)]
+struct Status {
+	#[bits(0)]
+	ready: bool,
+	#[bits(3 .. 7, order = Msb0, endian = be)]
+	code: u8,
+	#[bits(5 .. 8)]
+	level: u8,
+	#[bits(8 .. 11, order = Msb0)]
+	op: Opcode,
+}
+
+#[test]
+fn network_layout() {
+	let header = Ipv4 {
+		version: 4,
+		ihl: 5,
+		dscp: 0x2E,
+		ecn: 1,
+		length: 0x05DC,
+		dont_fragment: true,
+		more_fragments: false,
+		offset: 0x1ABC,
+	};
+	let bits = Ipv4Bits::from(header);
+	assert_eq!(Ipv4Bits::LEN, 64);
+	assert_eq!(bits.as_bitarray().as_raw_slice(), [
+		0x45, 0xB9, 0x05, 0xDC, 0x00, 0x00, 0x5A, 0xBC,
+	]);
+	assert_eq!(bits.version(), 4);
+	assert_eq!(bits.ihl(), 5);
+	assert_eq!(bits.dscp(), 0x2E);
+	assert_eq!(bits.ecn(), 1);
+	assert_eq!(bits.length(), 0x05DC);
+	assert!(bits.dont_fragment());
+	assert!(!bits.more_fragments());
+	assert_eq!(bits.offset(), 0x1ABC);
+	assert_eq!(Ipv4::from(bits), Ipv4 {
+		version: 4,
+		ihl: 5,
+		dscp: 0x2E,
+		ecn: 1,
+		length: 0x05DC,
+		dont_fragment: true,
+		more_fragments: false,
+		offset: 0x1ABC,
+	});
+}
+
+#[test]
+fn setters_are_isolated() {
+	let mut bits = Ipv4Bits::ZERO;
+	bits.set_version(0xFF);
+	assert_eq!(bits.version(), 0xF);
+	assert_eq!(bits.ihl(), 0);
+	bits.set_more_fragments(true);
+	assert!(!bits.dont_fragment());
+	assert_eq!(bits.offset(), 0);
+	bits.set_offset(0xFFFF);
+	assert!(bits.more_fragments());
+	assert_eq!(bits.offset(), 0x1FFF);
+	assert_eq!(bits.as_bitarray().count_ones(), 4 + 1 + 13);
+
+	let mut raw = bits.into_bitarray();
+	raw.fill(false);
+	let bits = Ipv4Bits::from_bitarray(raw);
+	assert_eq!(bits, Ipv4Bits::default());
+}
+
+#[test]
+fn custom_layout() {
+	let insn = Instruction {
+		op: Opcode::Jump,
+		sign: Sign::Negative,
+		imm: -1234,
+		addr: 0xF_1234,
+		r#final: true,
+	};
+	let mut bits = Packed::from(insn);
+	assert_eq!(Packed::LEN, 40);
+	assert_eq!(bits.as_bitarray().len(), 48);
+	assert_eq!(bits.op(), Some(Opcode::Jump));
+	assert_eq!(bits.sign(), Some(Sign::Negative));
+	assert_eq!(bits.imm(), -1234);
+	assert_eq!(bits.addr(), 0xF_1234);
+	assert!(bits.r#final());
+	assert_eq!(bits.as_bitarray()[.. 3].load_le::<u8>(), 6);
+	assert_eq!(bits.as_bitarray()[17 .. 37].load_be::<u32>(), 0xF_1234);
+
+	bits.set_sign(Sign::Zero);
+	bits.set_final(false);
+	assert_eq!(Instruction::try_from(bits), Ok(Instruction {
+		op: Opcode::Jump,
+		sign: Sign::Zero,
+		imm: -1234,
+		addr: 0xF_1234,
+		r#final: false,
+	}));
+
+	bits.as_mut_bitarray()[.. 3].store_le(0u8);
+	assert_eq!(bits.op(), None);
+	assert_eq!(Instruction::try_from(bits), Err(bits));
+}
+
+#[test]
+fn field_order() {
+	let status = Status {
+		ready: true,
+		code: 0b1011,
+		level: 5,
+		op: Opcode::Jump,
+	};
+	let mut bits = StatusBits::from(status);
+	assert_eq!(StatusBits::LEN, 16);
+	assert_eq!(bits.as_bitarray().as_raw_slice(), [0xB7, 0xC0]);
+	assert_eq!(bits.code(), 0b1011);
+	assert_eq!(bits.op(), Some(Opcode::Jump));
+	assert_eq!(bits.as_bitarray()[1 .. 5].load_be::<u8>(), 0b1011);
+
+	bits.set_code(0);
+	bits.set_op(Opcode::Load);
+	assert_eq!(Status::try_from(bits), Ok(Status {
+		ready: true,
+		code: 0,
+		level: 5,
+		op: Opcode::Load,
+	}));
+	assert_eq!(bits.as_bitarray().as_raw_slice(), [0xA1, 0x20]);
+}
+
+#[test]
+fn signed_enum_top_bit() {
+	for &mode in &[Mode::A, Mode::B, Mode::C, Mode::D] {
+		let bits = RegBits::from(Reg { mode });
+		assert_eq!(bits.mode(), Some(mode));
+		assert_eq!(Reg::try_from(bits), Ok(Reg { mode }));
+	}
+	let bits = RegBits::from(Reg { mode: Mode::C });
+	assert_eq!(bits.as_bitarray()[.. 2], bits![1, 0]);
+}
+
+#[test]
+fn enum_values() {
+	assert_eq!(Opcode::Store.into_repr(), 2);
+	assert_eq!(Opcode::from_repr(6), Some(Opcode::Jump));
+	assert_eq!(Opcode::from_repr(3), None);
+	assert_eq!(Sign::from_repr(-1), Some(Sign::Negative));
+	assert_eq!(Sign::Positive.into_repr(), 1i16);
+	assert_eq!(bool::from_repr(1), Some(true));
+	assert_eq!(bool::from_repr(2), None);
+	assert_eq!(u32::from_repr(7), Some(7));
+}
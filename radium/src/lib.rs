@@ -2,21 +2,38 @@
 #![no_std]
 #![deny(unconditional_recursion)]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 pub mod marker;
 mod seal;
 pub mod types;
+pub mod wait;
+mod wide;
 
 use core::{
 	cell::Cell,
 	sync::atomic::*,
 };
 
-use crate::marker::*;
+use crate::marker::{
+	*,
+	Atomic,
+};
+#[cfg(all(target_arch = "x86_64", target_feature = "cmpxchg16b"))]
+use crate::types::{
+	AtomicI128,
+	AtomicU128,
+};
+#[cfg(target_has_atomic = "8")]
+pub use crate::types::Locked;
 pub use crate::types::{
 	Atom,
 	Isotope,
 	Radon,
 };
+#[cfg(feature = "std")]
+pub use crate::wait::Wait;
 
 #[doc = include_str!("../doc/radium.md")]
 pub trait Radium: seal::Sealed {
@@ -274,6 +291,11 @@ macro_rules! radium {
 		$(@<$t:ident>)? $base:ty => $atom:ident
 	) => {
 		#[cfg(target_has_atomic = $width)]
+		radium!(impl $bit $num $(@<$t>)? $base => $atom);
+	};
+
+	// Forward the implementation to the atom's inherent methods.
+	(impl $bit:ident $num:ident $(@<$t:ident>)? $base:ty => $atom:ident) => {
 		impl$(<$t>)? Radium for $atom$(<$t>)? {
 			type Item = $base;
 
@@ -756,6 +778,18 @@ macro_rules! radium {
 		}
 	};
 
+	// Generate the read-modify-write functions of `Locked<T>`, which run the
+	// `Cell` implementation while holding the lock.
+	(locked $($n:ident => $marker:ident),+ $(,)?) => { $(
+		#[inline]
+		fn $n(&self, value: T, order: Ordering) -> T
+		where
+			T: $marker,
+		{
+			self.critical(order, |cell| Radium::$n(cell, value, order))
+		}
+	)+ };
+
 	// Generate stubs for the conditionally-unreachable methods.
 	(unreachable $($n:ident),+ $(,)?) => { $(
 		fn $n(&self, _: Self::Item, _: Ordering) -> Self::Item {
@@ -799,6 +833,12 @@ radium! {
 	}
 }
 
+// `core` has no stable 128-bit atomics; these are `radium`’s own.
+#[cfg(all(target_arch = "x86_64", target_feature = "cmpxchg16b"))]
+radium!(impl bit num i128 => AtomicI128);
+#[cfg(all(target_arch = "x86_64", target_feature = "cmpxchg16b"))]
+radium!(impl bit num u128 => AtomicU128);
+
 impl<T> Radium for Atom<T>
 where
 	T: Atomic,
@@ -839,6 +879,114 @@ where
 	fn fence(_: Ordering) {}
 }
 
+#[cfg(target_has_atomic = "8")]
+impl<T> Radium for Locked<T>
+where
+	T: Nuclear,
+	Cell<T>: Radium<Item = T>,
+{
+	type Item = T;
+
+	#[inline]
+	fn new(value: T) -> Self {
+		Self {
+			lock:  AtomicBool::new(false),
+			inner: Cell::new(value),
+		}
+	}
+
+	#[inline]
+	fn fence(order: Ordering) {
+		core::sync::atomic::fence(order);
+	}
+
+	#[inline]
+	fn get_mut(&mut self) -> &mut T {
+		self.inner.get_mut()
+	}
+
+	#[inline]
+	fn into_inner(self) -> T {
+		self.inner.into_inner()
+	}
+
+	#[inline]
+	fn load(&self, order: Ordering) -> T {
+		self.critical(order, Cell::get)
+	}
+
+	#[inline]
+	fn store(&self, value: T, order: Ordering) {
+		self.critical(order, |cell| cell.set(value));
+	}
+
+	#[inline]
+	fn swap(&self, value: T, order: Ordering) -> T {
+		self.critical(order, |cell| cell.replace(value))
+	}
+
+	#[inline]
+	#[allow(deprecated)]
+	fn compare_and_swap(&self, current: T, new: T, order: Ordering) -> T {
+		self.critical(order, |cell| {
+			Radium::compare_and_swap(cell, current, new, order)
+		})
+	}
+
+	#[inline]
+	fn compare_exchange(
+		&self,
+		current: T,
+		new: T,
+		success: Ordering,
+		failure: Ordering,
+	) -> Result<T, T> {
+		self.critical(success, |cell| {
+			Radium::compare_exchange(cell, current, new, success, failure)
+		})
+	}
+
+	#[inline]
+	fn compare_exchange_weak(
+		&self,
+		current: T,
+		new: T,
+		success: Ordering,
+		failure: Ordering,
+	) -> Result<T, T> {
+		//  The lock never fails spuriously.
+		Radium::compare_exchange(self, current, new, success, failure)
+	}
+
+	radium!(locked
+		fetch_and => BitOps,
+		fetch_nand => BitOps,
+		fetch_or => BitOps,
+		fetch_xor => BitOps,
+		fetch_add => NumericOps,
+		fetch_sub => NumericOps,
+		fetch_max => NumericOps,
+		fetch_min => NumericOps,
+	);
+
+	#[inline]
+	fn fetch_update<F>(
+		&self,
+		set_order: Ordering,
+		fetch_order: Ordering,
+		func: F,
+	) -> Result<T, T>
+	where
+		F: FnMut(T) -> Option<T>,
+	{
+		//  The function runs while the lock is held, so it is called exactly
+		//  once.
+		self.critical(set_order, |cell| {
+			Radium::fetch_update(cell, set_order, fetch_order, func)
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	#[allow(unused_imports)]
@@ -906,6 +1054,89 @@ mod tests {
 		assert_impl_all!(Isotope<*mut ()>: Radium<Item = *mut ()>);
 	}
 
+	#[test]
+	fn pointers() {
+		fn exercise<R>(make: impl Fn(*mut u8) -> R)
+		where R: Radium<Item = *mut u8> {
+			let mut data = [0u8; 4];
+			let base = data.as_mut_ptr();
+			let (one, two) = unsafe { (base.add(1), base.add(2)) };
+
+			let ptr = make(base);
+			assert_eq!(ptr.swap(one, Ordering::Relaxed), base);
+			assert_eq!(
+				ptr.compare_exchange(
+					base,
+					two,
+					Ordering::Relaxed,
+					Ordering::Relaxed,
+				),
+				Err(one),
+			);
+			assert_eq!(
+				ptr.compare_exchange(
+					one,
+					two,
+					Ordering::Relaxed,
+					Ordering::Relaxed,
+				),
+				Ok(one),
+			);
+			assert_eq!(
+				ptr.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |p| {
+					Some(unsafe { p.add(1) })
+				}),
+				Ok(two),
+			);
+			assert_eq!(
+				ptr.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |_| None),
+				Err(unsafe { base.add(3) }),
+			);
+			assert_eq!(ptr.into_inner(), unsafe { base.add(3) });
+		}
+
+		exercise(Cell::new);
+		exercise(<Isotope<*mut u8>>::new);
+		exercise(<Radon<*mut u8>>::new);
+		#[cfg(target_has_atomic = "ptr")]
+		{
+			exercise(AtomicPtr::new);
+			exercise(<Atom<*mut u8>>::new);
+		}
+		#[cfg(target_has_atomic = "8")]
+		exercise(<Locked<*mut u8>>::new);
+	}
+
+	#[test]
+	#[cfg(target_has_atomic = "8")]
+	fn locked() {
+		let num = <Shared<u128>>::new(u64::MAX as u128);
+		assert_eq!(num.fetch_add(1, Ordering::SeqCst), u64::MAX as u128);
+		assert_eq!(num.fetch_sub(2, Ordering::AcqRel), 1 << 64);
+		assert_eq!(num.fetch_max(1 << 100, Ordering::Relaxed), (1 << 64) - 2);
+		assert_eq!(
+			num.compare_exchange_weak(
+				1 << 100,
+				7,
+				Ordering::Release,
+				Ordering::Relaxed,
+			),
+			Ok(1 << 100),
+		);
+		assert_eq!(num.fetch_xor(3, Ordering::Relaxed), 7);
+		num.store(9, Ordering::SeqCst);
+		assert_eq!(num.load(Ordering::Acquire), 9);
+
+		// Every operation releases the lock, so nothing here deadlocks.
+		let flag = <Locked<bool>>::default();
+		assert!(!flag.fetch_or(true, Ordering::Relaxed));
+		assert!(flag.fetch_nand(true, Ordering::Relaxed));
+		assert!(!flag.load(Ordering::Relaxed));
+		let mut flag = flag;
+		*flag.get_mut() = true;
+		assert!(flag.into_inner());
+	}
+
 	#[test]
 	fn maybe_atom() {
 		#[cfg(target_has_atomic = "8")]
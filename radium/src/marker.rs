@@ -44,3 +44,15 @@ pub trait Nuclear: Copy + Sealed {
 	/// The `RadiumT` type corresponding to `Self`.
 	type Nucleus: Radium<Item = Self>;
 }
+
+/// Relates a primitive type to a shared-mutable type that is always `Sync`.
+///
+/// The destination type is the `AtomicT` type when it exists, and
+/// [`Locked<T>`] when it does not. This is only implemented on targets that
+/// have atomic `bool`s, which the lock requires.
+///
+/// [`Locked<T>`]: crate::types::Locked
+pub trait Shareable: Copy + Sealed {
+	/// The `AtomicT` or `Locked<T>` type corresponding to `Self`.
+	type Shared: Radium<Item = Self> + Send + Sync;
+}
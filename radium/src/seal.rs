@@ -3,6 +3,13 @@ use core::{
 	sync::atomic::*,
 };
 
+#[cfg(target_has_atomic = "8")]
+use crate::types::Locked;
+#[cfg(all(target_arch = "x86_64", target_feature = "cmpxchg16b"))]
+use crate::types::{
+	AtomicI128,
+	AtomicU128,
+};
 use crate::{
 	Atom,
 	Atomic,
@@ -14,8 +21,9 @@ use crate::{
 
 /// Forbid external implementation of `radium` traits. This crate *only* works
 /// on the standard-library `AtomicT` and `Cell<T>` types, as well as its own
-/// `Atom<T>` and `Isotope<T>`. We do not support third-party types, as only the
-/// standard library can reasonably guarantee atomic behavior.
+/// `Atom<T>`, `Isotope<T>`, `Radon<T>`, and `Locked<T>`. We do not support
+/// third-party types, as only the standard library can reasonably guarantee
+/// atomic behavior.
 pub trait Sealed {}
 
 macro_rules! sealed {
@@ -77,3 +85,17 @@ where
 	Cell<T>: Radium<Item = T>,
 {
 }
+
+#[cfg(target_has_atomic = "8")]
+impl<T> Sealed for Locked<T>
+where
+	T: Nuclear,
+	Cell<T>: Radium<Item = T>,
+{
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "cmpxchg16b"))]
+impl Sealed for AtomicI128 {}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "cmpxchg16b"))]
+impl Sealed for AtomicU128 {}
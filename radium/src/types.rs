@@ -15,9 +15,15 @@ use crate::{
 	marker::{
 		Atomic,
 		Nuclear,
+		Shareable,
 	},
 	Radium,
 };
+#[cfg(all(target_arch = "x86_64", target_feature = "cmpxchg16b"))]
+pub use crate::wide::{
+	AtomicI128,
+	AtomicU128,
+};
 
 #[repr(transparent)]
 #[doc = include_str!("../doc/atom.md")]
@@ -160,19 +166,159 @@ where
 	}
 }
 
+#[doc = include_str!("../doc/locked.md")]
+#[cfg(target_has_atomic = "8")]
+pub struct Locked<T>
+where
+	T: Nuclear,
+	Cell<T>: Radium<Item = T>,
+{
+	/// Set while a thread is operating on `inner`.
+	pub(crate) lock:  AtomicBool,
+	/// The protected value. It is only accessed while `lock` is held, or
+	/// through a unique reference.
+	pub(crate) inner: Cell<T>,
+}
+
+#[cfg(target_has_atomic = "8")]
+impl<T> Locked<T>
+where
+	T: Nuclear,
+	Cell<T>: Radium<Item = T>,
+{
+	/// Runs a function on the protected value while holding the lock.
+	///
+	/// The lock is taken with `Acquire` and released with `Release` ordering.
+	/// When `order` is `SeqCst`, a sequentially-consistent fence is also
+	/// issued inside the critical section.
+	#[inline]
+	pub(crate) fn critical<F, R>(&self, order: Ordering, func: F) -> R
+	where F: FnOnce(&Cell<T>) -> R {
+		let _guard = LockGuard::acquire(&self.lock);
+		if order == Ordering::SeqCst {
+			fence(Ordering::SeqCst);
+		}
+		func(&self.inner)
+	}
+}
+
+/// `Locked<T>` behaves as the atomic for `T` would: every access to the value
+/// is serialized by the lock.
+#[cfg(target_has_atomic = "8")]
+unsafe impl<T> Send for Locked<T>
+where
+	T: Nuclear,
+	Cell<T>: Radium<Item = T>,
+{
+}
+
+#[cfg(target_has_atomic = "8")]
+unsafe impl<T> Sync for Locked<T>
+where
+	T: Nuclear,
+	Cell<T>: Radium<Item = T>,
+{
+}
+
+#[cfg(target_has_atomic = "8")]
+impl<T> Debug for Locked<T>
+where
+	T: Nuclear + Debug,
+	Cell<T>: Radium<Item = T>,
+{
+	#[inline]
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		let value = self.critical(Ordering::Relaxed, Cell::get);
+		Debug::fmt(&value, fmt)
+	}
+}
+
+#[cfg(target_has_atomic = "8")]
+impl<T> Default for Locked<T>
+where
+	T: Nuclear + Default,
+	Cell<T>: Radium<Item = T>,
+{
+	#[inline]
+	fn default() -> Self {
+		Radium::new(T::default())
+	}
+}
+
+#[cfg(target_has_atomic = "8")]
+impl<T> From<T> for Locked<T>
+where
+	T: Nuclear,
+	Cell<T>: Radium<Item = T>,
+{
+	#[inline]
+	fn from(val: T) -> Self {
+		Radium::new(val)
+	}
+}
+
+/// Holds a spinlock, and releases it when dropped.
+#[cfg(target_has_atomic = "8")]
+struct LockGuard<'a> {
+	/// The held lock.
+	lock: &'a AtomicBool,
+}
+
+#[cfg(target_has_atomic = "8")]
+impl<'a> LockGuard<'a> {
+	/// Spins until the lock is acquired.
+	#[inline]
+	fn acquire(lock: &'a AtomicBool) -> Self {
+		while lock
+			.compare_exchange_weak(
+				false,
+				true,
+				Ordering::Acquire,
+				Ordering::Relaxed,
+			)
+			.is_err()
+		{
+			//  Wait for the lock to be released before contending for the
+			//  cache line again.
+			while lock.load(Ordering::Relaxed) {
+				core::hint::spin_loop();
+			}
+		}
+		Self { lock }
+	}
+}
+
+#[cfg(target_has_atomic = "8")]
+impl Drop for LockGuard<'_> {
+	#[inline]
+	fn drop(&mut self) {
+		self.lock.store(false, Ordering::Release);
+	}
+}
+
+/// Always-`Sync` shared mutability for a primitive.
+///
+/// This resolves to the `AtomicT` type when the target has it, and to
+/// [`Locked<T>`] when it does not. The `force-locked` feature selects
+/// `Locked<T>` for every primitive, so that the fallback can be exercised on
+/// targets that have every atomic.
+///
+/// This alias only exists on targets that have atomic `bool`s, as the lock
+/// itself requires them.
+#[cfg(target_has_atomic = "8")]
+pub type Shared<T> = <T as Shareable>::Shared;
+
 /// Creates type aliases that resolve to either `AtomicT` or `Cell<T>` depending
 /// on availability.
 macro_rules! alias {
 	($($width:literal => { $(
-		$(@<$t:ident>)? $base:ty => $radium:ident $(=> $atom:ident)?
+		$(@<$t:ident>)? $base:ty => $radium:ident => $atom:ident
 	);+ $(;)? })+) => { $( $(
-		alias!(atom $width $(@<$t>)? $base => $radium $(=> $atom)?);
+		alias!(atom $width $(@<$t>)? $base => $radium => $atom);
 
-		alias!(cell $width $(@<$t>)? $base => $radium $(=> $atom)?);
+		alias!(cell $width $(@<$t>)? $base => $radium => $atom);
 	)+ )+ };
 
-	(atom $width:literal $(@<$t:ident>)? $base:ty => $radium:ident) => {};
-
 	(atom $width:literal $(@<$t:ident>)? $base:ty => $radium:ident => $atom:ident) => {
 		#[doc = concat!("Best-effort atomicity for `", stringify!($base), "`.")]
 		///
@@ -185,6 +331,20 @@ macro_rules! alias {
 		impl$(<$t>)? Atomic for $base {
 			type Atom = $atom$(<$t>)?;
 		}
+
+		// Prefer the atomic for `Shared<T>`, unless told to use the lock.
+		#[cfg(all(target_has_atomic = $width, not(feature = "force-locked")))]
+		impl$(<$t>)? Shareable for $base {
+			type Shared = $atom$(<$t>)?;
+		}
+
+		#[cfg(all(
+			target_has_atomic = "8",
+			any(not(target_has_atomic = $width), feature = "force-locked"),
+		))]
+		impl$(<$t>)? Shareable for $base {
+			type Shared = Locked<$base>;
+		}
 	};
 
 	// When an atom is provided, be conditional on target atomics.
@@ -202,22 +362,54 @@ macro_rules! alias {
 		}
 	};
 
-	// When an atom is not provided, unconditionally create the alias.
-	(cell $width:literal $(@<$t:ident>)? $base:ty => $radium:ident) => {
+	// `core` has no stable 128-bit atomics, so `radium` provides its own where
+	// the target has a double-width compare-exchange.
+	(wide $($base:ty => $radium:ident => $atom:ident);+ $(;)?) => { $(
 		#[doc = concat!("Best-effort atomicity for `", stringify!($base), "`.")]
 		///
-		/// The required atomic support is not stabilized in `core`, so this is
-		/// unconditionally a `Cell`.
-		pub type $radium$(<$t>)? = Cell<$base>;
+		/// This target has `cmpxchg16b`, so `radium` provides the atomic.
+		#[cfg(all(target_arch = "x86_64", target_feature = "cmpxchg16b"))]
+		pub type $radium = $atom;
+
+		#[doc = concat!("Best-effort atomicity for `", stringify!($base), "`.")]
+		///
+		/// The required atomic support is not stabilized in `core`, and this
+		/// target does not have `cmpxchg16b`, so this is a `Cell`.
+		#[cfg(not(all(target_arch = "x86_64", target_feature = "cmpxchg16b")))]
+		pub type $radium = Cell<$base>;
 
 		/// Note: the standard library has an unstable atomic for this type.
 		/// `radium` commits to operating on the stable release series, and so
-		/// will not use its atomic variant, but is willing to prepare for
-		/// assumed stabilization by acting on the `Cell`.
-		impl$(<$t>)? Nuclear for $base {
-			type Nucleus = $radium$(<$t>)?;
+		/// will not use it, but provides its own where the target allows.
+		impl Nuclear for $base {
+			type Nucleus = $radium;
 		}
-	};
+
+		#[cfg(all(target_arch = "x86_64", target_feature = "cmpxchg16b"))]
+		impl Atomic for $base {
+			type Atom = $atom;
+		}
+
+		#[cfg(all(
+			target_arch = "x86_64",
+			target_feature = "cmpxchg16b",
+			not(feature = "force-locked"),
+		))]
+		impl Shareable for $base {
+			type Shared = $atom;
+		}
+
+		#[cfg(all(
+			target_has_atomic = "8",
+			any(
+				not(all(target_arch = "x86_64", target_feature = "cmpxchg16b")),
+				feature = "force-locked",
+			),
+		))]
+		impl Shareable for $base {
+			type Shared = Locked<$base>;
+		}
+	)+ };
 }
 
 alias! {
@@ -238,10 +430,6 @@ alias! {
 		i64 => RadiumI64 => AtomicI64;
 		u64 => RadiumU64 => AtomicU64;
 	}
-	"128" => {
-		i128 => RadiumI128; // => AtomicI128; // when this stabilizes
-		u128 => RadiumU128; // => AtomicU128; // when this stabilizes
-	}
 	"ptr" => {
 		isize => RadiumIsize => AtomicIsize;
 		usize => RadiumUsize => AtomicUsize;
@@ -249,6 +437,12 @@ alias! {
 	}
 }
 
+alias! {
+	wide
+	i128 => RadiumI128 => AtomicI128;
+	u128 => RadiumU128 => AtomicU128;
+}
+
 #[cfg(test)]
 mod tests {
 	use static_assertions::*;
@@ -284,6 +478,11 @@ mod tests {
 			assert_impl_all!(Atom<usize>: Debug, Default, From<usize>, Sync);
 			assert_impl_all!(Atom<*mut ()>: Debug, From<*mut ()>, Sync);
 		}
+		#[cfg(all(target_arch = "x86_64", target_feature = "cmpxchg16b"))]
+		{
+			assert_impl_all!(Atom<i128>: Debug, Default, From<i128>, Sync);
+			assert_impl_all!(Atom<u128>: Debug, Default, From<u128>, Sync);
+		}
 	}
 
 	#[test]
@@ -398,8 +597,68 @@ mod tests {
 			assert_not_impl_any!(Isotope<*mut ()>: Sync);
 		}
 
-		// These are always non-atomic until `Atomic*128` stabilizes.
-		assert_not_impl_any!(Isotope<i128>: Sync);
-		assert_not_impl_any!(Isotope<u128>: Sync);
+		#[cfg(all(target_arch = "x86_64", target_feature = "cmpxchg16b"))]
+		{
+			assert_impl_all!(Isotope<i128>: Sync);
+			assert_impl_all!(Isotope<u128>: Sync);
+		}
+		#[cfg(not(all(target_arch = "x86_64", target_feature = "cmpxchg16b")))]
+		{
+			assert_not_impl_any!(Isotope<i128>: Sync);
+			assert_not_impl_any!(Isotope<u128>: Sync);
+		}
+	}
+
+	#[test]
+	#[cfg(target_has_atomic = "8")]
+	fn locked_impls() {
+		assert_impl_all!(Locked<bool>: Debug, Default, From<bool>, Send, Sync);
+		assert_impl_all!(Locked<i8>: Debug, Default, From<i8>, Send, Sync);
+		assert_impl_all!(Locked<u16>: Debug, Default, From<u16>, Send, Sync);
+		assert_impl_all!(Locked<i32>: Debug, Default, From<i32>, Send, Sync);
+		assert_impl_all!(Locked<u64>: Debug, Default, From<u64>, Send, Sync);
+		assert_impl_all!(Locked<i128>: Debug, Default, From<i128>, Send, Sync);
+		assert_impl_all!(Locked<u128>: Debug, Default, From<u128>, Send, Sync);
+		assert_impl_all!(Locked<usize>: Debug, Default, From<usize>, Sync);
+		assert_impl_all!(Locked<*mut ()>: Debug, From<*mut ()>, Send, Sync);
+	}
+
+	#[test]
+	#[cfg(target_has_atomic = "8")]
+	fn shared_aliases() {
+		#[cfg(not(feature = "force-locked"))]
+		{
+			assert_type_eq_all!(Shared<bool>, AtomicBool);
+			assert_type_eq_all!(Shared<u8>, AtomicU8);
+			#[cfg(target_has_atomic = "64")]
+			assert_type_eq_all!(Shared<i64>, AtomicI64);
+			#[cfg(target_has_atomic = "ptr")]
+			assert_type_eq_all!(Shared<*mut u8>, AtomicPtr<u8>);
+		}
+		#[cfg(feature = "force-locked")]
+		{
+			assert_type_eq_all!(Shared<bool>, Locked<bool>);
+			assert_type_eq_all!(Shared<u8>, Locked<u8>);
+			assert_type_eq_all!(Shared<i64>, Locked<i64>);
+			assert_type_eq_all!(Shared<*mut u8>, Locked<*mut u8>);
+		}
+
+		#[cfg(all(
+			target_arch = "x86_64",
+			target_feature = "cmpxchg16b",
+			not(feature = "force-locked"),
+		))]
+		{
+			assert_type_eq_all!(Shared<i128>, AtomicI128);
+			assert_type_eq_all!(Shared<u128>, AtomicU128);
+		}
+		#[cfg(any(
+			not(all(target_arch = "x86_64", target_feature = "cmpxchg16b")),
+			feature = "force-locked",
+		))]
+		{
+			assert_type_eq_all!(Shared<i128>, Locked<i128>);
+			assert_type_eq_all!(Shared<u128>, Locked<u128>);
+		}
 	}
 }
@@ -0,0 +1,481 @@
+#![cfg(feature = "std")]
+#![doc = include_str!("../doc/wait.md")]
+
+use core::{
+	cell::Cell,
+	sync::atomic::*,
+	time::Duration,
+};
+use std::{
+	sync::{
+		Condvar,
+		Mutex,
+		MutexGuard,
+		PoisonError,
+	},
+	thread,
+};
+
+#[cfg(target_has_atomic = "8")]
+use crate::types::Locked;
+#[cfg(all(target_arch = "x86_64", target_feature = "cmpxchg16b"))]
+use crate::types::{
+	AtomicI128,
+	AtomicU128,
+};
+use crate::{
+	Atom,
+	Isotope,
+	Radium,
+	Radon,
+	marker::{
+		Atomic,
+		Nuclear,
+	},
+};
+
+/// Blocks threads until a shared-mutable value changes.
+///
+/// This lets synchronization primitives built on [`Radium`] sleep instead of
+/// spinning on `.load()`. The protocol is the same as a futex: a waiter passes
+/// the value it last observed, and only sleeps if the value is still that;
+/// a thread that changes the value then calls one of the `.notify_*()`
+/// methods.
+///
+/// See the [module documentation](self) for the implementation used by each
+/// type.
+pub trait Wait: Radium {
+	/// Blocks the current thread while the value is `expected`.
+	///
+	/// This returns when the value is not `expected`, when another thread calls
+	/// a `.notify_*()` method, when `timeout` elapses, or spuriously. Callers
+	/// must load the value again afterwards, and loop if it has not changed.
+	///
+	/// ## Returns
+	///
+	/// `false` if `timeout` elapsed, and `true` otherwise.
+	fn wait(&self, expected: Self::Item, timeout: Option<Duration>) -> bool;
+
+	/// Wakes at least one thread blocked in `.wait()` on this value.
+	fn notify_one(&self);
+
+	/// Wakes every thread blocked in `.wait()` on this value.
+	fn notify_all(&self);
+}
+
+/// Implements `Wait` for types that use the condition-variable table.
+macro_rules! table {
+	($($(@<$t:ident>)? $atom:ty),+ $(,)?) => { $(
+		impl$(<$t>)? Wait for $atom {
+			#[inline]
+			fn wait(
+				&self,
+				expected: Self::Item,
+				timeout: Option<Duration>,
+			) -> bool {
+				table::wait(self, expected, timeout)
+			}
+
+			#[inline]
+			fn notify_one(&self) {
+				table::notify(self);
+			}
+
+			#[inline]
+			fn notify_all(&self) {
+				table::notify(self);
+			}
+		}
+	)+ };
+}
+
+#[cfg(target_has_atomic = "8")]
+table!(AtomicBool, AtomicI8, AtomicU8);
+
+#[cfg(target_has_atomic = "16")]
+table!(AtomicI16, AtomicU16);
+
+#[cfg(all(
+	target_has_atomic = "32",
+	not(any(target_os = "linux", target_os = "android")),
+))]
+table!(AtomicI32, AtomicU32);
+
+#[cfg(target_has_atomic = "64")]
+table!(AtomicI64, AtomicU64);
+
+#[cfg(all(target_arch = "x86_64", target_feature = "cmpxchg16b"))]
+table!(AtomicI128, AtomicU128);
+
+#[cfg(target_has_atomic = "ptr")]
+table!(AtomicIsize, AtomicUsize, @<T> AtomicPtr<T>);
+
+/// Implements `Wait` for the 32-bit atomics with the `futex` system call.
+#[cfg(all(
+	target_has_atomic = "32",
+	any(target_os = "linux", target_os = "android"),
+))]
+macro_rules! futex {
+	($($atom:ty),+ $(,)?) => { $(
+		impl Wait for $atom {
+			#[inline]
+			fn wait(
+				&self,
+				expected: Self::Item,
+				timeout: Option<Duration>,
+			) -> bool {
+				let expected = u32::from_ne_bytes(expected.to_ne_bytes());
+				futex::wait(futex::addr(self), expected, timeout)
+			}
+
+			#[inline]
+			fn notify_one(&self) {
+				futex::wake(futex::addr(self), 1);
+			}
+
+			#[inline]
+			fn notify_all(&self) {
+				futex::wake(futex::addr(self), i32::MAX);
+			}
+		}
+	)+ };
+}
+
+#[cfg(all(
+	target_has_atomic = "32",
+	any(target_os = "linux", target_os = "android"),
+))]
+futex!(AtomicI32, AtomicU32);
+
+#[cfg(target_has_atomic = "8")]
+impl<T> Wait for Locked<T>
+where
+	T: Nuclear + PartialEq,
+	Cell<T>: Radium<Item = T>,
+{
+	#[inline]
+	fn wait(&self, expected: T, timeout: Option<Duration>) -> bool {
+		table::wait(self, expected, timeout)
+	}
+
+	#[inline]
+	fn notify_one(&self) {
+		table::notify(self);
+	}
+
+	#[inline]
+	fn notify_all(&self) {
+		table::notify(self);
+	}
+}
+
+impl<T> Wait for Atom<T>
+where
+	T: Atomic,
+	T::Atom: Wait + Radium<Item = T>,
+{
+	#[inline]
+	fn wait(&self, expected: T, timeout: Option<Duration>) -> bool {
+		self.inner.wait(expected, timeout)
+	}
+
+	#[inline]
+	fn notify_one(&self) {
+		self.inner.notify_one();
+	}
+
+	#[inline]
+	fn notify_all(&self) {
+		self.inner.notify_all();
+	}
+}
+
+impl<T> Wait for Isotope<T>
+where
+	T: Nuclear,
+	T::Nucleus: Wait + Radium<Item = T>,
+{
+	#[inline]
+	fn wait(&self, expected: T, timeout: Option<Duration>) -> bool {
+		self.inner.wait(expected, timeout)
+	}
+
+	#[inline]
+	fn notify_one(&self) {
+		self.inner.notify_one();
+	}
+
+	#[inline]
+	fn notify_all(&self) {
+		self.inner.notify_all();
+	}
+}
+
+/// A `Cell` cannot be changed by another thread, so waiting on it only
+/// succeeds if the value already differs, or by running out the timeout.
+///
+/// ## Panics
+///
+/// `.wait()` panics if the value is `expected` and there is no timeout, as it
+/// would block forever.
+impl<T> Wait for Cell<T>
+where
+	T: PartialEq,
+	Cell<T>: Radium<Item = T>,
+{
+	#[inline]
+	fn wait(&self, expected: T, timeout: Option<Duration>) -> bool {
+		if self.load(Ordering::Relaxed) != expected {
+			return true;
+		}
+		match timeout {
+			Some(timeout) => {
+				thread::sleep(timeout);
+				false
+			},
+			None => panic!("waiting on an unchanged `Cell` would never return"),
+		}
+	}
+
+	#[inline]
+	fn notify_one(&self) {}
+
+	#[inline]
+	fn notify_all(&self) {}
+}
+
+impl<T> Wait for Radon<T>
+where
+	T: Nuclear + PartialEq,
+	Cell<T>: Radium<Item = T>,
+{
+	#[inline]
+	fn wait(&self, expected: T, timeout: Option<Duration>) -> bool {
+		self.inner.wait(expected, timeout)
+	}
+
+	#[inline]
+	fn notify_one(&self) {}
+
+	#[inline]
+	fn notify_all(&self) {}
+}
+
+/// Waiting with the `futex` system call.
+#[cfg(all(
+	target_has_atomic = "32",
+	any(target_os = "linux", target_os = "android"),
+))]
+mod futex {
+	use core::time::Duration;
+	use std::io;
+
+	/// Gets the address of a 32-bit atomic, as the system call expects.
+	pub(super) fn addr<A>(atom: &A) -> *const u32 {
+		(atom as *const A).cast()
+	}
+
+	/// Sleeps while `*addr == expected`.
+	///
+	/// Returns `false` if the timeout elapsed.
+	pub(super) fn wait(
+		addr: *const u32,
+		expected: u32,
+		timeout: Option<Duration>,
+	) -> bool {
+		let timeout = timeout.map(|dur| libc::timespec {
+			tv_sec:  dur.as_secs().try_into().unwrap_or(libc::time_t::MAX),
+			tv_nsec: dur.subsec_nanos() as _,
+		});
+		let timeout = timeout
+			.as_ref()
+			.map_or(core::ptr::null(), |ts| ts as *const libc::timespec);
+		let res = unsafe {
+			libc::syscall(
+				libc::SYS_futex,
+				addr,
+				libc::FUTEX_WAIT | libc::FUTEX_PRIVATE_FLAG,
+				expected,
+				timeout,
+			)
+		};
+		res == 0
+			|| io::Error::last_os_error().raw_os_error() != Some(libc::ETIMEDOUT)
+	}
+
+	/// Wakes up to `count` threads sleeping on `addr`.
+	pub(super) fn wake(addr: *const u32, count: i32) {
+		unsafe {
+			libc::syscall(
+				libc::SYS_futex,
+				addr,
+				libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
+				count,
+			);
+		}
+	}
+}
+
+/// Waiting on a shared table of condition variables.
+///
+/// Each value is assigned a slot by its address, so unrelated values can share
+/// a slot. Notifying a value therefore wakes every thread waiting on its slot,
+/// and the others go back to sleep when they see that their value is
+/// unchanged.
+mod table {
+	use super::*;
+
+	/// The number of slots in the table.
+	const SLOTS: usize = 64;
+
+	/// One slot in the table.
+	struct Slot {
+		/// Held while checking the value before sleeping, and while notifying,
+		/// so that notifications cannot be lost in between.
+		lock: Mutex<()>,
+		/// The waiting threads.
+		cond: Condvar,
+	}
+
+	//  The `std` feature requires Rust 1.63, which made these `const`.
+	#[allow(clippy::declare_interior_mutable_const, clippy::incompatible_msrv)]
+	const SLOT: Slot = Slot {
+		lock: Mutex::new(()),
+		cond: Condvar::new(),
+	};
+
+	static TABLE: [Slot; SLOTS] = [SLOT; SLOTS];
+
+	/// Finds the slot for a value.
+	fn slot<R>(radium: &R) -> &'static Slot {
+		let addr = radium as *const R as usize;
+		//  Discard the low bits, which are mostly alignment.
+		&TABLE[(addr >> 4) % SLOTS]
+	}
+
+	/// Locks a slot, ignoring poison, as it guards no data.
+	fn lock(slot: &Slot) -> MutexGuard<'_, ()> {
+		slot.lock.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+
+	/// Sleeps while the value is `expected`.
+	///
+	/// Returns `false` if the timeout elapsed.
+	pub(super) fn wait<R>(
+		radium: &R,
+		expected: R::Item,
+		timeout: Option<Duration>,
+	) -> bool
+	where
+		R: Radium,
+		R::Item: PartialEq,
+	{
+		let slot = slot(radium);
+		let guard = lock(slot);
+		if radium.load(Ordering::SeqCst) != expected {
+			return true;
+		}
+		match timeout {
+			Some(timeout) => {
+				let (guard, res) = slot
+					.cond
+					.wait_timeout(guard, timeout)
+					.unwrap_or_else(PoisonError::into_inner);
+				drop(guard);
+				!res.timed_out()
+			},
+			None => {
+				drop(slot.cond.wait(guard));
+				true
+			},
+		}
+	}
+
+	/// Wakes every thread sleeping on the value’s slot.
+	pub(super) fn notify<R>(radium: &R) {
+		let slot = slot(radium);
+		let _guard = lock(slot);
+		slot.cond.notify_all();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		sync::Arc,
+		time::Instant,
+	};
+
+	use static_assertions::*;
+
+	use super::*;
+	use crate::types::Shared;
+
+	/// Hands a value back and forth between two threads, waiting for each
+	/// change.
+	fn ping_pong<R>()
+	where R: 'static + Wait<Item = u32> + Send + Sync {
+		let shared = Arc::new(R::new(0));
+		let other = shared.clone();
+		let thread = thread::spawn(move || {
+			for round in (1 .. 100).step_by(2) {
+				while other.load(Ordering::SeqCst) != round {
+					other.wait(round - 1, None);
+				}
+				other.store(round + 1, Ordering::SeqCst);
+				other.notify_one();
+			}
+		});
+		for round in (0 .. 100).step_by(2) {
+			while shared.load(Ordering::SeqCst) != round {
+				shared.wait(round - 1, None);
+			}
+			shared.store(round + 1, Ordering::SeqCst);
+			shared.notify_all();
+		}
+		thread.join().unwrap();
+	}
+
+	#[test]
+	fn handoff() {
+		ping_pong::<AtomicU32>();
+		ping_pong::<Atom<u32>>();
+		ping_pong::<Isotope<u32>>();
+		ping_pong::<Locked<u32>>();
+		ping_pong::<Shared<u32>>();
+	}
+
+	#[test]
+	fn timeouts() {
+		let start = Instant::now();
+		let dur = Some(Duration::from_millis(10));
+
+		assert!(!AtomicU32::new(5).wait(5, dur));
+		assert!(!AtomicU64::new(5).wait(5, dur));
+		assert!(!Cell::new(5u8).wait(5, dur));
+		assert!(start.elapsed() >= Duration::from_millis(30));
+
+		assert!(AtomicU32::new(5).wait(4, dur));
+		assert!(AtomicBool::new(true).wait(false, None));
+		assert!(Radon::new(5i16).wait(4, None));
+	}
+
+	#[test]
+	#[should_panic = "waiting on an unchanged `Cell` would never return"]
+	fn cell_deadlock() {
+		Cell::new(0usize).wait(0, None);
+	}
+
+	#[test]
+	fn impls() {
+		assert_impl_all!(AtomicBool: Wait);
+		assert_impl_all!(AtomicI32: Wait);
+		assert_impl_all!(AtomicUsize: Wait);
+		assert_impl_all!(AtomicPtr<u8>: Wait);
+		assert_impl_all!(Atom<u16>: Wait);
+		assert_impl_all!(Isotope<i128>: Wait);
+		assert_impl_all!(Locked<u128>: Wait);
+		assert_impl_all!(Radon<u64>: Wait);
+		assert_impl_all!(Cell<*mut u8>: Wait);
+	}
+}
@@ -0,0 +1,357 @@
+//! Double-width atomics for targets with a 128-bit compare-exchange.
+//!
+//! `core` does not yet have stable `AtomicI128` or `AtomicU128` types. On
+//! `x86_64` targets built with the `cmpxchg16b` feature (for example, with
+//! `-C target-feature=+cmpxchg16b` or a `-C target-cpu` that has it), `radium`
+//! provides them itself, built on the `lock cmpxchg16b` instruction.
+//!
+//! Every operation, including `load` and `store`, is a compare-exchange, and so
+//! is sequentially consistent regardless of the `Ordering` it receives.
+
+#![cfg(all(target_arch = "x86_64", target_feature = "cmpxchg16b"))]
+
+use core::{
+	arch::asm,
+	cell::UnsafeCell,
+	cmp,
+	fmt::{
+		self,
+		Debug,
+		Formatter,
+	},
+	sync::atomic::Ordering,
+};
+
+/// Compares the 16 bytes at `dst` with `current`, and replaces them with `new`
+/// if they are equal. Values are passed as `(low, high)` halves.
+///
+/// Returns the previous value, and whether the replacement occurred.
+///
+/// ## Safety
+///
+/// `dst` must be valid for reads and writes, and aligned to 16 bytes.
+#[inline]
+unsafe fn cmpxchg16b(
+	dst: *mut u128,
+	current: (u64, u64),
+	new: (u64, u64),
+) -> ((u64, u64), bool) {
+	let (lo, hi, done): (u64, u64, u64);
+	//  LLVM reserves `rbx`, so the low half of `new` is swapped into it for the
+	//  duration of the instruction and the original value put back afterwards.
+	//  Only the low byte of `rcx` is set by `sete`.
+	asm!(
+		"xchg {tmp}, rbx",
+		"lock cmpxchg16b xmmword ptr [{dst}]",
+		"sete cl",
+		"mov rbx, {tmp}",
+		dst = in(reg) dst,
+		tmp = inout(reg) new.0 => _,
+		inout("rcx") new.1 => done,
+		inout("rax") current.0 => lo,
+		inout("rdx") current.1 => hi,
+		options(nostack),
+	);
+	((lo, hi), done as u8 != 0)
+}
+
+/// Generates the double-width atomic types.
+macro_rules! atomic128 {
+	($($atom:ident => $base:ident),+ $(,)?) => { $(
+		#[doc = concat!(
+			"An integer type which can be safely shared between threads.\n\n",
+			"This has the same in-memory representation as `",
+			stringify!($base),
+			"`, but is aligned to 16 bytes. It mirrors the API of the ",
+			"standard library’s atomic integers.",
+		)]
+		#[repr(C, align(16))]
+		pub struct $atom {
+			/// The shared value. It is only accessed through `cmpxchg16b`, or
+			/// through a unique reference.
+			inner: UnsafeCell<$base>,
+		}
+
+		/// All shared access goes through `cmpxchg16b`.
+		unsafe impl Sync for $atom {}
+
+		impl $atom {
+			/// Creates a new atomic integer.
+			#[inline]
+			pub const fn new(value: $base) -> Self {
+				Self {
+					inner: UnsafeCell::new(value),
+				}
+			}
+
+			/// Returns a mutable reference to the underlying integer.
+			#[inline]
+			pub fn get_mut(&mut self) -> &mut $base {
+				self.inner.get_mut()
+			}
+
+			/// Consumes the atomic and returns the contained value.
+			#[inline]
+			pub fn into_inner(self) -> $base {
+				self.inner.into_inner()
+			}
+
+			/// Loads a value from the atomic integer.
+			///
+			/// This compare-exchanges the value with itself, and so requires
+			/// write access to the underlying memory.
+			#[inline]
+			pub fn load(&self, _: Ordering) -> $base {
+				match self.cas(0, 0) {
+					Ok(value) | Err(value) => value,
+				}
+			}
+
+			/// Stores a value into the atomic integer.
+			#[inline]
+			pub fn store(&self, value: $base, order: Ordering) {
+				self.swap(value, order);
+			}
+
+			/// Stores a value into the atomic integer, returning the previous
+			/// value.
+			#[inline]
+			pub fn swap(&self, value: $base, _: Ordering) -> $base {
+				self.rmw(|_| value)
+			}
+
+			/// Stores a value into the atomic integer if the current value is
+			/// the same as `current`, returning the previous value.
+			#[inline]
+			#[deprecated = "Use `compare_exchange` instead"]
+			pub fn compare_and_swap(
+				&self,
+				current: $base,
+				new: $base,
+				_: Ordering,
+			) -> $base {
+				match self.cas(current, new) {
+					Ok(value) | Err(value) => value,
+				}
+			}
+
+			/// Stores a value into the atomic integer if the current value is
+			/// the same as `current`.
+			///
+			/// Returns `Ok` with the previous value if it was replaced, and
+			/// `Err` with the current value if it was not.
+			#[inline]
+			pub fn compare_exchange(
+				&self,
+				current: $base,
+				new: $base,
+				_: Ordering,
+				_: Ordering,
+			) -> Result<$base, $base> {
+				self.cas(current, new)
+			}
+
+			/// Stores a value into the atomic integer if the current value is
+			/// the same as `current`.
+			///
+			/// `cmpxchg16b` never fails spuriously, so this is the same as
+			/// [`.compare_exchange()`](Self::compare_exchange).
+			#[inline]
+			pub fn compare_exchange_weak(
+				&self,
+				current: $base,
+				new: $base,
+				success: Ordering,
+				failure: Ordering,
+			) -> Result<$base, $base> {
+				self.compare_exchange(current, new, success, failure)
+			}
+
+			/// Adds to the current value, wrapping around on overflow, and
+			/// returns the previous value.
+			#[inline]
+			pub fn fetch_add(&self, value: $base, _: Ordering) -> $base {
+				self.rmw(|old| old.wrapping_add(value))
+			}
+
+			/// Subtracts from the current value, wrapping around on overflow,
+			/// and returns the previous value.
+			#[inline]
+			pub fn fetch_sub(&self, value: $base, _: Ordering) -> $base {
+				self.rmw(|old| old.wrapping_sub(value))
+			}
+
+			/// Bitwise “and” with the current value, returning the previous
+			/// value.
+			#[inline]
+			pub fn fetch_and(&self, value: $base, _: Ordering) -> $base {
+				self.rmw(|old| old & value)
+			}
+
+			/// Bitwise “nand” with the current value, returning the previous
+			/// value.
+			#[inline]
+			pub fn fetch_nand(&self, value: $base, _: Ordering) -> $base {
+				self.rmw(|old| !(old & value))
+			}
+
+			/// Bitwise “or” with the current value, returning the previous
+			/// value.
+			#[inline]
+			pub fn fetch_or(&self, value: $base, _: Ordering) -> $base {
+				self.rmw(|old| old | value)
+			}
+
+			/// Bitwise “xor” with the current value, returning the previous
+			/// value.
+			#[inline]
+			pub fn fetch_xor(&self, value: $base, _: Ordering) -> $base {
+				self.rmw(|old| old ^ value)
+			}
+
+			/// Stores the maximum of the current value and `value`, returning
+			/// the previous value.
+			#[inline]
+			pub fn fetch_max(&self, value: $base, _: Ordering) -> $base {
+				self.rmw(|old| cmp::max(old, value))
+			}
+
+			/// Stores the minimum of the current value and `value`, returning
+			/// the previous value.
+			#[inline]
+			pub fn fetch_min(&self, value: $base, _: Ordering) -> $base {
+				self.rmw(|old| cmp::min(old, value))
+			}
+
+			/// Fetches the value, and applies a function to it that returns an
+			/// optional new value.
+			///
+			/// Returns `Ok(previous)` if the function returned `Some` and the
+			/// new value was stored, or `Err(previous)` if it returned `None`.
+			#[inline]
+			pub fn fetch_update<F>(
+				&self,
+				_: Ordering,
+				fetch_order: Ordering,
+				mut func: F,
+			) -> Result<$base, $base>
+			where
+				F: FnMut($base) -> Option<$base>,
+			{
+				let mut prev = self.load(fetch_order);
+				while let Some(next) = func(prev) {
+					match self.cas(prev, next) {
+						Ok(value) => return Ok(value),
+						Err(value) => prev = value,
+					}
+				}
+				Err(prev)
+			}
+
+			/// Replaces the value with `func` of it, retrying until no other
+			/// thread intervenes, and returns the replaced value.
+			#[inline]
+			fn rmw<F>(&self, mut func: F) -> $base
+			where F: FnMut($base) -> $base {
+				let mut prev = self.load(Ordering::Relaxed);
+				loop {
+					match self.cas(prev, func(prev)) {
+						Ok(value) => return value,
+						Err(value) => prev = value,
+					}
+				}
+			}
+
+			/// Runs `cmpxchg16b` on the value.
+			#[inline]
+			fn cas(&self, current: $base, new: $base) -> Result<$base, $base> {
+				let split = |value: $base| (value as u64, (value >> 64) as u64);
+				let dst = self.inner.get().cast::<u128>();
+				let (prev, done) =
+					unsafe { cmpxchg16b(dst, split(current), split(new)) };
+				let prev = prev.0 as $base | (prev.1 as $base) << 64;
+				if done { Ok(prev) } else { Err(prev) }
+			}
+		}
+
+		impl Debug for $atom {
+			#[inline]
+			fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+				Debug::fmt(&self.load(Ordering::Relaxed), fmt)
+			}
+		}
+
+		impl Default for $atom {
+			#[inline]
+			fn default() -> Self {
+				Self::new(0)
+			}
+		}
+
+		impl From<$base> for $atom {
+			#[inline]
+			fn from(value: $base) -> Self {
+				Self::new(value)
+			}
+		}
+	)+ };
+}
+
+atomic128! {
+	AtomicI128 => i128,
+	AtomicU128 => u128,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn unsigned() {
+		let num = AtomicU128::new(u64::MAX as u128);
+		assert_eq!(num.fetch_add(1, Ordering::SeqCst), u64::MAX as u128);
+		assert_eq!(num.load(Ordering::SeqCst), 1 << 64);
+		assert_eq!(num.swap(!0, Ordering::SeqCst), 1 << 64);
+		assert_eq!(num.fetch_and(!(1 << 127), Ordering::SeqCst), !0);
+		assert_eq!(num.fetch_nand(!0, Ordering::SeqCst), !0 >> 1);
+		assert_eq!(num.load(Ordering::SeqCst), 1 << 127);
+		assert_eq!(
+			num.compare_exchange(0, 5, Ordering::SeqCst, Ordering::SeqCst),
+			Err(1 << 127),
+		);
+		assert_eq!(
+			num.compare_exchange_weak(
+				1 << 127,
+				5,
+				Ordering::SeqCst,
+				Ordering::SeqCst,
+			),
+			Ok(1 << 127),
+		);
+		assert_eq!(num.fetch_max(3, Ordering::SeqCst), 5);
+		assert_eq!(num.fetch_min(3, Ordering::SeqCst), 5);
+		assert_eq!(num.fetch_xor(1 << 100, Ordering::SeqCst), 3);
+		assert_eq!(num.into_inner(), 1 << 100 | 3);
+	}
+
+	#[test]
+	fn signed() {
+		let mut num = AtomicI128::default();
+		assert_eq!(num.fetch_sub(1, Ordering::SeqCst), 0);
+		assert_eq!(num.fetch_max(i128::MIN, Ordering::SeqCst), -1);
+		assert_eq!(num.fetch_min(i128::MIN, Ordering::SeqCst), -1);
+		assert_eq!(num.fetch_or(1 << 64, Ordering::SeqCst), i128::MIN);
+		assert_eq!(*num.get_mut(), i128::MIN | 1 << 64);
+
+		num.store(-2, Ordering::SeqCst);
+		assert_eq!(
+			num.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n
+				.checked_mul(3)),
+			Ok(-2),
+		);
+		assert_eq!(
+			num.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |_| None),
+			Err(-6),
+		);
+	}
+}